@@ -0,0 +1,299 @@
+use crate::context::{Context, Properties, Target};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+impl Background {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+}
+
+/// Detects the background of the terminal from:
+/// - `STARSHIP_BACKGROUND`, which the init scripts set once with `starship background`
+/// - `COLORFGBG`, which some terminals set
+///
+/// This runs for every prompt, so the slower ways to find the background are left to
+/// `starship background`.
+pub fn detect(context: &Context) -> Option<Background> {
+    context
+        .get_env("STARSHIP_BACKGROUND")
+        .and_then(|background| Background::from_name(&background))
+        .or_else(|| {
+            context
+                .get_env("COLORFGBG")
+                .and_then(|colorfgbg| from_colorfgbg(&colorfgbg))
+        })
+}
+
+/// `COLORFGBG` holds the foreground and background colors as indexes of the 16 ANSI colors,
+/// e.g. `15;0`. Some terminals put a default color in between: `15;default;0`.
+fn from_colorfgbg(colorfgbg: &str) -> Option<Background> {
+    match colorfgbg.rsplit(';').next()?.parse::<u8>().ok()? {
+        0..=6 | 8 => Some(Background::Dark),
+        7 | 9..=15 => Some(Background::Light),
+        _ => None,
+    }
+}
+
+fn from_desktop(context: &Context, os: &str) -> Option<Background> {
+    match os {
+        // The setting doesn't exist in light mode
+        "macos" => match context.exec_cmd("defaults", &["read", "-g", "AppleInterfaceStyle"]) {
+            Some(output) if output.stdout.trim() == "Dark" => Some(Background::Dark),
+            _ => Some(Background::Light),
+        },
+        "windows" => {
+            let output = context.exec_cmd(
+                "reg",
+                &[
+                    "query",
+                    r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+                    "/v",
+                    "AppsUseLightTheme",
+                ],
+            )?;
+            let value = output.stdout.split_whitespace().last()?;
+            match value {
+                "0x0" => Some(Background::Dark),
+                "0x1" => Some(Background::Light),
+                _ => None,
+            }
+        }
+        _ => {
+            let output = context.exec_cmd(
+                "gsettings",
+                &["get", "org.gnome.desktop.interface", "color-scheme"],
+            )?;
+            match output.stdout.trim().trim_matches('\'') {
+                "prefer-dark" => Some(Background::Dark),
+                "prefer-light" | "default" => Some(Background::Light),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Prints `light` or `dark` depending on the background color the terminal reports, or on the
+/// dark mode setting of the desktop if it doesn't answer. The init scripts store the output in
+/// `STARSHIP_BACKGROUND`.
+pub fn print_background() {
+    let context = Context::new(Properties::default(), Target::Main);
+    let background = query_terminal().or_else(|| from_desktop(&context, std::env::consts::OS));
+    if let Some(background) = background {
+        println!("{}", background.name());
+    }
+}
+
+/// Asks the terminal for its background color with OSC 11
+///
+/// A terminal that answers after the timeout leaves its answer in the input of the shell, which
+/// is why this only runs once when the shell starts.
+#[cfg(unix)]
+fn query_terminal() -> Option<Background> {
+    use crossterm::terminal;
+    use nix::poll::{PollFd, PollFlags, PollTimeout, poll};
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::fd::AsFd;
+    use std::time::{Duration, Instant};
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    terminal::enable_raw_mode().ok()?;
+
+    // Every terminal answers the device attributes query that follows the OSC 11 query, so that
+    // terminals without OSC 11 support don't make us wait for the timeout. The answer is read
+    // byte by byte to leave anything typed after it in the input.
+    let mut response = Vec::new();
+    if tty
+        .write_all(b"\x1b]11;?\x1b\\\x1b[c")
+        .and_then(|()| tty.flush())
+        .is_ok()
+    {
+        let start = Instant::now();
+        let mut byte = [0];
+        while !is_complete(&response) {
+            let timeout = PollTimeout::try_from(TIMEOUT.saturating_sub(start.elapsed()))
+                .unwrap_or(PollTimeout::ZERO);
+            let mut fds = [PollFd::new(tty.as_fd(), PollFlags::POLLIN)];
+            if !poll(&mut fds, timeout).is_ok_and(|ready| ready > 0) {
+                break;
+            }
+            if !tty.read(&mut byte).is_ok_and(|read| read == 1) {
+                break;
+            }
+            response.push(byte[0]);
+        }
+    }
+
+    let _ = terminal::disable_raw_mode();
+    parse_background_color(&response)
+}
+
+#[cfg(not(unix))]
+fn query_terminal() -> Option<Background> {
+    None
+}
+
+/// Whether the response ends with the answer to the device attributes query, `ESC [ ? ... c`
+#[cfg(unix)]
+fn is_complete(response: &[u8]) -> bool {
+    response.ends_with(b"c")
+        && response
+            .windows(3)
+            .rposition(|window| window == b"\x1b[?")
+            .is_some_and(|start| {
+                response[start + 3..response.len() - 1]
+                    .iter()
+                    .all(|byte| byte.is_ascii_digit() || *byte == b';')
+            })
+}
+
+/// Reads the background color out of an OSC 11 response like `ESC ] 11 ; rgb:RRRR/GGGG/BBBB ESC \`
+fn parse_background_color(response: &[u8]) -> Option<Background> {
+    let response = String::from_utf8_lossy(response);
+    let start = response.find("rgb:")? + "rgb:".len();
+    let components = response[start..]
+        .split(['/', '\x1b', '\x07'])
+        .take(3)
+        .map(|component| {
+            // Each component has 1 to 4 hex digits
+            let max = match component.len() {
+                1..=4 => (1_u32 << (4 * component.len())) - 1,
+                _ => return None,
+            };
+            let value = u32::from_str_radix(component, 16).ok()?;
+            Some(f64::from(value) / f64::from(max))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let [red, green, blue] = components[..] else {
+        return None;
+    };
+
+    let luminance = 0.2126 * red + 0.7152 * green + 0.0722 * blue;
+    if luminance > 0.5 {
+        Some(Background::Light)
+    } else {
+        Some(Background::Dark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CommandOutput;
+
+    #[test]
+    fn parse_colorfgbg() {
+        assert_eq!(from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("0;default;7"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("15;default"), None);
+        assert_eq!(from_colorfgbg(""), None);
+    }
+
+    #[test]
+    fn parse_osc_11_response() {
+        assert_eq!(
+            parse_background_color(b"\x1b]11;rgb:ffff/ffff/dddd\x1b\\\x1b[?62;22c"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_background_color(b"\x1b]11;rgb:1e1e/1e1e/2e2e\x07"),
+            Some(Background::Dark)
+        );
+        assert_eq!(
+            parse_background_color(b"\x1b]11;rgb:f/f/f\x07"),
+            Some(Background::Light)
+        );
+        assert_eq!(parse_background_color(b"\x1b[?62;22c"), None);
+        assert_eq!(parse_background_color(b"\x1b]11;rgb:ffff/ffff\x07"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn response_is_complete() {
+        assert!(is_complete(b"\x1b]11;rgb:0/0/0\x07\x1b[?62;22c"));
+        assert!(is_complete(b"\x1b[?1;2c"));
+        assert!(!is_complete(b"\x1b]11;rgb:0/0/0\x07"));
+        assert!(!is_complete(b"\x1b]11;rgb:c"));
+    }
+
+    #[test]
+    fn detect_in_order() {
+        let mut context = Context::default();
+        context
+            .env
+            .insert("STARSHIP_BACKGROUND", "light".to_string());
+        context.env.insert("COLORFGBG", "15;0".to_string());
+        assert_eq!(detect(&context), Some(Background::Light));
+
+        context.env.insert("STARSHIP_BACKGROUND", String::new());
+        assert_eq!(detect(&context), Some(Background::Dark));
+
+        // The desktop setting is only looked up by `starship background`
+        context.env.insert("COLORFGBG", "default".to_string());
+        context.cmd.insert(
+            "gsettings get org.gnome.desktop.interface color-scheme",
+            Some(CommandOutput {
+                stdout: "'prefer-dark'\n".to_string(),
+                stderr: String::new(),
+            }),
+        );
+        assert_eq!(detect(&context), None);
+    }
+
+    #[test]
+    fn detect_desktop_setting() {
+        let mut context = Context::default();
+        context.cmd.insert(
+            "defaults read -g AppleInterfaceStyle",
+            Some(CommandOutput {
+                stdout: "Dark\n".to_string(),
+                stderr: String::new(),
+            }),
+        );
+        context.cmd.insert(
+            "gsettings get org.gnome.desktop.interface color-scheme",
+            Some(CommandOutput {
+                stdout: "'prefer-light'\n".to_string(),
+                stderr: String::new(),
+            }),
+        );
+        context.cmd.insert(
+            r"reg query HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize /v AppsUseLightTheme",
+            Some(CommandOutput {
+                stdout: "\r\n    AppsUseLightTheme    REG_DWORD    0x0\r\n".to_string(),
+                stderr: String::new(),
+            }),
+        );
+        assert_eq!(from_desktop(&context, "macos"), Some(Background::Dark));
+        assert_eq!(from_desktop(&context, "linux"), Some(Background::Light));
+        assert_eq!(from_desktop(&context, "windows"), Some(Background::Dark));
+
+        context
+            .cmd
+            .insert("defaults read -g AppleInterfaceStyle", None);
+        assert_eq!(from_desktop(&context, "macos"), Some(Background::Light));
+    }
+}
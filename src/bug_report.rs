@@ -1,10 +1,19 @@
+use crate::config::ModuleConfig;
+use crate::configs::FullConfig;
+use crate::configure;
+use crate::context::Context;
+use crate::init;
+use crate::logger;
+use crate::print;
 use crate::shadow;
 use crate::utils::{self, exec_cmd};
 use nu_ansi_term::Style;
+use regex::Regex;
 
 use std::fs;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 pub fn create() {
     println!("{}\n", shadow::VERSION.trim());
@@ -54,6 +63,235 @@ pub fn create() {
     println!("Thanks for using the Starship bug report tool!");
 }
 
+/// Matches the config keys and environment variables that are redacted from bug report bundles
+pub const DEFAULT_REDACT_PATTERN: &str =
+    r"(?i)token|secret|passw(or)?d|credential|api_?key|auth|session_key";
+
+/// Environment variables read by starship that are included in bug report bundles, in addition
+/// to those starting with `STARSHIP_`
+const BUNDLE_ENV_VARS: &[&str] = &[
+    "SHELL",
+    "SHLVL",
+    "TERM",
+    "TERM_PROGRAM",
+    "TERM_PROGRAM_VERSION",
+    "LC_TERMINAL",
+    "LC_TERMINAL_VERSION",
+    "COLORTERM",
+    "NO_COLOR",
+    "COLUMNS",
+    "LANG",
+    "LC_ALL",
+    "HOME",
+    "PWD",
+    "USER",
+    "LOGNAME",
+    "TMUX",
+    "SSH_CONNECTION",
+    "PROMPT_COMMAND",
+    "VIRTUAL_ENV",
+    "CONDA_DEFAULT_ENV",
+    "KUBECONFIG",
+    "AWS_PROFILE",
+    "AWS_REGION",
+    "PATH",
+];
+
+/// Number of the most recent log files included in bug report bundles
+const BUNDLE_LOG_FILES: usize = 20;
+
+const REDACTED: &str = "<redacted>";
+
+/// Writes a bug report bundle that can be passed on without network access
+///
+/// The bundle is a tar archive if `output` ends with `.tar`, and a directory otherwise.
+pub fn create_bundle(output: &Path, redact_pattern: &str) {
+    let redact = match Regex::new(redact_pattern) {
+        Ok(redact) => redact,
+        Err(error) => {
+            eprintln!("Invalid redaction pattern {redact_pattern:?}: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    let files = collect_bundle(&Context::default(), &redact);
+    let result = if output.extension().is_some_and(|ext| ext == "tar") {
+        write_bundle_tar(output, &files)
+    } else {
+        write_bundle_dir(output, &files)
+    };
+
+    match result {
+        Ok(()) => println!(
+            "Wrote the bug report bundle to {}. Only the config and environment variables are redacted, review the module output in explain.txt, timings.txt and the logs before sharing it.",
+            output.display()
+        ),
+        Err(error) => {
+            eprintln!(
+                "Unable to write the bug report bundle to {}: {error}",
+                output.display()
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Collects the files of a bug report bundle, as paths relative to the bundle and contents
+fn collect_bundle(context: &Context, redact: &Regex) -> Vec<(PathBuf, Vec<u8>)> {
+    let mut user_config = toml::Value::Table(configure::get_configuration(context));
+    redact_toml(&mut user_config, redact);
+    let mut effective_config = toml::Value::try_from(FullConfig::load(&user_config))
+        .unwrap_or(toml::Value::Table(toml::Table::new()));
+    redact_toml(&mut effective_config, redact);
+
+    let os_info = os_info::get();
+    let environment = Environment {
+        os_type: os_info.os_type(),
+        os_version: os_info.version().clone(),
+        shell_info: get_shell_info(),
+        terminal_info: get_terminal_info(),
+        starship_config: toml::to_string_pretty(&user_config).unwrap_or_default(),
+    };
+
+    let mut files = vec![
+        (
+            PathBuf::from("report.md"),
+            get_github_issue_body(&environment).into_bytes(),
+        ),
+        (
+            PathBuf::from("config.toml"),
+            toml::to_string_pretty(&effective_config)
+                .unwrap_or_default()
+                .into_bytes(),
+        ),
+        (
+            PathBuf::from("explain.txt"),
+            print::get_explain(context).into_bytes(),
+        ),
+        (
+            PathBuf::from("timings.txt"),
+            print::get_timings(context).into_bytes(),
+        ),
+        (
+            PathBuf::from("env.txt"),
+            bundle_env(context, redact).into_bytes(),
+        ),
+    ];
+
+    let shell = &environment.shell_info.name;
+    match init::main_script(shell) {
+        Ok(Some(script)) => files.push((
+            PathBuf::from("init").join(shell),
+            format!("# starship {}\n{script}", shadow::PKG_VERSION).into_bytes(),
+        )),
+        Ok(None) => {}
+        Err(error) => log::warn!("Unable to render the init script of {shell}: {error}"),
+    }
+
+    for path in recent_log_files(&logger::get_log_dir(), BUNDLE_LOG_FILES) {
+        match (path.file_name(), fs::read(&path)) {
+            (Some(name), Ok(content)) => files.push((Path::new("logs").join(name), content)),
+            (_, Err(error)) => log::warn!("Unable to read log file {}: {error}", path.display()),
+            _ => {}
+        }
+    }
+
+    files
+}
+
+/// Replaces the string values of keys matching `redact`, at any depth
+fn redact_toml(value: &mut toml::Value, redact: &Regex) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if redact.is_match(key) && !matches!(value, toml::Value::Table(_)) {
+                    *value = toml::Value::String(REDACTED.to_string());
+                } else {
+                    redact_toml(value, redact);
+                }
+            }
+        }
+        toml::Value::Array(values) => values.iter_mut().for_each(|v| redact_toml(v, redact)),
+        _ => {}
+    }
+}
+
+/// Lists the environment variables read by starship as `NAME=value` lines
+fn bundle_env(context: &Context, redact: &Regex) -> String {
+    let mut names: Vec<String> = std::env::vars_os()
+        .filter_map(|(name, _)| name.into_string().ok())
+        .filter(|name| name.starts_with("STARSHIP_"))
+        .chain(BUNDLE_ENV_VARS.iter().map(|name| (*name).to_string()))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let value = context.get_env(&name)?;
+            let value = if redact.is_match(&name) {
+                REDACTED
+            } else {
+                &value
+            };
+            Some(format!("{name}={value}\n"))
+        })
+        .collect()
+}
+
+/// Returns the `limit` most recently modified log files in `log_dir`
+///
+/// The prompt arguments recorded for `starship init --check` are left out, as the log and
+/// cache directories are the same by default.
+fn recent_log_files(log_dir: &Path, limit: usize) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(log_dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .filter(|entry| !init::is_prompt_record(&entry.path()))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((metadata.modified().ok()?, entry.path())).filter(|_| metadata.is_file())
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files
+        .into_iter()
+        .take(limit)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+fn write_bundle_dir(output: &Path, files: &[(PathBuf, Vec<u8>)]) -> io::Result<()> {
+    for (path, content) in files {
+        let path = output.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+fn write_bundle_tar(output: &Path, files: &[(PathBuf, Vec<u8>)]) -> io::Result<()> {
+    let mtime = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let mut builder = tar::Builder::new(fs::File::create(output)?);
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(&mut header, path, content.as_slice())?;
+    }
+    builder.into_inner()?.sync_all()
+}
+
 const UNKNOWN_SHELL: &str = "<unknown shell>";
 const UNKNOWN_TERMINAL: &str = "<unknown terminal>";
 const UNKNOWN_VERSION: &str = "<unknown version>";
@@ -152,13 +390,13 @@ fn make_github_issue_link(body: &str) -> String {
 }
 
 #[derive(Debug)]
-struct ShellInfo {
-    name: String,
-    version: String,
-    config: String,
+pub struct ShellInfo {
+    pub name: String,
+    pub version: String,
+    pub config: String,
 }
 
-fn get_shell_info() -> ShellInfo {
+pub fn get_shell_info() -> ShellInfo {
     let shell = std::env::var("STARSHIP_SHELL");
     if shell.is_err() {
         return ShellInfo {
@@ -207,7 +445,7 @@ fn get_terminal_info() -> TerminalInfo {
     }
 }
 
-fn get_config_path(shell: &str) -> Option<PathBuf> {
+pub fn get_config_path(shell: &str) -> Option<PathBuf> {
     if shell == "nu" {
         return dirs::config_dir().map(|config_dir| config_dir.join("nushell").join("config.nu"));
     }
@@ -228,6 +466,11 @@ fn get_config_path(shell: &str) -> Option<PathBuf> {
             "elvish" => Some(".elvish/rc.elv"),
             "tcsh" => Some(".tcshrc"),
             "xonsh" => Some(".xonshrc"),
+            "osh" => Some(".config/oils/oshrc"),
+            "ysh" => Some(".config/oils/yshrc"),
+            "ksh" => Some(".kshrc"),
+            "mksh" => Some(".mkshrc"),
+            "murex" => Some(".murex_profile"),
             "cmd" => Some("AppData/Local/clink/starship.lua"),
             _ => None,
         }
@@ -299,6 +542,79 @@ mod tests {
         assert!(link.contains("No+Starship+config"));
     }
 
+    #[test]
+    fn test_redact_toml() {
+        let mut config = toml::toml! {
+            format = "$all"
+            [custom.api]
+            command = "curl"
+            env_token = "abc"
+            [custom.api.headers]
+            Authorization = "Bearer abc"
+        }
+        .into();
+        redact_toml(&mut config, &Regex::new(DEFAULT_REDACT_PATTERN).unwrap());
+
+        let expected: toml::Value = toml::toml! {
+            format = "$all"
+            [custom.api]
+            command = "curl"
+            env_token = "<redacted>"
+            [custom.api.headers]
+            Authorization = "<redacted>"
+        }
+        .into();
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn test_write_bundle() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let files = vec![
+            (PathBuf::from("report.md"), b"report".to_vec()),
+            (PathBuf::from("logs/session_1.log"), b"log".to_vec()),
+        ];
+
+        let bundle_dir = dir.path().join("bundle");
+        write_bundle_dir(&bundle_dir, &files)?;
+        assert_eq!(fs::read(bundle_dir.join("logs/session_1.log"))?, b"log");
+
+        let bundle_tar = dir.path().join("bundle.tar");
+        write_bundle_tar(&bundle_tar, &files)?;
+        let mut archive = tar::Archive::new(fs::File::open(&bundle_tar)?);
+        let paths = archive
+            .entries()?
+            .map(|entry| entry.and_then(|entry| Ok(entry.path()?.into_owned())))
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("report.md"),
+                PathBuf::from("logs/session_1.log")
+            ]
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn test_recent_log_files_skip_prompt_records() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for name in [
+            "session_0123456789abcdef.log",
+            "session_0123456789abcdef_prompt.log",
+            "session_0123456789abcdef_rprompt.log",
+            "timings.txt",
+        ] {
+            fs::write(dir.path().join(name), "")?;
+        }
+
+        assert_eq!(
+            recent_log_files(dir.path(), BUNDLE_LOG_FILES),
+            [dir.path().join("session_0123456789abcdef.log")]
+        );
+        dir.close()
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_get_config_path() {
@@ -0,0 +1,551 @@
+use nu_ansi_term::Color;
+
+use crate::config::parse_color_string;
+use crate::configs::Palette;
+use crate::context::Context;
+
+/// The colors a terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+/// Returns the color depth set by `color_depth`, detecting it from `COLORTERM` and `TERM` if it
+/// is `auto`
+pub fn color_depth(context: &Context) -> ColorDepth {
+    match context.root_config.color_depth.as_str() {
+        "truecolor" => ColorDepth::TrueColor,
+        "256" => ColorDepth::Ansi256,
+        "16" => ColorDepth::Ansi16,
+        depth => {
+            if depth != "auto" {
+                log::warn!("Unknown color_depth {depth:?}, expected auto, truecolor, 256 or 16");
+            }
+            detect_color_depth(
+                context.get_env("COLORTERM").as_deref(),
+                context.get_env("TERM").as_deref(),
+            )
+        }
+    }
+}
+
+fn detect_color_depth(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+    if matches!(colorterm, Some("truecolor" | "24bit")) {
+        return ColorDepth::TrueColor;
+    }
+    // Windows terminals don't set `TERM`
+    let Some(term) = term else {
+        return ColorDepth::TrueColor;
+    };
+    // Terminals whose `TERM` reaches remote hosts, unlike `COLORTERM`
+    const TRUECOLOR_TERMS: &[&str] = &["kitty", "alacritty", "wezterm", "ghostty", "foot"];
+    if term.ends_with("-direct") || TRUECOLOR_TERMS.iter().any(|name| term.contains(name)) {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Ansi256
+    } else {
+        ColorDepth::Ansi16
+    }
+}
+
+/// Converts the color to the closest one the terminal can show
+pub fn downsample(color: Color, depth: ColorDepth) -> Color {
+    match (depth, color) {
+        (ColorDepth::TrueColor, _) => color,
+        (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Fixed(closest_fixed((r, g, b))),
+        (ColorDepth::Ansi256, _) => color,
+        (ColorDepth::Ansi16, Color::Fixed(index)) if index < 16 => ANSI_COLORS[usize::from(index)],
+        (ColorDepth::Ansi16, Color::Rgb(..) | Color::Fixed(_)) => {
+            let rgb = to_rgb(color);
+            let closest = (0..16)
+                .min_by_key(|index| distance(rgb, fixed_to_rgb(*index)))
+                .unwrap_or(0);
+            ANSI_COLORS[usize::from(closest)]
+        }
+        (ColorDepth::Ansi16, _) => color,
+    }
+}
+
+/// The 16 ANSI colors in the order of their indexes
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Purple,
+    Color::Cyan,
+    Color::White,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightPurple,
+    Color::LightCyan,
+    Color::LightGray,
+];
+
+/// The values of the 16 ANSI colors in xterm
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Levels of each channel in the 6x6x6 color cube of the 256 colors
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn fixed_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_RGB[usize::from(index)],
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[usize::from(index / 36)],
+                CUBE_LEVELS[usize::from(index / 6 % 6)],
+                CUBE_LEVELS[usize::from(index % 6)],
+            )
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// The closest color of the color cube or the grayscale ramp of the 256 colors, whose values
+/// don't depend on the theme of the terminal unlike those of the first 16
+fn closest_fixed(rgb: (u8, u8, u8)) -> u8 {
+    let cube_index = |value: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| value.abs_diff(**level))
+            .map_or(0, |(index, _)| index as u8)
+    };
+    let cube = 16 + 36 * cube_index(rgb.0) + 6 * cube_index(rgb.1) + cube_index(rgb.2);
+
+    let average = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    if distance(rgb, fixed_to_rgb(gray)) < distance(rgb, fixed_to_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| u32::from(a.abs_diff(b)).pow(2);
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+/// The RGB value of the color, using the xterm values for the ANSI colors
+fn to_rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Fixed(index) => fixed_to_rgb(index),
+        Color::Default => ANSI_RGB[7],
+        color => ANSI_COLORS
+            .iter()
+            .position(|ansi| *ansi == color)
+            .map_or(ANSI_RGB[7], |index| ANSI_RGB[index]),
+    }
+}
+
+/// Parses the color functions `rgb(r, g, b)`, `hsl(h, s%, l%)`, `lighten(color, amount%)`,
+/// `darken(color, amount%)` and `mix(color, color, weight%)`. Colors in the arguments are looked
+/// up in `palette`.
+pub(crate) fn parse_color_function(color_string: &str, palette: Option<&Palette>) -> Option<Color> {
+    let (name, arguments) = color_string.strip_suffix(')')?.split_once('(')?;
+    let name = name.trim();
+    let mut arguments = split_arguments(arguments);
+    // CSS also allows separating the components with spaces
+    if arguments.len() == 1 && matches!(name, "rgb" | "hsl") {
+        arguments = arguments[0].split_whitespace().collect();
+    }
+    let color = |argument: &str| parse_color_string(argument, palette).map(to_rgb);
+
+    match (name, arguments.as_slice()) {
+        ("rgb", [r, g, b]) => {
+            let channel = |value: &str| match value.strip_suffix('%') {
+                Some(percent) => Some(to_channel(percent.parse::<f64>().ok()? / 100.0)),
+                None => value.parse::<u8>().ok(),
+            };
+            Some(Color::Rgb(channel(r)?, channel(g)?, channel(b)?))
+        }
+        ("hsl", [h, s, l]) => {
+            let hue = h.strip_suffix("deg").unwrap_or(h).parse::<f64>().ok()?;
+            Some(from_hsl(hue, parse_amount(s)?, parse_amount(l)?))
+        }
+        ("lighten", [base, amount]) => {
+            let (h, s, l) = to_hsl(color(base)?);
+            Some(from_hsl(h, s, l + parse_amount(amount)?))
+        }
+        ("darken", [base, amount]) => {
+            let (h, s, l) = to_hsl(color(base)?);
+            Some(from_hsl(h, s, l - parse_amount(amount)?))
+        }
+        ("mix", [a, b]) => Some(mix(color(a)?, color(b)?, 0.5)),
+        ("mix", [a, b, weight]) => Some(mix(color(a)?, color(b)?, parse_amount(weight)?)),
+        _ => None,
+    }
+}
+
+/// Splits the arguments of a color function on the commas that aren't in nested functions
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                split.push(arguments[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(arguments[start..].trim());
+    split
+}
+
+/// Parses a percentage, with or without `%`, as a fraction
+fn parse_amount(amount: &str) -> Option<f64> {
+    let percent = amount
+        .strip_suffix('%')
+        .unwrap_or(amount)
+        .parse::<f64>()
+        .ok()?;
+    percent.is_finite().then_some(percent / 100.0)
+}
+
+fn to_channel(fraction: f64) -> u8 {
+    (fraction.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn mix(a: (u8, u8, u8), b: (u8, u8, u8), weight: f64) -> Color {
+    let weight = weight.clamp(0.0, 1.0);
+    let channel =
+        |a: u8, b: u8| to_channel((f64::from(a) * weight + f64::from(b) * (1.0 - weight)) / 255.0);
+    Color::Rgb(channel(a.0, b.0), channel(a.1, b.1), channel(a.2, b.2))
+}
+
+/// Converts the color to hue in degrees, and saturation and lightness as fractions
+fn to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (
+        f64::from(r) / 255.0,
+        f64::from(g) / 255.0,
+        f64::from(b) / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, saturation, lightness)
+}
+
+fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Color {
+    let hue = hue.rem_euclid(360.0);
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match hue {
+        h if h < 60.0 => (chroma, x, 0.0),
+        h if h < 120.0 => (x, chroma, 0.0),
+        h if h < 180.0 => (0.0, chroma, x),
+        h if h < 240.0 => (0.0, x, chroma),
+        h if h < 300.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Color::Rgb(to_channel(r + m), to_channel(g + m), to_channel(b + m))
+}
+
+/// Looks up a CSS named color. The names of the 8 ANSI colors refer to the colors of the terminal
+/// instead, as they are parsed first.
+pub(crate) fn css_color(name: &str) -> Option<Color> {
+    CSS_COLORS
+        .binary_search_by_key(&name, |(css_name, _)| css_name)
+        .ok()
+        .map(|index| {
+            let (r, g, b) = CSS_COLORS[index].1;
+            Color::Rgb(r, g, b)
+        })
+}
+
+/// The CSS named colors, sorted by name
+const CSS_COLORS: &[(&str, (u8, u8, u8))] = &[
+    ("aliceblue", (240, 248, 255)),
+    ("antiquewhite", (250, 235, 215)),
+    ("aqua", (0, 255, 255)),
+    ("aquamarine", (127, 255, 212)),
+    ("azure", (240, 255, 255)),
+    ("beige", (245, 245, 220)),
+    ("bisque", (255, 228, 196)),
+    ("black", (0, 0, 0)),
+    ("blanchedalmond", (255, 235, 205)),
+    ("blue", (0, 0, 255)),
+    ("blueviolet", (138, 43, 226)),
+    ("brown", (165, 42, 42)),
+    ("burlywood", (222, 184, 135)),
+    ("cadetblue", (95, 158, 160)),
+    ("chartreuse", (127, 255, 0)),
+    ("chocolate", (210, 105, 30)),
+    ("coral", (255, 127, 80)),
+    ("cornflowerblue", (100, 149, 237)),
+    ("cornsilk", (255, 248, 220)),
+    ("crimson", (220, 20, 60)),
+    ("cyan", (0, 255, 255)),
+    ("darkblue", (0, 0, 139)),
+    ("darkcyan", (0, 139, 139)),
+    ("darkgoldenrod", (184, 134, 11)),
+    ("darkgray", (169, 169, 169)),
+    ("darkgreen", (0, 100, 0)),
+    ("darkgrey", (169, 169, 169)),
+    ("darkkhaki", (189, 183, 107)),
+    ("darkmagenta", (139, 0, 139)),
+    ("darkolivegreen", (85, 107, 47)),
+    ("darkorange", (255, 140, 0)),
+    ("darkorchid", (153, 50, 204)),
+    ("darkred", (139, 0, 0)),
+    ("darksalmon", (233, 150, 122)),
+    ("darkseagreen", (143, 188, 143)),
+    ("darkslateblue", (72, 61, 139)),
+    ("darkslategray", (47, 79, 79)),
+    ("darkslategrey", (47, 79, 79)),
+    ("darkturquoise", (0, 206, 209)),
+    ("darkviolet", (148, 0, 211)),
+    ("deeppink", (255, 20, 147)),
+    ("deepskyblue", (0, 191, 255)),
+    ("dimgray", (105, 105, 105)),
+    ("dimgrey", (105, 105, 105)),
+    ("dodgerblue", (30, 144, 255)),
+    ("firebrick", (178, 34, 34)),
+    ("floralwhite", (255, 250, 240)),
+    ("forestgreen", (34, 139, 34)),
+    ("fuchsia", (255, 0, 255)),
+    ("gainsboro", (220, 220, 220)),
+    ("ghostwhite", (248, 248, 255)),
+    ("gold", (255, 215, 0)),
+    ("goldenrod", (218, 165, 32)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("greenyellow", (173, 255, 47)),
+    ("grey", (128, 128, 128)),
+    ("honeydew", (240, 255, 240)),
+    ("hotpink", (255, 105, 180)),
+    ("indianred", (205, 92, 92)),
+    ("indigo", (75, 0, 130)),
+    ("ivory", (255, 255, 240)),
+    ("khaki", (240, 230, 140)),
+    ("lavender", (230, 230, 250)),
+    ("lavenderblush", (255, 240, 245)),
+    ("lawngreen", (124, 252, 0)),
+    ("lemonchiffon", (255, 250, 205)),
+    ("lightblue", (173, 216, 230)),
+    ("lightcoral", (240, 128, 128)),
+    ("lightcyan", (224, 255, 255)),
+    ("lightgoldenrodyellow", (250, 250, 210)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgreen", (144, 238, 144)),
+    ("lightgrey", (211, 211, 211)),
+    ("lightpink", (255, 182, 193)),
+    ("lightsalmon", (255, 160, 122)),
+    ("lightseagreen", (32, 178, 170)),
+    ("lightskyblue", (135, 206, 250)),
+    ("lightslategray", (119, 136, 153)),
+    ("lightslategrey", (119, 136, 153)),
+    ("lightsteelblue", (176, 196, 222)),
+    ("lightyellow", (255, 255, 224)),
+    ("lime", (0, 255, 0)),
+    ("limegreen", (50, 205, 50)),
+    ("linen", (250, 240, 230)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("mediumaquamarine", (102, 205, 170)),
+    ("mediumblue", (0, 0, 205)),
+    ("mediumorchid", (186, 85, 211)),
+    ("mediumpurple", (147, 112, 219)),
+    ("mediumseagreen", (60, 179, 113)),
+    ("mediumslateblue", (123, 104, 238)),
+    ("mediumspringgreen", (0, 250, 154)),
+    ("mediumturquoise", (72, 209, 204)),
+    ("mediumvioletred", (199, 21, 133)),
+    ("midnightblue", (25, 25, 112)),
+    ("mintcream", (245, 255, 250)),
+    ("mistyrose", (255, 228, 225)),
+    ("moccasin", (255, 228, 181)),
+    ("navajowhite", (255, 222, 173)),
+    ("navy", (0, 0, 128)),
+    ("oldlace", (253, 245, 230)),
+    ("olive", (128, 128, 0)),
+    ("olivedrab", (107, 142, 35)),
+    ("orange", (255, 165, 0)),
+    ("orangered", (255, 69, 0)),
+    ("orchid", (218, 112, 214)),
+    ("palegoldenrod", (238, 232, 170)),
+    ("palegreen", (152, 251, 152)),
+    ("paleturquoise", (175, 238, 238)),
+    ("palevioletred", (219, 112, 147)),
+    ("papayawhip", (255, 239, 213)),
+    ("peachpuff", (255, 218, 185)),
+    ("peru", (205, 133, 63)),
+    ("pink", (255, 192, 203)),
+    ("plum", (221, 160, 221)),
+    ("powderblue", (176, 224, 230)),
+    ("purple", (128, 0, 128)),
+    ("rebeccapurple", (102, 51, 153)),
+    ("red", (255, 0, 0)),
+    ("rosybrown", (188, 143, 143)),
+    ("royalblue", (65, 105, 225)),
+    ("saddlebrown", (139, 69, 19)),
+    ("salmon", (250, 128, 114)),
+    ("sandybrown", (244, 164, 96)),
+    ("seagreen", (46, 139, 87)),
+    ("seashell", (255, 245, 238)),
+    ("sienna", (160, 82, 45)),
+    ("silver", (192, 192, 192)),
+    ("skyblue", (135, 206, 235)),
+    ("slateblue", (106, 90, 205)),
+    ("slategray", (112, 128, 144)),
+    ("slategrey", (112, 128, 144)),
+    ("snow", (255, 250, 250)),
+    ("springgreen", (0, 255, 127)),
+    ("steelblue", (70, 130, 180)),
+    ("tan", (210, 180, 140)),
+    ("teal", (0, 128, 128)),
+    ("thistle", (216, 191, 216)),
+    ("tomato", (255, 99, 71)),
+    ("turquoise", (64, 224, 208)),
+    ("violet", (238, 130, 238)),
+    ("wheat", (245, 222, 179)),
+    ("white", (255, 255, 255)),
+    ("whitesmoke", (245, 245, 245)),
+    ("yellow", (255, 255, 0)),
+    ("yellowgreen", (154, 205, 50)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css_colors_are_sorted() {
+        assert!(CSS_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(CSS_COLORS.len(), 148);
+        assert_eq!(css_color("rebeccapurple"), Some(Color::Rgb(102, 51, 153)));
+        assert_eq!(css_color("notacolor"), None);
+    }
+
+    #[test]
+    fn color_functions() {
+        assert_eq!(
+            parse_color_function("rgb(10, 20, 30)", None),
+            Some(Color::Rgb(10, 20, 30))
+        );
+        assert_eq!(
+            parse_color_function("rgb(100% 0% 50%)", None),
+            Some(Color::Rgb(255, 0, 128))
+        );
+        assert_eq!(
+            parse_color_function("hsl(210, 50%, 40%)", None),
+            Some(Color::Rgb(51, 102, 153))
+        );
+        assert_eq!(
+            parse_color_function("lighten(#336699, 10%)", None),
+            Some(Color::Rgb(64, 128, 191))
+        );
+        assert_eq!(
+            parse_color_function("darken(rgb(51,102,153), 100%)", None),
+            Some(Color::Rgb(0, 0, 0))
+        );
+        assert_eq!(
+            parse_color_function("mix(#000000, #ffffff, 25%)", None),
+            Some(Color::Rgb(191, 191, 191))
+        );
+
+        let mut palette = Palette::new();
+        palette.insert("accent".to_string(), "#336699".to_string());
+        assert_eq!(
+            parse_color_function("mix(accent, #ffffff)", Some(&palette)),
+            Some(Color::Rgb(153, 179, 204))
+        );
+
+        assert_eq!(parse_color_function("rgb(1, 2)", None), None);
+        assert_eq!(parse_color_function("rgb(256, 0, 0)", None), None);
+        assert_eq!(parse_color_function("lighten(notacolor, 10%)", None), None);
+        assert_eq!(parse_color_function("blur(#000000)", None), None);
+    }
+
+    #[test]
+    fn detect_depth() {
+        let detect = detect_color_depth;
+        assert_eq!(
+            detect(Some("truecolor"), Some("xterm")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(None, None), ColorDepth::TrueColor);
+        assert_eq!(detect(None, Some("xterm-kitty")), ColorDepth::TrueColor);
+        assert_eq!(detect(None, Some("xterm-direct")), ColorDepth::TrueColor);
+        assert_eq!(detect(None, Some("screen-256color")), ColorDepth::Ansi256);
+        assert_eq!(detect(Some(""), Some("screen")), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn downsample_colors() {
+        let rgb = Color::Rgb(0x1e, 0x3c, 0xdc);
+        assert_eq!(downsample(rgb, ColorDepth::TrueColor), rgb);
+        assert_eq!(downsample(rgb, ColorDepth::Ansi256), Color::Fixed(26));
+        assert_eq!(downsample(rgb, ColorDepth::Ansi16), Color::Blue);
+        assert_eq!(
+            downsample(Color::Rgb(0x80, 0x80, 0x80), ColorDepth::Ansi256),
+            Color::Fixed(244)
+        );
+        assert_eq!(
+            downsample(Color::Rgb(255, 0, 0), ColorDepth::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(
+            downsample(Color::Fixed(208), ColorDepth::Ansi16),
+            Color::Yellow
+        );
+        assert_eq!(
+            downsample(Color::Fixed(9), ColorDepth::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(downsample(Color::Green, ColorDepth::Ansi16), Color::Green);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::color;
 use crate::configs::Palette;
 use crate::context::Context;
 
@@ -363,11 +364,41 @@ impl From<nu_ansi_term::Color> for Style {
  - '<color>'       (see the `parse_color_string` doc for valid color strings)
 */
 pub fn parse_style_string(style_string: &str, context: Option<&Context>) -> Option<Style> {
+    let palette = context.and_then(Context::palette);
+    let style = parse_style_with_palette(style_string, palette)?;
+    let Some(context) = context else {
+        return Some(style);
+    };
+
+    // Convert the colors to ones the terminal can show
+    let depth = color::color_depth(context);
+    Some(style.map_style(|style| nu_ansi_term::Style {
+        foreground: style.foreground.map(|c| color::downsample(c, depth)),
+        background: style.background.map(|c| color::downsample(c, depth)),
+        ..*style
+    }))
+}
+
+/// Splits a style string on the whitespace outside of color functions like `rgb(1, 2, 3)`
+pub(crate) fn style_tokens(style_string: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0_usize;
     style_string
-        .split_whitespace()
-        .try_fold(Style::default(), |style, token| {
-            let token = token.to_lowercase();
+        .split(move |c: char| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            depth == 0 && c.is_whitespace()
+        })
+        .filter(|token| !token.is_empty())
+}
 
+/// Parse a style string like `parse_style_string`, looking up custom colors in `palette`
+pub fn parse_style_with_palette(style_string: &str, palette: Option<&Palette>) -> Option<Style> {
+    style_tokens(style_string)
+        .map(str::to_lowercase)
+        .try_fold(Style::default(), |style, token| {
             // Check for FG/BG identifiers and strip them off if appropriate
             // If col_fg is true, color the foreground. If it's false, color the background.
             let (token, col_fg) = if token.as_str().starts_with("fg:") {
@@ -401,15 +432,7 @@ pub fn parse_style_string(style_string: &str, context: Option<&Context>) -> Opti
                         None // fg:none yields no style.
                     } else {
                         // Either bg or valid color or both.
-                        let parsed = parse_color_string(
-                            color_string,
-                            context.and_then(|x| {
-                                get_palette(
-                                    &x.root_config.palettes,
-                                    x.root_config.palette.as_deref(),
-                                )
-                            }),
-                        );
+                        let parsed = parse_color_string(color_string, palette);
                         // bg + invalid color = reset the background to default.
                         if !col_fg && parsed.is_none() {
                             let mut new_style = style;
@@ -432,12 +455,13 @@ pub fn parse_style_string(style_string: &str, context: Option<&Context>) -> Opti
 }
 
 /** Parse a string that represents a color setting, returning None if this fails
- There are three valid color formats:
-  - #RRGGBB      (a hash followed by an RGB hex)
+ The valid color formats are:
+  - #RRGGBB      (a hash followed by an RGB hex, or the short #RGB)
   - u8           (a number from 0-255, representing an ANSI color)
-  - colstring    (one of the 16 predefined color strings or a custom user-defined color)
+  - colstring    (a custom user-defined color, one of the 16 predefined color strings or a CSS color name)
+  - function     (`rgb()`, `hsl()`, `lighten()`, `darken()` or `mix()`, see `color::parse_color_function`)
 */
-fn parse_color_string(
+pub(crate) fn parse_color_string(
     color_string: &str,
     palette: Option<&Palette>,
 ) -> Option<nu_ansi_term::Color> {
@@ -445,13 +469,24 @@ fn parse_color_string(
     log::trace!("Parsing color_string: {color_string}");
     if color_string.starts_with('#') {
         log::trace!("Attempting to read hexadecimal color string: {color_string}");
-        if color_string.len() != 7 {
-            log::debug!("Could not parse hexadecimal string: {color_string}");
-            return None;
-        }
-        let r: u8 = u8::from_str_radix(&color_string[1..3], 16).ok()?;
-        let g: u8 = u8::from_str_radix(&color_string[3..5], 16).ok()?;
-        let b: u8 = u8::from_str_radix(&color_string[5..7], 16).ok()?;
+        let hex = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        let (r, g, b) = match color_string.len() {
+            7 => (
+                hex(color_string.get(1..3)?)?,
+                hex(color_string.get(3..5)?)?,
+                hex(color_string.get(5..7)?)?,
+            ),
+            // #RGB is short for #RRGGBB
+            4 => (
+                hex(color_string.get(1..2)?)? * 17,
+                hex(color_string.get(2..3)?)? * 17,
+                hex(color_string.get(3..4)?)? * 17,
+            ),
+            _ => {
+                log::debug!("Could not parse hexadecimal string: {color_string}");
+                return None;
+            }
+        };
         log::trace!("Read RGB color string: {r},{g},{b}");
         return Some(Color::Rgb(r, g, b));
     }
@@ -468,6 +503,16 @@ fn parse_color_string(
         return parse_color_string(palette_color, None);
     }
 
+    if color_string.ends_with(')') {
+        let color = color::parse_color_function(color_string, palette);
+        if color.is_some() {
+            log::trace!("Read color function: {color_string}");
+        } else {
+            log::debug!("Could not parse color function: {color_string}");
+        }
+        return color;
+    }
+
     // Check for any predefined color strings
     // There are no predefined enums for bright colors, so we use Color::Fixed
     let predefined_color = match color_string.to_lowercase().as_str() {
@@ -487,7 +532,7 @@ fn parse_color_string(
         "bright-purple" => Some(Color::LightPurple),
         "bright-cyan" => Some(Color::LightCyan),
         "bright-white" => Some(Color::LightGray),
-        _ => None,
+        name => color::css_color(name),
     };
 
     if predefined_color.is_some() {
@@ -498,22 +543,45 @@ fn parse_color_string(
     predefined_color
 }
 
-fn get_palette<'a>(
-    palettes: &'a HashMap<String, Palette>,
+/// Returns the palette with the colors of the palettes it `extends`, which it overrides
+pub(crate) fn get_palette(
+    palettes: &HashMap<String, Palette>,
     palette_name: Option<&str>,
-) -> Option<&'a Palette> {
-    if let Some(palette_name) = palette_name {
-        let palette = palettes.get(palette_name);
-        if palette.is_some() {
-            log::trace!("Found color palette: {palette_name}");
-        } else {
-            log::warn!("Could not find color palette: {palette_name}");
-        }
-        palette
-    } else {
+) -> Option<Palette> {
+    let Some(palette_name) = palette_name else {
         log::trace!("No color palette specified, using defaults");
-        None
+        return None;
+    };
+
+    let mut palette: Option<Palette> = None;
+    let mut chain = vec![palette_name];
+    let mut name = palette_name;
+    loop {
+        let Some(current) = palettes.get(name) else {
+            log::warn!("Could not find color palette: {name}");
+            break;
+        };
+        log::trace!("Found color palette: {name}");
+        let resolved = palette.get_or_insert_with(Palette::new);
+        for (color_name, color) in current {
+            if color_name != "extends" && !resolved.contains_key(color_name) {
+                resolved.insert(color_name.clone(), color.clone());
+            }
+        }
+
+        match current.get("extends") {
+            Some(parent) if chain.contains(&parent.as_str()) => {
+                log::warn!("Color palette {palette_name} extends itself through {parent}");
+                break;
+            }
+            Some(parent) => {
+                chain.push(parent);
+                name = parent;
+            }
+            None => break,
+        }
     }
+    palette
 }
 
 #[cfg(test)]
@@ -1051,6 +1119,142 @@ mod tests {
         assert!(get_palette(&palettes, None).is_none());
     }
 
+    #[test]
+    fn table_get_palette_extends() {
+        let palettes: HashMap<String, Palette> = toml::from_str(
+            r##"
+            base = { fg = "#ffffff", accent = "blue" }
+            mine = { extends = "base", accent = "red" }
+            loop_a = { extends = "loop_b", a = "red" }
+            loop_b = { extends = "loop_a", b = "blue" }
+            "##,
+        )
+        .unwrap();
+
+        let mine = get_palette(&palettes, Some("mine")).unwrap();
+        assert_eq!(mine.get("fg").unwrap(), "#ffffff");
+        assert_eq!(mine.get("accent").unwrap(), "red");
+        assert!(!mine.contains_key("extends"));
+
+        let looped = get_palette(&palettes, Some("loop_a")).unwrap();
+        assert_eq!(looped.len(), 2);
+    }
+
+    #[test]
+    fn palette_follows_background() {
+        let config = toml::toml! {
+            palette = "fallback"
+            palette_light = "light"
+            palette_dark = "dark"
+            [palettes.fallback]
+            accent = "green"
+            [palettes.light]
+            accent = "black"
+            [palettes.dark]
+            accent = "white"
+        };
+        let style = |background: Option<&str>| {
+            let mut context = Context::default().set_config(config.clone());
+            if let Some(background) = background {
+                context
+                    .env
+                    .insert("STARSHIP_BACKGROUND", background.to_string());
+            }
+            parse_style_string("accent", Some(&context))
+        };
+
+        assert_eq!(style(Some("light")), Some(Color::Black.into()));
+        assert_eq!(style(Some("dark")), Some(Color::White.into()));
+        assert_eq!(style(None), Some(Color::Green.into()));
+    }
+
+    #[test]
+    fn table_get_colors_css() {
+        assert_eq!(
+            parse_color_string("#36c", None),
+            Some(Color::Rgb(0x33, 0x66, 0xcc))
+        );
+        assert_eq!(parse_color_string("#36", None), None);
+        assert_eq!(
+            parse_color_string("rebeccapurple", None),
+            Some(Color::Rgb(102, 51, 153))
+        );
+        // The ANSI color names refer to the colors of the terminal
+        assert_eq!(parse_color_string("red", None), Some(Color::Red));
+        assert_eq!(
+            parse_color_string("hsl(0, 100%, 50%)", None),
+            Some(Color::Rgb(255, 0, 0))
+        );
+    }
+
+    #[test]
+    fn table_get_styles_color_functions() {
+        let mut palette = Palette::new();
+        palette.insert("accent".to_string(), "lighten(#336699, 10%)".to_string());
+        assert_eq!(
+            parse_style_with_palette("bold fg:rgb(1, 2, 3) bg:accent", Some(&palette)),
+            Some(
+                AnsiStyle::new()
+                    .bold()
+                    .fg(Color::Rgb(1, 2, 3))
+                    .on(Color::Rgb(64, 128, 191))
+                    .into()
+            )
+        );
+        assert_eq!(
+            parse_style_with_palette("fg:mix(#000, #fff, 25%) underline", None),
+            Some(
+                AnsiStyle::new()
+                    .fg(Color::Rgb(191, 191, 191))
+                    .underline()
+                    .into()
+            )
+        );
+        assert_eq!(parse_style_with_palette("rgb(1, 2", None), None);
+    }
+
+    #[test]
+    fn styles_follow_color_depth() {
+        let style = |color_depth: &str, term: &str| {
+            let mut context = Context::default().set_config(toml::toml! {
+                color_depth = color_depth
+            });
+            context.env.insert("TERM", term.to_string());
+            parse_style_string("fg:#1e3cdc bg:208 bold", Some(&context))
+        };
+
+        assert_eq!(
+            style("auto", "xterm-kitty"),
+            Some(
+                AnsiStyle::new()
+                    .fg(Color::Rgb(0x1e, 0x3c, 0xdc))
+                    .on(Color::Fixed(208))
+                    .bold()
+                    .into()
+            )
+        );
+        assert_eq!(
+            style("auto", "xterm-256color"),
+            Some(
+                AnsiStyle::new()
+                    .fg(Color::Fixed(26))
+                    .on(Color::Fixed(208))
+                    .bold()
+                    .into()
+            )
+        );
+        assert_eq!(
+            style("16", "xterm-kitty"),
+            Some(
+                AnsiStyle::new()
+                    .fg(Color::Blue)
+                    .on(Color::Yellow)
+                    .bold()
+                    .into()
+            )
+        );
+    }
+
     #[test]
     fn read_config_no_config_file_path_provided() {
         assert_eq!(
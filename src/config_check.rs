@@ -0,0 +1,780 @@
+use nu_ansi_term::Color;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{get_palette, parse_color_string, parse_style_with_palette, style_tokens};
+use crate::configs::env_var::EnvVarConfig;
+use crate::configs::{FullConfig, Palette, StarshipRootConfig};
+use crate::context::Context;
+use crate::formatter::model::{FormatElement, StyleElement};
+use crate::formatter::{StyleVariableHolder, VariableHolder, parser};
+use crate::module::{ALL_MODULES, Variable};
+use crate::modules;
+use crate::serde_utils::{UnknownKeys, ValueDeserializer, closest_match};
+
+/// Root config keys whose format strings render modules
+const MODULE_FORMATS: &[&str] = &[
+    "format",
+    "right_format",
+    "continuation_prompt",
+    "transient_format",
+    "title_format",
+    "title_preexec_format",
+];
+
+/// Variables of `version_format`, see `VersionFormatter`
+const VERSION_VARIABLES: &[Variable] = &[
+    Variable::new("raw", "The version as it was detected"),
+    Variable::new("major", "The major part of the version"),
+    Variable::new("minor", "The minor part of the version"),
+    Variable::new("patch", "The patch part of the version"),
+];
+
+/// Variables of `auto_disabled_format`
+const AUTO_DISABLED_VARIABLES: &[Variable] = &[Variable::new(
+    "name",
+    "The name of the auto-disabled module",
+)];
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    /// Dotted path of the key the diagnostic is about, empty for the whole config
+    pub(crate) path: String,
+    pub(crate) message: String,
+}
+
+impl Diagnostic {
+    fn new(path: &str, message: String) -> Self {
+        Self {
+            path: path.to_string(),
+            message,
+        }
+    }
+}
+
+/// Lints the config, returning the problems found in the order they were found
+pub(crate) fn diagnose(config: &toml::Table) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let unknown_keys = UnknownKeys::default();
+    let deserializer = ValueDeserializer::new(config).with_unknown_keys(&unknown_keys, "");
+    let root = StarshipRootConfig::deserialize(deserializer).unwrap_or_else(|error| {
+        diagnostics.push(Diagnostic::new("", error.to_string()));
+        StarshipRootConfig::default()
+    });
+
+    for (key, value) in config {
+        if key == "env_var" {
+            // `env_var` holds both a top-level module and named modules in sub-tables
+            let (named, top_level): (toml::Table, toml::Table) = value
+                .as_table()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .partition(|(_, value)| value.is_table());
+            if !top_level.is_empty() {
+                let deserializer =
+                    ValueDeserializer::new(&top_level).with_unknown_keys(&unknown_keys, key);
+                if let Err(error) = EnvVarConfig::deserialize(deserializer) {
+                    diagnostics.push(Diagnostic::new(key, error.to_string()));
+                }
+            }
+            lint_module(key, named.into(), &unknown_keys, &mut diagnostics);
+        } else if ALL_MODULES.contains(&key.as_str()) || key == "custom" {
+            lint_module(key, value.clone(), &unknown_keys, &mut diagnostics);
+        }
+    }
+
+    for unknown_key in unknown_keys.into_inner() {
+        let did_you_mean = unknown_key
+            .suggestion
+            .map(|suggestion| format!(", did you mean `{suggestion}`?"))
+            .unwrap_or_default();
+        diagnostics.push(Diagnostic::new(
+            &unknown_key.path,
+            format!("unknown key{did_you_mean}"),
+        ));
+    }
+
+    let selected_palettes = [
+        ("palette", &root.palette),
+        ("palette_light", &root.palette_light),
+        ("palette_dark", &root.palette_dark),
+    ];
+    for (key, name) in selected_palettes {
+        if let Some(name) = name
+            .as_ref()
+            .filter(|name| !root.palettes.contains_key(*name))
+        {
+            diagnostics.push(Diagnostic::new(
+                key,
+                format!("the palette `{name}` is not defined in `palettes`"),
+            ));
+        }
+    }
+    if !["auto", "truecolor", "256", "16"].contains(&root.color_depth.as_str()) {
+        diagnostics.push(Diagnostic::new(
+            "color_depth",
+            format!(
+                "unknown color depth `{}`, expected `auto`, `truecolor`, `256` or `16`",
+                root.color_depth
+            ),
+        ));
+    }
+    for (name, palette) in &root.palettes {
+        for (color_name, color) in palette {
+            if color_name == "extends" {
+                if !root.palettes.contains_key(color) {
+                    diagnostics.push(Diagnostic::new(
+                        &format!("palettes.{name}.extends"),
+                        format!("the palette `{color}` is not defined in `palettes`"),
+                    ));
+                }
+            } else if parse_color_string(color, None).is_none() {
+                diagnostics.push(Diagnostic::new(
+                    &format!("palettes.{name}.{color_name}"),
+                    format!("invalid color `{color}`"),
+                ));
+            }
+        }
+    }
+
+    // Styles have to work with each palette that may be selected
+    let mut palettes: Vec<(&str, Option<Palette>)> = Vec::new();
+    for name in selected_palettes
+        .iter()
+        .filter_map(|(_, name)| name.as_deref())
+    {
+        if !palettes.iter().any(|(other, _)| *other == name) {
+            palettes.push((name, get_palette(&root.palettes, Some(name))));
+        }
+    }
+    let linter = Linter { config, palettes };
+    linter.lint_strings(&toml::Value::Table(config.clone()), "", &mut diagnostics);
+
+    diagnostics
+}
+
+/// Deserializes the config of the module at `key` like `FullConfig` does, to check its types
+/// and collect its unknown keys
+fn lint_module(
+    key: &str,
+    value: toml::Value,
+    unknown_keys: &UnknownKeys,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let config = toml::Table::from_iter([(key.to_string(), value)]);
+    let deserializer = ValueDeserializer::new(&config).with_unknown_keys(unknown_keys, "");
+    if let Err(error) = FullConfig::deserialize(deserializer) {
+        diagnostics.push(Diagnostic::new(key, error.to_string()));
+    }
+}
+
+struct Linter<'a> {
+    config: &'a toml::Table,
+    /// The palettes that may be selected, by name
+    palettes: Vec<(&'a str, Option<Palette>)>,
+}
+
+impl Linter<'_> {
+    /// Checks the styles and format strings in `value`, found at `path`
+    fn lint_strings(&self, value: &toml::Value, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, value) in table {
+                    // Palettes are checked on their own, as they contain colors instead of styles
+                    if path.is_empty() && key == "palettes" {
+                        continue;
+                    }
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    self.lint_strings(value, &path, diagnostics);
+                }
+            }
+            toml::Value::Array(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    self.lint_strings(value, &format!("{path}[{index}]"), diagnostics);
+                }
+            }
+            toml::Value::String(string) => {
+                let key = path.rsplit('.').next().unwrap_or(path);
+                if key == "style" || key.ends_with("_style") {
+                    self.lint_style(string, path, diagnostics);
+                } else if let Some(scope) = format_scope(path) {
+                    self.lint_format(string, path, &scope, diagnostics);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lint_style(&self, style: &str, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+        for token in style_tokens(style) {
+            let lowercase = token.to_lowercase();
+            let color = lowercase
+                .strip_prefix("fg:")
+                .or_else(|| lowercase.strip_prefix("bg:"))
+                .unwrap_or(&lowercase);
+            if color == "none" {
+                continue;
+            }
+            if self.palettes.is_empty() {
+                if parse_style_with_palette(color, None).is_none() {
+                    diagnostics.push(Diagnostic::new(
+                        path,
+                        format!("`{token}` in the style `{style}` is not a style"),
+                    ));
+                }
+                continue;
+            }
+            if let Some((name, _)) = self
+                .palettes
+                .iter()
+                .find(|(_, palette)| parse_style_with_palette(color, palette.as_ref()).is_none())
+            {
+                diagnostics.push(Diagnostic::new(
+                    path,
+                    format!(
+                        "`{token}` in the style `{style}` is not a style or color of the palette `{name}`"
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn lint_format(
+        &self,
+        format: &str,
+        path: &str,
+        scope: &Scope,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let elements = match parser::parse(format) {
+            Ok(elements) => elements,
+            Err(error) => {
+                let (line, column) = match error.line_col {
+                    pest::error::LineColLocation::Pos(position)
+                    | pest::error::LineColLocation::Span(position, _) => position,
+                };
+                diagnostics.push(Diagnostic::new(
+                    path,
+                    format!(
+                        "the format string can't be parsed at line {line}, column {column}: {}",
+                        error.variant.message()
+                    ),
+                ));
+                return;
+            }
+        };
+
+        // Styles with variables are only known when the module renders
+        for style in literal_styles(&elements) {
+            self.lint_style(&style, path, diagnostics);
+        }
+
+        let Scope::Variables(known) = scope else {
+            for variable in elements
+                .get_variables()
+                .into_iter()
+                .chain(elements.get_style_variables())
+            {
+                if !self.is_module(&variable, path) {
+                    let did_you_mean = closest_match(&variable, ALL_MODULES)
+                        .map(|module| format!(", did you mean `${module}`?"))
+                        .unwrap_or_default();
+                    diagnostics.push(Diagnostic::new(
+                        path,
+                        format!("`${variable}` is not a module{did_you_mean}"),
+                    ));
+                }
+            }
+            return;
+        };
+
+        // Variables of the sub-format strings of a module are not provided to the others
+        let key = path.rsplit('.').next().unwrap_or(path);
+        let known: Vec<&Variable> = known.iter().filter(|known| known.is_in(key)).collect();
+        let styles = elements.get_style_variables();
+        for (variable, in_style) in elements
+            .get_variables()
+            .into_iter()
+            .map(|variable| (variable, false))
+            .chain(styles.into_iter().map(|variable| (variable, true)))
+        {
+            let message = match known.iter().find(|known| known.name == variable) {
+                Some(known) if known.style && !in_style => {
+                    format!("`${variable}` can only be used in style strings")
+                }
+                Some(known) if !known.style && in_style => {
+                    format!("`${variable}` can't be used in style strings")
+                }
+                Some(_) => continue,
+                None => {
+                    let names: Vec<&'static str> = known
+                        .iter()
+                        .filter(|known| known.style == in_style)
+                        .map(|known| known.name)
+                        .collect();
+                    let did_you_mean = closest_match(&variable, &names)
+                        .map(|name| format!(", did you mean `${name}`?"))
+                        .unwrap_or_default();
+                    format!("unknown variable `${variable}`{did_you_mean}")
+                }
+            };
+            diagnostics.push(Diagnostic::new(path, message));
+        }
+    }
+
+    /// Whether `variable` of a root format string at `path` renders a module
+    fn is_module(&self, variable: &str, path: &str) -> bool {
+        if let Some((parent, child)) = variable.split_once('.') {
+            return matches!(parent, "custom" | "env_var")
+                && self
+                    .config
+                    .get(parent)
+                    .and_then(|modules| modules.get(child))
+                    .is_some_and(toml::Value::is_table);
+        }
+        ALL_MODULES.contains(&variable)
+            || matches!(variable, "all" | "custom" | "env_var")
+            || (path == "title_preexec_format" && variable == "command")
+    }
+}
+
+/// What the variables of a format string refer to
+enum Scope {
+    /// Modules, in the root format strings
+    Root,
+    /// The variables of a module, or of a format string with its own variables
+    Variables(&'static [Variable]),
+}
+
+/// Returns what the variables of the string at `path` refer to, if it is a format string
+fn format_scope(path: &str) -> Option<Scope> {
+    let segments: Vec<&str> = path.split('.').collect();
+    match segments.as_slice() {
+        [key] if MODULE_FORMATS.contains(key) => Some(Scope::Root),
+        ["profiles", _] => Some(Scope::Root),
+        ["auto_disabled_format"] => Some(Scope::Variables(AUTO_DISABLED_VARIABLES)),
+        [.., "time_format"] => None,
+        [.., "version_format"] => Some(Scope::Variables(VERSION_VARIABLES)),
+        [.., key] if !is_format_key(key) => None,
+        [module, _, ..]
+            if ALL_MODULES.contains(module) || matches!(*module, "custom" | "env_var") =>
+        {
+            Some(Scope::Variables(modules::variables(module)))
+        }
+        _ => None,
+    }
+}
+
+fn is_format_key(key: &str) -> bool {
+    key == "format" || key == "format_compact" || key.ends_with("_format")
+}
+
+/// Returns the style strings of `elements` that don't contain variables
+fn literal_styles(elements: &[FormatElement]) -> Vec<String> {
+    elements
+        .iter()
+        .flat_map(|element| match element {
+            FormatElement::TextGroup(group) => {
+                let mut styles = literal_styles(&group.format);
+                if group.style.get_style_variables().is_empty() {
+                    styles.push(
+                        group
+                            .style
+                            .iter()
+                            .map(|element| match element {
+                                StyleElement::Text(text) | StyleElement::Variable(text) => {
+                                    text.as_ref()
+                                }
+                            })
+                            .collect(),
+                    );
+                }
+                styles
+            }
+            FormatElement::Conditional(format) => literal_styles(format),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Returns the line and column of the key at the dotted `path` in `document`, if it exists
+fn locate(
+    content: &str,
+    document: &toml_edit::Document<&str>,
+    path: &str,
+) -> Option<(usize, usize)> {
+    let mut item = document.as_item();
+    let mut offset = None;
+    for segment in path.split('.') {
+        let mut indices = segment.split('[');
+        let key = indices.next()?;
+        let (key, value) = item.as_table_like()?.get_key_value(key)?;
+        offset = key.span().map(|span| span.start).or(offset);
+        item = value;
+        for index in indices {
+            item = item.get(index.trim_end_matches(']').parse::<usize>().ok()?)?;
+            offset = item.span().map(|span| span.start).or(offset);
+        }
+    }
+
+    let before = &content[..offset?];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or(before).chars().count() + 1;
+    Some((line, column))
+}
+
+/// Prints the problems of the config file at `path`, or of the current config file, and exits
+/// with a non-zero status if there are errors
+pub fn check(path: Option<PathBuf>) {
+    let explicit = path.is_some();
+    let Some(path) = path.or_else(|| Context::default().get_config_path_os().map(PathBuf::from))
+    else {
+        eprintln!("Unable to determine the path of the config file");
+        std::process::exit(1);
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(error) if !explicit && error.kind() == std::io::ErrorKind::NotFound => {
+            println!("No config file found at {}", path.display());
+            return;
+        }
+        Err(error) => {
+            eprintln!("Unable to read {}: {error}", path.display());
+            std::process::exit(1);
+        }
+    };
+
+    let (document, config) = match toml_edit::Document::parse(content.as_str())
+        .map_err(|error| error.to_string())
+        .and_then(|document| {
+            toml::from_str::<toml::Table>(&content)
+                .map(|config| (document, config))
+                .map_err(|error| error.to_string())
+        }) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            println!(" {} {}: {error}", Color::Red.paint("✘"), path.display());
+            std::process::exit(1);
+        }
+    };
+
+    let mut diagnostics: Vec<_> = diagnose(&config)
+        .into_iter()
+        .map(|diagnostic| {
+            let location = locate(&content, &document, &diagnostic.path);
+            (location, diagnostic)
+        })
+        .collect();
+    diagnostics.sort_by_key(|(location, _)| location.unwrap_or((usize::MAX, 0)));
+
+    print_diagnostics(&path, &diagnostics);
+}
+
+fn print_diagnostics(path: &Path, diagnostics: &[(Option<(usize, usize)>, Diagnostic)]) {
+    for (location, diagnostic) in diagnostics {
+        let location = location
+            .map(|(line, column)| format!(":{line}:{column}"))
+            .unwrap_or_default();
+        let key = if diagnostic.path.is_empty() {
+            String::new()
+        } else {
+            format!(" {}:", diagnostic.path)
+        };
+        println!(
+            " {} {}{location}{key} {}",
+            Color::Red.paint("✘"),
+            path.display(),
+            diagnostic.message
+        );
+    }
+
+    if diagnostics.is_empty() {
+        println!(
+            " {} {} has no problems",
+            Color::Green.paint("✔"),
+            path.display()
+        );
+    } else {
+        println!("\n {} problem(s) found", diagnostics.len());
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(config: toml::Table) -> Vec<(String, String)> {
+        diagnose(&config)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.path, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn valid_config() {
+        let config = toml::toml! {
+            format = "$directory${custom.foo}$git_branch$character"
+            palette = "mine"
+            [palettes.mine]
+            accent = "#336699"
+            [directory]
+            style = "bold fg:accent bg:none"
+            format = "[$path]($style)[$read_only]($read_only_style) "
+            [git_branch]
+            format = "[$symbol$branch(:$remote_branch)](bold accent) "
+            [nodejs]
+            version_format = "v${major}"
+            [custom.foo]
+            command = "echo foo"
+            format = "[$output]($style)"
+            [env_var]
+            variable = "SHELL"
+            [env_var.USER]
+            format = "$env_value"
+        };
+        assert_eq!(messages(config), []);
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let config = toml::toml! {
+            fromat = "$all"
+            [git_stauts]
+            disabled = true
+            [git_status]
+            stahsed = "$count"
+            [custom.foo]
+            comand = "echo foo"
+            [env_var]
+            varaible = "SHELL"
+        };
+        assert_eq!(
+            messages(config),
+            [
+                (
+                    "fromat".to_string(),
+                    "unknown key, did you mean `format`?".to_string()
+                ),
+                (
+                    "git_stauts".to_string(),
+                    "unknown key, did you mean `git_status`?".to_string()
+                ),
+                (
+                    "git_status.stahsed".to_string(),
+                    "unknown key, did you mean `stashed`?".to_string()
+                ),
+                (
+                    "custom.foo.comand".to_string(),
+                    "unknown key, did you mean `command`?".to_string()
+                ),
+                (
+                    "env_var.varaible".to_string(),
+                    "unknown key, did you mean `variable`?".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_types() {
+        let config = toml::toml! {
+            [git_branch]
+            disabled = "yes"
+        };
+        let diagnostics = messages(config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, "git_branch");
+    }
+
+    #[test]
+    fn invalid_styles_and_palettes() {
+        let config = toml::toml! {
+            palette = "missing"
+            [palettes.mine]
+            accent = "#3366"
+            [directory]
+            style = "bold fg:accnt"
+            format = "[$path](italic redd)"
+        };
+        assert_eq!(
+            messages(config),
+            [
+                (
+                    "palette".to_string(),
+                    "the palette `missing` is not defined in `palettes`".to_string()
+                ),
+                (
+                    "palettes.mine.accent".to_string(),
+                    "invalid color `#3366`".to_string()
+                ),
+                (
+                    "directory.style".to_string(),
+                    "`fg:accnt` in the style `bold fg:accnt` is not a style or color of the palette `missing`".to_string()
+                ),
+                (
+                    "directory.format".to_string(),
+                    "`redd` in the style `italic redd` is not a style or color of the palette `missing`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn light_and_dark_palettes() {
+        let config = toml::toml! {
+            palette_light = "light"
+            palette_dark = "dark"
+            [palettes.base]
+            fg = "black"
+            [palettes.light]
+            extends = "base"
+            accent = "blue"
+            [palettes.dark]
+            extends = "missing"
+            [directory]
+            style = "fg bold"
+            format = "[$path](accent)"
+        };
+        assert_eq!(
+            messages(config),
+            [
+                (
+                    "palettes.dark.extends".to_string(),
+                    "the palette `missing` is not defined in `palettes`".to_string()
+                ),
+                (
+                    "directory.style".to_string(),
+                    "`fg` in the style `fg bold` is not a style or color of the palette `dark`"
+                        .to_string()
+                ),
+                (
+                    "directory.format".to_string(),
+                    "`accent` in the style `accent` is not a style or color of the palette `dark`"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn color_functions_and_depth() {
+        let config = toml::toml! {
+            color_depth = "88"
+            [palettes.mine]
+            accent = "lighten(#336699, 10%)"
+            muted = "mix(#336699, grey)"
+            broken = "rgb(1, 2)"
+            [directory]
+            style = "bold fg:rgb(1, 2, 3) bg:hsl(210, 50%, 40%)"
+        };
+        assert_eq!(
+            messages(config),
+            [
+                (
+                    "color_depth".to_string(),
+                    "unknown color depth `88`, expected `auto`, `truecolor`, `256` or `16`"
+                        .to_string()
+                ),
+                (
+                    "palettes.mine.broken".to_string(),
+                    "invalid color `rgb(1, 2)`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_formats() {
+        let config = toml::toml! {
+            format = "$directroy$custom"
+            [directory]
+            format = "[$path]($style"
+            [nodejs]
+            format = "[$versoin$style]($symbol)"
+            version_format = "$version"
+        };
+        assert_eq!(
+            messages(config),
+            [
+                (
+                    "format".to_string(),
+                    "`$directroy` is not a module, did you mean `$directory`?".to_string()
+                ),
+                (
+                    "directory.format".to_string(),
+                    "the format string can't be parsed at line 1, column 15: expected variable or string".to_string()
+                ),
+                (
+                    "nodejs.format".to_string(),
+                    "`$style` can only be used in style strings".to_string()
+                ),
+                (
+                    "nodejs.format".to_string(),
+                    "unknown variable `$versoin`, did you mean `$version`?".to_string()
+                ),
+                (
+                    "nodejs.format".to_string(),
+                    "`$symbol` can't be used in style strings".to_string()
+                ),
+                (
+                    "nodejs.version_format".to_string(),
+                    "unknown variable `$version`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn sub_format_variables_are_not_in_format() {
+        let config = toml::toml! {
+            [git_status]
+            format = "$aheadd$count"
+            format_compact = "$ahead_count"
+        };
+        assert_eq!(
+            messages(config),
+            [
+                (
+                    "git_status.format".to_string(),
+                    "unknown variable `$aheadd`, did you mean `$ahead_behind`?".to_string()
+                ),
+                (
+                    "git_status.format".to_string(),
+                    "unknown variable `$count`".to_string()
+                ),
+                (
+                    "git_status.format_compact".to_string(),
+                    "unknown variable `$ahead_count`, did you mean `$ahead_behind`?".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        let config = toml::Value::try_from(FullConfig::default()).unwrap();
+        assert_eq!(messages(config.try_into().unwrap()), []);
+    }
+
+    #[test]
+    fn locates_keys() {
+        let content = "format = \"$all\"\n\n[git_status]\n  ahead = \"⇡\"\n\n[[battery.display]]\nstyle = \"red\"\n";
+        let document = toml_edit::Document::parse(content).unwrap();
+        assert_eq!(locate(content, &document, "format"), Some((1, 1)));
+        assert_eq!(locate(content, &document, "git_status.ahead"), Some((4, 3)));
+        assert_eq!(
+            locate(content, &document, "battery.display[0].style"),
+            Some((7, 1))
+        );
+        assert_eq!(locate(content, &document, "git_status.behind"), None);
+    }
+}
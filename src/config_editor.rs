@@ -0,0 +1,735 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use nu_ansi_term::{Color, Style};
+use std::io::{self, IsTerminal, Write};
+use toml_edit::DocumentMut;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config_check;
+use crate::configure;
+use crate::context::{Context, Properties, Shell, Target};
+use crate::context_env::Env;
+use crate::module::ALL_MODULES;
+use crate::modules;
+use crate::print;
+
+/// Width of the column listing the modules
+const SECTIONS_WIDTH: usize = 24;
+/// Number of prompt lines shown in the preview
+const PREVIEW_LINES: usize = 3;
+const HELP: &str =
+    "↑↓ move  ←→ switch  Enter edit  Space toggle  d reset to default  s save  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Sections,
+    Options,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Info(String),
+    Error(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    /// The config was edited, so the preview is outdated
+    Changed,
+    Save,
+    Quit,
+}
+
+/// State of `starship configure`, kept apart from the terminal so that it can be tested
+struct Editor {
+    /// The user's config, edited in place to preserve its comments and formatting
+    doc: DocumentMut,
+    /// The default config, see `configure::get_full_configuration`
+    defaults: toml::Table,
+    /// The root config (as an empty string) and the modules
+    sections: Vec<&'static str>,
+    section: usize,
+    option: usize,
+    focus: Focus,
+    /// The value being typed, if an option is being edited
+    input: Option<String>,
+    status: Option<Status>,
+    /// Whether there are unsaved changes
+    dirty: bool,
+    /// Whether quitting with unsaved changes was already requested once
+    quit_requested: bool,
+}
+
+impl Editor {
+    fn new(doc: DocumentMut, defaults: toml::Table) -> Self {
+        let sections = std::iter::once("")
+            .chain(
+                ALL_MODULES
+                    .iter()
+                    .copied()
+                    .filter(|module| defaults.get(*module).is_some_and(toml::Value::is_table)),
+            )
+            .collect();
+        Self {
+            doc,
+            defaults,
+            sections,
+            section: 0,
+            option: 0,
+            focus: Focus::Sections,
+            input: None,
+            status: None,
+            dirty: false,
+            quit_requested: false,
+        }
+    }
+
+    fn current_section(&self) -> &'static str {
+        self.sections[self.section]
+    }
+
+    fn section_defaults(&self) -> Option<&toml::Table> {
+        match self.current_section() {
+            "" => Some(&self.defaults),
+            section => self.defaults.get(section).and_then(toml::Value::as_table),
+        }
+    }
+
+    fn section_table(&self) -> Option<&dyn toml_edit::TableLike> {
+        match self.current_section() {
+            "" => Some(self.doc.as_table()),
+            section => self
+                .doc
+                .as_table()
+                .get(section)
+                .and_then(toml_edit::Item::as_table_like),
+        }
+    }
+
+    /// Keys of the current section: those of the default config, then the ones only the user set
+    fn options(&self) -> Vec<String> {
+        // Tables of the root config are modules, palettes and profiles
+        let root = self.current_section().is_empty();
+        let mut options: Vec<String> = self
+            .section_defaults()
+            .into_iter()
+            .flatten()
+            .filter(|(_, value)| !root || !value.is_table())
+            .map(|(key, _)| key.clone())
+            .collect();
+        if let Some(table) = self.section_table() {
+            for (key, item) in table.iter() {
+                if (root && item.is_table_like()) || options.iter().any(|option| option == key) {
+                    continue;
+                }
+                options.push(key.to_string());
+            }
+        }
+        options
+    }
+
+    fn selected_option(&self) -> Option<String> {
+        self.options().into_iter().nth(self.option)
+    }
+
+    /// Dotted path of the key in the config
+    fn path(&self, key: &str) -> String {
+        match self.current_section() {
+            "" => key.to_string(),
+            section => format!("{section}.{key}"),
+        }
+    }
+
+    fn default_value(&self, key: &str) -> Option<String> {
+        self.section_defaults()
+            .and_then(|defaults| defaults.get(key))
+            .map(ToString::to_string)
+    }
+
+    fn user_value(&self, key: &str) -> Option<String> {
+        let item = self.section_table()?.get(key)?;
+        // Show tables inline, as they are entered
+        let mut value = item.clone().into_value().ok()?;
+        value.decor_mut().clear();
+        Some(value.to_string())
+    }
+
+    fn is_overridden(&self, section: &str) -> bool {
+        self.doc
+            .as_table()
+            .get(section)
+            .and_then(toml_edit::Item::as_table_like)
+            .is_some_and(|table| !table.is_empty())
+    }
+
+    /// Sets the key of the current section, unless the config check finds new problems with it
+    fn set(&mut self, key: &str, input: &str) -> Result<(), String> {
+        let path = self.path(key);
+        let mut doc = self.doc.clone();
+        configure::handle_update_configuration(&mut doc, &path, input)?;
+
+        let before = Self::diagnose(&self.doc).unwrap_or_default();
+        let after = Self::diagnose(&doc)?;
+        if let Some(diagnostic) = after
+            .into_iter()
+            .find(|diagnostic| !before.contains(diagnostic))
+        {
+            return Err(diagnostic.message);
+        }
+
+        self.doc = doc;
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn diagnose(doc: &DocumentMut) -> Result<Vec<config_check::Diagnostic>, String> {
+        let config = toml::from_str(&doc.to_string()).map_err(|error| error.to_string())?;
+        Ok(config_check::diagnose(&config))
+    }
+
+    /// Removes the key from the current section, so that its default is used again
+    fn reset(&mut self, key: &str) -> bool {
+        let removed = match self.current_section() {
+            "" => self.doc.as_table_mut().remove(key),
+            section => self
+                .doc
+                .as_table_mut()
+                .get_mut(section)
+                .and_then(toml_edit::Item::as_table_like_mut)
+                .and_then(|table| table.remove(key)),
+        };
+        self.dirty |= removed.is_some();
+        removed.is_some()
+    }
+
+    fn toggle(&mut self, key: &str) -> Result<(), String> {
+        let value = self
+            .user_value(key)
+            .or_else(|| self.default_value(key))
+            .and_then(|value| value.parse::<bool>().ok())
+            .ok_or_else(|| format!("`{}` is not a boolean", self.path(key)))?;
+        self.set(key, &(!value).to_string())
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let (selected, len) = match self.focus {
+            Focus::Sections => (&mut self.section, self.sections.len()),
+            Focus::Options => {
+                let len = self.options().len();
+                (&mut self.option, len)
+            }
+        };
+        if down {
+            *selected = (*selected + 1).min(len.saturating_sub(1));
+        } else {
+            *selected = selected.saturating_sub(1);
+        }
+        if self.focus == Focus::Sections {
+            self.option = 0;
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if let Some(input) = self.input.as_mut() {
+            match key.code {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Enter => {
+                    let input = input.clone();
+                    let Some(option) = self.selected_option() else {
+                        self.input = None;
+                        return Action::None;
+                    };
+                    match self.set(&option, &input) {
+                        Ok(()) => {
+                            self.input = None;
+                            self.status = Some(Status::Info(format!("Set {}", self.path(&option))));
+                            return Action::Changed;
+                        }
+                        Err(error) => self.status = Some(Status::Error(error)),
+                    }
+                }
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        let quit_requested = std::mem::take(&mut self.quit_requested);
+        self.status = None;
+        let ctrl_c =
+            key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+        match key.code {
+            _ if ctrl_c => return Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if !self.dirty || quit_requested {
+                    return Action::Quit;
+                }
+                self.quit_requested = true;
+                self.status = Some(Status::Error(
+                    "There are unsaved changes, press q again to discard them or s to save"
+                        .to_string(),
+                ));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(false),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(true),
+            KeyCode::Left | KeyCode::Char('h') => self.focus = Focus::Sections,
+            KeyCode::Right | KeyCode::Char('l') => self.focus = Focus::Options,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Sections => Focus::Options,
+                    Focus::Options => Focus::Sections,
+                }
+            }
+            KeyCode::Char('s') => return Action::Save,
+            KeyCode::Enter if self.focus == Focus::Sections => self.focus = Focus::Options,
+            KeyCode::Enter => {
+                if let Some(option) = self.selected_option() {
+                    self.input = self.user_value(&option).or(self.default_value(&option));
+                }
+            }
+            KeyCode::Char(' ') if self.focus == Focus::Options => {
+                if let Some(option) = self.selected_option() {
+                    match self.toggle(&option) {
+                        Ok(()) => return Action::Changed,
+                        Err(error) => self.status = Some(Status::Error(error)),
+                    }
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete if self.focus == Focus::Options => {
+                let Some(option) = self.selected_option() else {
+                    return Action::None;
+                };
+                if self.reset(&option) {
+                    self.status = Some(Status::Info(format!(
+                        "Reset {} to its default",
+                        self.path(&option)
+                    )));
+                    return Action::Changed;
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// Renders the prompt with the edited config, without the escapes of any shell
+    fn preview(&self) -> String {
+        let Ok(config) = toml::from_str(&self.doc.to_string()) else {
+            return String::new();
+        };
+        let current_dir = std::env::current_dir().unwrap_or_default();
+        let context = Context::new_with_shell_and_path(
+            Properties::default(),
+            Shell::Unknown,
+            Target::Main,
+            current_dir.clone(),
+            current_dir,
+            Env::default(),
+        )
+        .set_config(config);
+        print::get_prompt(&context)
+    }
+
+    fn draw(&self, out: &mut impl Write, preview: &str, title: &str) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (usize::from(width), usize::from(height));
+        let list_height = height.saturating_sub(PREVIEW_LINES + 3).max(1);
+        let options_width = width.saturating_sub(SECTIONS_WIDTH + 1);
+
+        queue!(
+            out,
+            cursor::Hide,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(0, 0),
+            Print(Style::new().reverse().paint(fit(title, width)))
+        )?;
+
+        // Module list
+        let offset = scroll_offset(self.section, list_height);
+        for (row, (index, section)) in self
+            .sections
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(list_height)
+            .enumerate()
+        {
+            let name = match *section {
+                "" => "(prompt)",
+                section => section,
+            };
+            let marker = if self.is_overridden(section) {
+                '*'
+            } else {
+                ' '
+            };
+            let line = fit(&format!("{marker}{name}"), SECTIONS_WIDTH);
+            let style = if index != self.section {
+                Style::new()
+            } else if self.focus == Focus::Sections {
+                Style::new().reverse()
+            } else {
+                Style::new().bold()
+            };
+            queue!(
+                out,
+                cursor::MoveTo(0, to_u16(row + 1)),
+                Print(style.paint(line))
+            )?;
+        }
+
+        // Options of the selected module, after its description
+        let x = to_u16(SECTIONS_WIDTH + 1);
+        let description = match self.current_section() {
+            "" => "The prompt and the options that apply to every module",
+            section => modules::description(section),
+        };
+        queue!(
+            out,
+            cursor::MoveTo(x, 1),
+            Print(Style::new().dimmed().paint(fit(description, options_width)))
+        )?;
+        let options = self.options();
+        let key_width = options.iter().map(|key| key.width()).max().unwrap_or(0);
+        let offset = scroll_offset(self.option, list_height.saturating_sub(1));
+        for (row, (index, key)) in options
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(list_height.saturating_sub(1))
+            .enumerate()
+        {
+            let default = self.default_value(key);
+            let line = match self.user_value(key) {
+                Some(value) => {
+                    let default = default
+                        .map(|default| format!("  (default: {default})"))
+                        .unwrap_or_default();
+                    fit(
+                        &format!("*{key:key_width$}  {value}{default}"),
+                        options_width,
+                    )
+                }
+                None => fit(
+                    &format!(" {key:key_width$}  {}", default.unwrap_or_default()),
+                    options_width,
+                ),
+            };
+            let mut style = if self.user_value(key).is_some() {
+                Style::new().fg(Color::Yellow)
+            } else {
+                Style::new()
+            };
+            if index == self.option && self.focus == Focus::Options {
+                style = style.reverse();
+            }
+            queue!(
+                out,
+                cursor::MoveTo(x, to_u16(row + 2)),
+                Print(style.paint(line))
+            )?;
+        }
+
+        // The prompt, as it would be rendered now
+        let preview_row = list_height + 1;
+        queue!(
+            out,
+            cursor::MoveTo(0, to_u16(preview_row)),
+            Print(
+                Style::new()
+                    .dimmed()
+                    .paint(fit(&format!("── Preview {}", "─".repeat(width)), width))
+            )
+        )?;
+        for (row, line) in preview
+            .trim_start_matches('\n')
+            .lines()
+            .take(PREVIEW_LINES)
+            .enumerate()
+        {
+            queue!(
+                out,
+                cursor::MoveTo(0, to_u16(preview_row + row + 1)),
+                Print(line),
+                Print(Style::new().prefix()),
+            )?;
+        }
+
+        // Input, status or help
+        let status_row = to_u16(height.saturating_sub(1));
+        queue!(out, cursor::MoveTo(0, status_row))?;
+        match (&self.input, &self.status) {
+            (Some(input), status) => {
+                let option = self.selected_option().unwrap_or_default();
+                let prompt = format!("{} = ", self.path(&option));
+                if let Some(Status::Error(error)) = status {
+                    queue!(
+                        out,
+                        cursor::MoveTo(0, status_row.saturating_sub(1)),
+                        Print(Color::Red.paint(fit(error, width))),
+                        cursor::MoveTo(0, status_row),
+                    )?;
+                }
+                queue!(
+                    out,
+                    Print(fit(&format!("{prompt}{input}"), width)),
+                    cursor::MoveTo(
+                        to_u16((prompt.width() + input.width()).min(width)),
+                        status_row
+                    ),
+                    cursor::Show,
+                )?;
+            }
+            (None, Some(Status::Info(info))) => {
+                queue!(out, Print(Color::Green.paint(fit(info, width))))?;
+            }
+            (None, Some(Status::Error(error))) => {
+                queue!(out, Print(Color::Red.paint(fit(error, width))))?;
+            }
+            (None, None) => {
+                queue!(out, Print(Style::new().dimmed().paint(fit(HELP, width))))?;
+            }
+        }
+
+        out.flush()
+    }
+}
+
+/// Restores the terminal when the editor exits, even if it panics
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            out,
+            terminal::EnterAlternateScreen,
+            terminal::DisableLineWrap,
+            cursor::Hide
+        )?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(
+            io::stdout(),
+            cursor::Show,
+            terminal::EnableLineWrap,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Opens an editor for the config in the terminal, which previews the prompt as options change
+pub fn run(context: &Context) -> io::Result<()> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("`starship configure` needs an interactive terminal");
+        std::process::exit(1);
+    }
+
+    let Some(config_path) = context.get_config_path_os() else {
+        eprintln!("config path required to edit configuration");
+        std::process::exit(1);
+    };
+    let title = format!(" starship configure  {}", config_path.to_string_lossy());
+    let defaults = match configure::get_full_configuration(context, true) {
+        toml::Value::Table(defaults) => defaults,
+        _ => toml::Table::new(),
+    };
+    let mut editor = Editor::new(configure::get_configuration_edit(context), defaults);
+
+    // Logs would be printed over the editor
+    let max_level = log::max_level();
+    log::set_max_level(log::LevelFilter::Off);
+
+    let mut out = io::stdout();
+    let guard = TerminalGuard::enter(&mut out)?;
+    let mut preview = editor.preview();
+    loop {
+        editor.draw(&mut out, &preview, &title)?;
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match editor.handle_key(key) {
+            Action::None => {}
+            Action::Changed => preview = editor.preview(),
+            Action::Save => match configure::try_write_configuration(&config_path, &editor.doc) {
+                Ok(()) => {
+                    editor.dirty = false;
+                    editor.status = Some(Status::Info(format!(
+                        "Saved to {}",
+                        config_path.to_string_lossy()
+                    )));
+                }
+                Err(error) => {
+                    editor.status = Some(Status::Error(format!(
+                        "Unable to save to {}: {error}",
+                        config_path.to_string_lossy()
+                    )));
+                }
+            },
+            Action::Quit => break,
+        }
+    }
+    drop(guard);
+
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// Index of the first row to show, so that the selected row is visible
+fn scroll_offset(selected: usize, height: usize) -> usize {
+    (selected + 1).saturating_sub(height)
+}
+
+/// Pads or truncates the text to exactly `width` columns
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width > width {
+            break;
+        }
+        used += c_width;
+        fitted.push(c);
+    }
+    if fitted.len() < text.len() && width > 0 {
+        // Make room for the ellipsis
+        while used + 1 > width {
+            let Some(c) = fitted.pop() else { break };
+            used -= c.width().unwrap_or(0);
+        }
+        fitted.push('…');
+        used += 1;
+    }
+    fitted.extend(std::iter::repeat_n(' ', width.saturating_sub(used)));
+    fitted
+}
+
+fn to_u16(value: usize) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor(config: &str) -> Editor {
+        let defaults = match toml::Value::try_from(crate::configs::FullConfig::default()).unwrap() {
+            toml::Value::Table(defaults) => defaults,
+            _ => unreachable!(),
+        };
+        Editor::new(config.parse().unwrap(), defaults)
+    }
+
+    fn select(editor: &mut Editor, section: &str) {
+        editor.section = editor.sections.iter().position(|s| *s == section).unwrap();
+        editor.focus = Focus::Options;
+    }
+
+    fn press(editor: &mut Editor, code: KeyCode) -> Action {
+        editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn options_include_defaults_and_user_keys() {
+        let mut editor = editor("format = '$all'\n[aws]\nunknown = 1\n");
+
+        let root = editor.options();
+        assert!(root.contains(&"format".to_string()));
+        assert!(root.contains(&"add_newline".to_string()));
+        assert!(!root.iter().any(|key| key == "aws" || key == "palettes"));
+
+        select(&mut editor, "aws");
+        let options = editor.options();
+        assert_eq!(options.first().map(String::as_str), Some("format"));
+        assert_eq!(options.last().map(String::as_str), Some("unknown"));
+        assert_eq!(editor.user_value("unknown").as_deref(), Some("1"));
+        assert_eq!(
+            editor.default_value("style").as_deref(),
+            Some("\"bold yellow\"")
+        );
+    }
+
+    #[test]
+    fn set_validates_and_preserves_comments() {
+        let mut editor = editor("# my prompt\n[aws]\n# the color\nstyle = 'bold red'\n");
+        select(&mut editor, "aws");
+
+        assert_eq!(
+            editor.set("style", "bold notacolor"),
+            Err("`notacolor` in the style `bold notacolor` is not a style".to_string())
+        );
+        assert!(!editor.dirty);
+
+        editor.set("style", "italic blue").unwrap();
+        editor.set("force_display", "true").unwrap();
+        assert!(editor.dirty);
+        assert_eq!(
+            editor.doc.to_string(),
+            "# my prompt\n[aws]\n# the color\nstyle = \"italic blue\"\nforce_display = true\n"
+        );
+    }
+
+    #[test]
+    fn toggle_and_reset() {
+        let mut editor = editor("[aws]\nforce_display = true\n");
+        select(&mut editor, "aws");
+        editor.option = editor
+            .options()
+            .iter()
+            .position(|key| key == "disabled")
+            .unwrap();
+
+        assert_eq!(press(&mut editor, KeyCode::Char(' ')), Action::Changed);
+        assert_eq!(editor.user_value("disabled").as_deref(), Some("true"));
+        assert_eq!(press(&mut editor, KeyCode::Char('d')), Action::Changed);
+        assert_eq!(editor.user_value("disabled"), None);
+        assert_eq!(editor.doc.to_string(), "[aws]\nforce_display = true\n");
+
+        editor.option = 0;
+        assert_eq!(press(&mut editor, KeyCode::Char(' ')), Action::None);
+        assert!(matches!(editor.status, Some(Status::Error(_))));
+    }
+
+    #[test]
+    fn edit_and_quit() {
+        let mut editor = editor("");
+        select(&mut editor, "aws");
+
+        press(&mut editor, KeyCode::Enter);
+        assert_eq!(
+            editor.input.as_deref(),
+            Some(r"'on [$symbol($profile )(\($region\) )(\[$duration\] )]($style)'")
+        );
+        editor.input = Some(String::new());
+        for c in "$region".chars() {
+            press(&mut editor, KeyCode::Char(c));
+        }
+        assert_eq!(press(&mut editor, KeyCode::Enter), Action::Changed);
+        assert_eq!(editor.input, None);
+        assert_eq!(editor.doc.to_string(), "[aws]\nformat = \"$region\"\n");
+
+        assert_eq!(press(&mut editor, KeyCode::Char('q')), Action::None);
+        assert_eq!(press(&mut editor, KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn fit_pads_and_truncates() {
+        assert_eq!(fit("abc", 5), "abc  ");
+        assert_eq!(fit("abcdef", 4), "abc…");
+        assert_eq!(fit("日本語", 4), "日… ");
+        assert_eq!(fit("abc", 0), "");
+    }
+}
@@ -14,6 +14,8 @@ pub struct AzureConfig<'a> {
     pub style: &'a str,
     pub disabled: bool,
     pub subscription_aliases: HashMap<String, &'a str>,
+    pub tenant_aliases: HashMap<String, &'a str>,
+    pub expiration_symbol: &'a str,
 }
 
 impl Default for AzureConfig<'_> {
@@ -24,6 +26,8 @@ impl Default for AzureConfig<'_> {
             style: "blue bold",
             disabled: true,
             subscription_aliases: HashMap::new(),
+            tenant_aliases: HashMap::new(),
+            expiration_symbol: "X",
         }
     }
 }
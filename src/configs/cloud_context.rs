@@ -0,0 +1,43 @@
+use indexmap::{IndexMap, indexmap};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct CloudContextConfig<'a> {
+    pub format: &'a str,
+    pub style: &'a str,
+    pub symbols: IndexMap<String, &'a str>,
+    pub account_aliases: HashMap<String, &'a str>,
+    pub region_aliases: HashMap<String, &'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> CloudContextConfig<'a> {
+    pub fn get_symbol(&self, provider: &str) -> Option<&'a str> {
+        self.symbols.get(provider).copied()
+    }
+}
+
+impl Default for CloudContextConfig<'_> {
+    fn default() -> Self {
+        Self {
+            format: "on [$symbol$provider( $account)( \\($region\\))]($style) ",
+            style: "bold blue",
+            symbols: indexmap! {
+                "aws".to_string() => "☁️  ",
+                "gcloud".to_string() => "☁️  ",
+                "azure".to_string() => "󰠅 ",
+                "openstack".to_string() => "☁️  ",
+            },
+            account_aliases: HashMap::new(),
+            region_aliases: HashMap::new(),
+            disabled: true,
+        }
+    }
+}
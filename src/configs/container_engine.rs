@@ -0,0 +1,60 @@
+use indexmap::{IndexMap, indexmap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct ContainerEngineConfig<'a> {
+    pub format: &'a str,
+    pub style: &'a str,
+    pub symbols: IndexMap<String, &'a str>,
+    pub rootless_symbol: &'a str,
+    pub remote_symbol: &'a str,
+    pub local_symbol: &'a str,
+    pub count_running: bool,
+    pub only_with_files: bool,
+    pub disabled: bool,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+}
+
+impl<'a> ContainerEngineConfig<'a> {
+    pub fn get_symbol(&self, engine: &str) -> Option<&'a str> {
+        self.symbols.get(engine).copied()
+    }
+}
+
+impl Default for ContainerEngineConfig<'_> {
+    fn default() -> Self {
+        Self {
+            format: "via [$symbol$context( \\($location\\))( $running running)]($style) ",
+            style: "blue bold",
+            symbols: indexmap! {
+                "docker".to_string() => "🐳 ",
+                "podman".to_string() => "🦭 ",
+                "nerdctl".to_string() => "📦 ",
+            },
+            rootless_symbol: "rootless",
+            remote_symbol: "remote",
+            local_symbol: "",
+            count_running: true,
+            only_with_files: true,
+            disabled: true,
+            detect_extensions: vec![],
+            detect_files: vec![
+                "compose.yml",
+                "compose.yaml",
+                "docker-compose.yml",
+                "docker-compose.yaml",
+                "Dockerfile",
+                "Containerfile",
+            ],
+            detect_folders: vec![],
+        }
+    }
+}
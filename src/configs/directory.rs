@@ -13,6 +13,7 @@ pub struct DirectoryConfig<'a> {
     pub truncation_length: i64,
     pub truncate_to_repo: bool,
     pub substitutions: IndexMap<String, &'a str>,
+    pub bookmarks: IndexMap<String, &'a str>,
     pub fish_style_pwd_dir_length: i64,
     pub use_logical_path: bool,
     pub format: &'a str,
@@ -20,12 +21,19 @@ pub struct DirectoryConfig<'a> {
     pub style: &'a str,
     pub repo_root_style: Option<&'a str>,
     pub before_repo_root_style: Option<&'a str>,
+    pub mount_point_style: Option<&'a str>,
+    pub symlink_style: Option<&'a str>,
+    pub read_only_segment_style: Option<&'a str>,
+    pub segment_styles: IndexMap<String, &'a str>,
     pub disabled: bool,
     pub read_only: &'a str,
     pub read_only_style: &'a str,
     pub truncation_symbol: &'a str,
+    pub truncate_to_width: bool,
+    pub elision_symbol: &'a str,
     pub home_symbol: &'a str,
     pub use_os_path_sep: bool,
+    pub hyperlinks: bool,
 }
 
 impl Default for DirectoryConfig<'_> {
@@ -36,17 +44,25 @@ impl Default for DirectoryConfig<'_> {
             fish_style_pwd_dir_length: 0,
             use_logical_path: true,
             substitutions: IndexMap::new(),
+            bookmarks: IndexMap::new(),
             format: "[$path]($style)[$read_only]($read_only_style) ",
             repo_root_format: "[$before_root_path]($before_repo_root_style)[$repo_root]($repo_root_style)[$path]($style)[$read_only]($read_only_style) ",
             style: "cyan bold",
             repo_root_style: None,
             before_repo_root_style: None,
+            mount_point_style: None,
+            symlink_style: None,
+            read_only_segment_style: None,
+            segment_styles: IndexMap::new(),
             disabled: false,
             read_only: "🔒",
             read_only_style: "red",
             truncation_symbol: "",
+            truncate_to_width: false,
+            elision_symbol: "…",
             home_symbol: "~",
             use_os_path_sep: true,
+            hyperlinks: false,
         }
     }
 }
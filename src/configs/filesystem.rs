@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct FilesystemConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub threshold: i64,
+    pub threshold_style: &'a str,
+    pub network_symbol: &'a str,
+    pub network_style: &'a str,
+    pub network_fs_types: Vec<&'a str>,
+    pub disable_git_status_on: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl Default for FilesystemConfig<'_> {
+    fn default() -> Self {
+        Self {
+            format: "on [$symbol$fs_type( $free free)]($style) ",
+            symbol: "💾 ",
+            style: "bold white",
+            threshold: 90,
+            threshold_style: "bold red",
+            network_symbol: "🌐 ",
+            network_style: "bold yellow",
+            network_fs_types: vec![
+                "nfs",
+                "nfs4",
+                "cifs",
+                "smb3",
+                "smbfs",
+                "afs",
+                "9p",
+                "ceph",
+                "glusterfs",
+                "lustre",
+                "davfs",
+                "fuse.sshfs",
+                "fuse.rclone",
+                "fuse.s3fs",
+            ],
+            disable_git_status_on: vec![],
+            disabled: true,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use indexmap::{IndexMap, indexmap};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -17,6 +18,8 @@ pub struct GitBranchConfig<'a> {
     pub always_show_remote: bool,
     pub ignore_branches: Vec<&'a str>,
     pub ignore_bare_repo: bool,
+    pub hyperlinks: bool,
+    pub url_templates: IndexMap<String, &'a str>,
     pub disabled: bool,
 }
 
@@ -32,6 +35,13 @@ impl Default for GitBranchConfig<'_> {
             always_show_remote: false,
             ignore_branches: vec![],
             ignore_bare_repo: false,
+            hyperlinks: false,
+            url_templates: indexmap! {
+                "github.com".to_string() => "{base_url}/tree/{branch}",
+                "gitlab.com".to_string() => "{base_url}/-/tree/{branch}",
+                "codeberg.org".to_string() => "{base_url}/src/branch/{branch}",
+                "bitbucket.org".to_string() => "{base_url}/src/{branch}",
+            },
             disabled: false,
         }
     }
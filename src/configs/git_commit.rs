@@ -1,3 +1,4 @@
+use indexmap::{IndexMap, indexmap};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -16,6 +17,8 @@ pub struct GitCommitConfig<'a> {
     pub tag_symbol: &'a str,
     pub tag_disabled: bool,
     pub tag_max_candidates: usize,
+    pub hyperlinks: bool,
+    pub url_templates: IndexMap<String, &'a str>,
 }
 
 impl Default for GitCommitConfig<'_> {
@@ -30,6 +33,13 @@ impl Default for GitCommitConfig<'_> {
             tag_symbol: " 🏷  ",
             tag_disabled: true,
             tag_max_candidates: 0,
+            hyperlinks: false,
+            url_templates: indexmap! {
+                "github.com".to_string() => "{base_url}/commit/{commit}",
+                "gitlab.com".to_string() => "{base_url}/-/commit/{commit}",
+                "codeberg.org".to_string() => "{base_url}/commit/{commit}",
+                "bitbucket.org".to_string() => "{base_url}/commits/{commit}",
+            },
         }
     }
 }
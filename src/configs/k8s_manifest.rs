@@ -0,0 +1,45 @@
+use indexmap::{IndexMap, indexmap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct K8sManifestConfig<'a> {
+    pub format: &'a str,
+    pub symbols: IndexMap<String, &'a str>,
+    pub style: &'a str,
+    pub namespace_style: &'a str,
+    pub mismatch_style: &'a str,
+    pub mismatch_symbol: &'a str,
+    pub overlay_dir: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> K8sManifestConfig<'a> {
+    pub fn get_symbol(&self, kind: &str) -> Option<&'a str> {
+        self.symbols.get(kind).copied()
+    }
+}
+
+impl Default for K8sManifestConfig<'_> {
+    fn default() -> Self {
+        Self {
+            format: "via [$symbol$name( v$app_version)( \\($overlay\\))]($style) ([$mismatch$namespace]($namespace_style) )",
+            symbols: indexmap! {
+                "helm".to_string() => "⎈ ",
+                "kustomize".to_string() => "☸ ",
+                "argocd".to_string() => "🐙 ",
+            },
+            style: "bold blue",
+            namespace_style: "cyan",
+            mismatch_style: "bold red",
+            mismatch_symbol: "⚠ ",
+            overlay_dir: "overlays",
+            disabled: true,
+        }
+    }
+}
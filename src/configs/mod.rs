@@ -9,11 +9,13 @@ pub mod bun;
 pub mod c;
 pub mod cc;
 pub mod character;
+pub mod cloud_context;
 pub mod cmake;
 pub mod cmd_duration;
 pub mod cobol;
 pub mod conda;
 pub mod container;
+pub mod container_engine;
 pub mod cpp;
 pub mod crystal;
 pub mod custom;
@@ -29,6 +31,7 @@ pub mod elm;
 pub mod env_var;
 pub mod erlang;
 pub mod fennel;
+pub mod filesystem;
 pub mod fill;
 pub mod fortran;
 pub mod fossil_branch;
@@ -52,6 +55,7 @@ pub mod hostname;
 pub mod java;
 pub mod jobs;
 pub mod julia;
+pub mod k8s_manifest;
 pub mod kotlin;
 pub mod kubernetes;
 pub mod line_break;
@@ -137,6 +141,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     character: character::CharacterConfig<'a>,
     #[serde(borrow)]
+    cloud_context: cloud_context::CloudContextConfig<'a>,
+    #[serde(borrow)]
     cmake: cmake::CMakeConfig<'a>,
     #[serde(borrow)]
     cmd_duration: cmd_duration::CmdDurationConfig<'a>,
@@ -147,6 +153,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     container: container::ContainerConfig<'a>,
     #[serde(borrow)]
+    container_engine: container_engine::ContainerEngineConfig<'a>,
+    #[serde(borrow)]
     cpp: cpp::CppConfig<'a>,
     #[serde(borrow)]
     crystal: crystal::CrystalConfig<'a>,
@@ -175,6 +183,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     fennel: fennel::FennelConfig<'a>,
     #[serde(borrow)]
+    filesystem: filesystem::FilesystemConfig<'a>,
+    #[serde(borrow)]
     fill: fill::FillConfig<'a>,
     #[serde(borrow)]
     fortran: fortran::FortranConfig<'a>,
@@ -221,6 +231,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     julia: julia::JuliaConfig<'a>,
     #[serde(borrow)]
+    k8s_manifest: k8s_manifest::K8sManifestConfig<'a>,
+    #[serde(borrow)]
     kotlin: kotlin::KotlinConfig<'a>,
     #[serde(borrow)]
     kubernetes: kubernetes::KubernetesConfig<'a>,
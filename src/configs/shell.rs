@@ -21,6 +21,11 @@ pub struct ShellConfig<'a> {
     pub nu_indicator: &'a str,
     pub xonsh_indicator: &'a str,
     pub cmd_indicator: &'a str,
+    pub osh_indicator: &'a str,
+    pub ysh_indicator: &'a str,
+    pub ksh_indicator: &'a str,
+    pub mksh_indicator: &'a str,
+    pub murex_indicator: &'a str,
     pub unknown_indicator: &'a str,
     pub style: &'a str,
     pub disabled: bool,
@@ -41,6 +46,11 @@ impl Default for ShellConfig<'_> {
             nu_indicator: "nu",
             xonsh_indicator: "xsh",
             cmd_indicator: "cmd",
+            osh_indicator: "osh",
+            ysh_indicator: "ysh",
+            ksh_indicator: "ksh",
+            mksh_indicator: "mksh",
+            murex_indicator: "mrx",
             unknown_indicator: "",
             style: "white bold",
             disabled: true,
@@ -15,18 +15,111 @@ pub struct StarshipRootConfig {
     pub format: String,
     pub right_format: String,
     pub continuation_prompt: String,
+    pub transient_format: String,
     pub scan_timeout: u64,
     pub command_timeout: u64,
+    pub auto_disable_threshold: u64,
+    pub auto_disable_strikes: u32,
+    pub auto_disabled_format: String,
+    pub record_timings: bool,
+    pub shell_integration: bool,
+    pub title_format: String,
+    pub title_preexec_format: String,
     pub add_newline: bool,
     pub follow_symlinks: bool,
+    pub responsive_layout: bool,
+    pub layout_priorities: IndexMap<String, i64>,
+    pub separator: SeparatorConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub palette: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub palette_light: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub palette_dark: Option<String>,
     pub palettes: HashMap<String, Palette>,
+    pub color_depth: String,
     pub profiles: IndexMap<String, String>,
 }
 
 pub type Palette = HashMap<String, String>;
 
+/// Glyphs the renderer inserts between modules, colored by the backgrounds of their neighbors
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct SeparatorConfig {
+    pub disabled: bool,
+    pub left: String,
+    pub right: String,
+    pub left_thin: String,
+    pub right_thin: String,
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for SeparatorConfig {
+    fn default() -> Self {
+        Self {
+            disabled: true,
+            left: "".to_string(),
+            right: "".to_string(),
+            left_thin: "".to_string(),
+            right_thin: "".to_string(),
+            start: String::new(),
+            end: String::new(),
+        }
+    }
+}
+
+/// Priority of modules not listed in `layout_priorities`
+pub const DEFAULT_LAYOUT_PRIORITY: i64 = 50;
+
+/// Modules with at least this priority are never dropped by the responsive layout
+pub const REQUIRED_LAYOUT_PRIORITY: i64 = 100;
+
+/// Default `layout_priorities`, which `layout_priority` searches without building a map
+const DEFAULT_LAYOUT_PRIORITIES: &[(&str, i64)] = &[
+    ("time", 10),
+    ("memory_usage", 10),
+    ("battery", 20),
+    ("os", 20),
+    ("shell", 20),
+    ("localip", 20),
+    ("cmd_duration", 30),
+    ("jobs", 60),
+    ("status", 70),
+    ("git_branch", 80),
+    ("directory", 90),
+    ("character", 100),
+];
+
+fn default_layout_priorities() -> IndexMap<String, i64> {
+    DEFAULT_LAYOUT_PRIORITIES
+        .iter()
+        .map(|(module, priority)| ((*module).to_string(), *priority))
+        .collect()
+}
+
+impl StarshipRootConfig {
+    /// Returns the responsive layout priority of a module; lower priorities are dropped first
+    pub fn layout_priority(&self, module: &str) -> i64 {
+        self.layout_priorities
+            .get(module)
+            .copied()
+            .or_else(|| {
+                DEFAULT_LAYOUT_PRIORITIES
+                    .iter()
+                    .find(|(name, _)| *name == module)
+                    .map(|(_, priority)| *priority)
+            })
+            .unwrap_or(DEFAULT_LAYOUT_PRIORITY)
+    }
+}
+
 // List of default prompt order
 // NOTE: If this const value is changed then Default prompt order subheading inside
 // prompt heading of config docs needs to be updated according to changes made here.
@@ -37,8 +130,10 @@ pub const PROMPT_ORDER: &[&str] = &[
     "shlvl",
     "singularity",
     "kubernetes",
+    "k8s_manifest",
     "nats",
     "directory",
+    "filesystem",
     "vcsh",
     "fossil_branch",
     "fossil_metrics",
@@ -51,6 +146,7 @@ pub const PROMPT_ORDER: &[&str] = &[
     "hg_state",
     "pijul_channel",
     "docker_context",
+    "container_engine",
     "package",
     // ↓ Toolchain version modules ↓
     // (Let's keep these sorted alphabetically)
@@ -117,6 +213,7 @@ pub const PROMPT_ORDER: &[&str] = &[
     "gcloud",
     "openstack",
     "azure",
+    "cloud_context",
     "direnv",
     "env_var",
     "mise",
@@ -145,13 +242,27 @@ impl Default for StarshipRootConfig {
             format: "$all".to_string(),
             right_format: String::new(),
             continuation_prompt: "[∙](bright-black) ".to_string(),
+            transient_format: String::new(),
             profiles: Default::default(),
             scan_timeout: 30,
             command_timeout: 500,
+            auto_disable_threshold: 0,
+            auto_disable_strikes: 3,
+            auto_disabled_format: "[⏱ $name](bright-black) ".to_string(),
+            record_timings: false,
+            shell_integration: false,
+            title_format: String::new(),
+            title_preexec_format: "$command".to_string(),
             add_newline: true,
             follow_symlinks: true,
+            responsive_layout: false,
+            layout_priorities: default_layout_priorities(),
+            separator: SeparatorConfig::default(),
             palette: None,
+            palette_light: None,
+            palette_dark: None,
             palettes: HashMap::default(),
+            color_depth: "auto".to_string(),
         }
     }
 }
@@ -11,11 +11,16 @@ pub struct TerraformConfig<'a> {
     pub format: &'a str,
     pub version_format: &'a str,
     pub symbol: &'a str,
+    pub tofu_symbol: &'a str,
+    pub lock_outdated_symbol: &'a str,
     pub style: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub tofu_detect_extensions: Vec<&'a str>,
+    pub tofu_detect_files: Vec<&'a str>,
+    pub tofu_detect_folders: Vec<&'a str>,
     pub commands: Vec<Vec<&'a str>>,
 }
 
@@ -25,11 +30,16 @@ impl Default for TerraformConfig<'_> {
             format: "via [$symbol$workspace]($style) ",
             version_format: "v${raw}",
             symbol: "💠 ",
+            tofu_symbol: "🧊 ",
+            lock_outdated_symbol: "!lock",
             style: "bold 105",
             disabled: false,
             detect_extensions: vec!["tf", "tfplan", "tfstate"],
             detect_files: vec![],
             detect_folders: vec![".terraform"],
+            tofu_detect_extensions: vec!["tofu"],
+            tofu_detect_files: vec![],
+            tofu_detect_folders: vec![],
             commands: vec![
                 // terraform is usually `terraform`
                 vec!["terraform", "version"],
@@ -7,9 +7,11 @@ use crate::config::ModuleConfig;
 use crate::config::StarshipConfig;
 use crate::configs::PROMPT_ORDER;
 use crate::context::Context;
+use crate::print::{self, Preset};
 use crate::utils;
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 use toml_edit::DocumentMut;
 
 #[cfg(not(windows))]
@@ -29,7 +31,7 @@ pub fn update_configuration(context: &Context, name: &str, value: &str) {
     }
 }
 
-fn handle_update_configuration(
+pub(crate) fn handle_update_configuration(
     doc: &mut DocumentMut,
     name: &str,
     value: &str,
@@ -74,8 +76,9 @@ fn handle_update_configuration(
     Ok(())
 }
 
-pub fn print_configuration(context: &Context, use_default: bool, paths: &[String]) -> String {
-    let config = if use_default {
+/// Returns the computed config, or the default config if `use_default` is set
+pub fn get_full_configuration(context: &Context, use_default: bool) -> toml::Value {
+    if use_default {
         // Get default config
         let default_config = crate::configs::FullConfig::default();
         // Convert back to Value because toml can't serialize FullConfig directly
@@ -87,7 +90,11 @@ pub fn print_configuration(context: &Context, use_default: bool, paths: &[String
         let user_config = crate::configs::FullConfig::load(&user_config);
         // Convert back to Value because toml can't serialize FullConfig directly
         toml::value::Value::try_from(user_config).unwrap()
-    };
+    }
+}
+
+pub fn print_configuration(context: &Context, use_default: bool, paths: &[String]) -> String {
+    let config = get_full_configuration(context, use_default);
 
     println!("# Warning: This config does not include keys that have an unset value\n");
 
@@ -223,6 +230,149 @@ fn handle_toggle_configuration(doc: &mut DocumentMut, name: &str, key: &str) ->
     Ok(())
 }
 
+/// Merges the presets into the config, later presets taking precedence over earlier ones.
+/// Keys that are already set in the config are kept, unless `overwrite` is set.
+pub fn apply_presets(context: &Context, presets: &[Preset], overwrite: bool) {
+    let mut combined = DocumentMut::new();
+    for preset in presets {
+        let preset_doc = print::get_preset_content(preset)
+            .map_err(|error| error.to_string())
+            .and_then(|content| {
+                String::from_utf8_lossy(&content)
+                    .parse::<DocumentMut>()
+                    .map_err(|error| error.to_string())
+            });
+        match preset_doc {
+            Ok(preset_doc) => {
+                merge_configuration(&mut combined, &preset_doc, true);
+            }
+            Err(error) => {
+                eprintln!("Unable to load the preset {}: {error}", preset.0);
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut doc = get_configuration_edit(context);
+    let stats = merge_configuration(&mut doc, &combined, overwrite);
+    write_configuration(context, &doc);
+
+    let names = presets
+        .iter()
+        .map(|preset| preset.0)
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!(
+        "Applied {names}: {} key(s) added, {} replaced, {} kept",
+        stats.added, stats.replaced, stats.kept
+    );
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct MergeStats {
+    added: usize,
+    replaced: usize,
+    kept: usize,
+}
+
+/// Merges `source` into `doc`, keeping the comments and formatting of both.
+/// Tables that are new to `doc` are appended to it.
+fn merge_configuration(doc: &mut DocumentMut, source: &DocumentMut, overwrite: bool) -> MergeStats {
+    let mut stats = MergeStats::default();
+    let mut next_position = last_position(doc.as_item()) + 1;
+    merge_tables(
+        doc.as_table_mut(),
+        source.as_table(),
+        overwrite,
+        &mut next_position,
+        &mut stats,
+    );
+    stats
+}
+
+fn merge_tables(
+    target: &mut dyn toml_edit::TableLike,
+    source: &dyn toml_edit::TableLike,
+    overwrite: bool,
+    next_position: &mut isize,
+    stats: &mut MergeStats,
+) {
+    for (key, item) in source.iter() {
+        let Some(existing) = target.get_mut(key) else {
+            let mut item = item.clone();
+            stats.added += count_values(&item);
+            set_positions(&mut item, next_position);
+            target.insert(key, item);
+            continue;
+        };
+
+        if let (Some(existing), Some(item)) = (existing.as_table_like_mut(), item.as_table_like()) {
+            merge_tables(existing, item, overwrite, next_position, stats);
+        } else if !overwrite || same_value(existing, item) {
+            stats.kept += count_values(existing);
+        } else {
+            let mut new_value = item.clone();
+            if let (Some(new_value), Some(value)) = (new_value.as_value_mut(), existing.as_value())
+            {
+                *new_value.decor_mut() = value.decor().clone();
+            }
+            *existing = new_value;
+            stats.replaced += 1;
+        }
+    }
+}
+
+fn same_value(a: &toml_edit::Item, b: &toml_edit::Item) -> bool {
+    let undecorated = |item: &toml_edit::Item| {
+        item.clone().into_value().ok().map(|mut value| {
+            value.decor_mut().clear();
+            value.to_string()
+        })
+    };
+    undecorated(a).is_some_and(|a| Some(a) == undecorated(b))
+}
+
+/// Number of values in the item, counting those of its tables
+fn count_values(item: &toml_edit::Item) -> usize {
+    match item.as_table_like() {
+        Some(table) => table.iter().map(|(_, item)| count_values(item)).sum(),
+        None => 1,
+    }
+}
+
+/// The highest position of the tables in the item
+fn last_position(item: &toml_edit::Item) -> isize {
+    let last_table_position = |table: &toml_edit::Table| {
+        table
+            .iter()
+            .map(|(_, item)| last_position(item))
+            .fold(table.position().unwrap_or(0), isize::max)
+    };
+    match item {
+        toml_edit::Item::Table(table) => last_table_position(table),
+        toml_edit::Item::ArrayOfTables(tables) => {
+            tables.iter().map(last_table_position).max().unwrap_or(0)
+        }
+        _ => 0,
+    }
+}
+
+/// Numbers the tables in the item from `next_position`, so that they follow the existing ones
+fn set_positions(item: &mut toml_edit::Item, next_position: &mut isize) {
+    let tables: Vec<&mut toml_edit::Table> = match item {
+        toml_edit::Item::Table(table) => vec![table],
+        toml_edit::Item::ArrayOfTables(tables) => tables.iter_mut().collect(),
+        _ => return,
+    };
+    for table in tables {
+        table.set_position(*next_position);
+        *next_position += 1;
+        for (_, item) in table.iter_mut() {
+            set_positions(item, next_position);
+        }
+    }
+}
+
 pub fn get_configuration(context: &Context) -> toml::Table {
     let starship_config = StarshipConfig::initialize(context.get_config_path_os().as_deref());
 
@@ -245,11 +395,17 @@ pub fn write_configuration(context: &Context, doc: &DocumentMut) {
         process::exit(1);
     });
 
+    try_write_configuration(config_path, doc).expect("Error writing starship config");
+}
+
+/// Writes the config to `config_path`, returning the error rather than panicking
+pub fn try_write_configuration(
+    config_path: impl AsRef<Path>,
+    doc: &DocumentMut,
+) -> std::io::Result<()> {
     let config_str = doc.to_string();
 
-    File::create(config_path)
-        .and_then(|mut file| file.write_all(config_str.as_ref()))
-        .expect("Error writing starship config");
+    File::create(config_path).and_then(|mut file| file.write_all(config_str.as_ref()))
 }
 
 pub fn edit_configuration(
@@ -313,7 +469,11 @@ fn get_editor_internal(visual: Option<String>, editor: Option<String>) -> String
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::create_dir, io, path::PathBuf};
+    use std::{
+        fs::{self, create_dir},
+        io,
+        path::PathBuf,
+    };
 
     use tempfile::TempDir;
     use toml_edit::Item;
@@ -581,6 +741,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_configuration_keeps_existing_values() {
+        let mut doc: DocumentMut =
+            "# my config\nformat = '$all'\n\n[aws]\n# my symbol\nsymbol = 'A '\n"
+                .parse()
+                .unwrap();
+        let preset: DocumentMut =
+            "format = '$directory'\n\n[aws]\nsymbol = 'B '\nstyle = 'red'\n\n[palettes.company]\nprimary = 'blue'\nsecondary = 'green'\n"
+                .parse()
+                .unwrap();
+
+        let stats = merge_configuration(&mut doc, &preset, false);
+
+        assert_eq!(
+            stats,
+            MergeStats {
+                added: 3,
+                replaced: 0,
+                kept: 2
+            }
+        );
+        assert_eq!(
+            doc.to_string(),
+            "# my config\nformat = '$all'\n\n[aws]\n# my symbol\nsymbol = 'A '\nstyle = 'red'\n\n[palettes.company]\nprimary = 'blue'\nsecondary = 'green'\n"
+        );
+    }
+
+    #[test]
+    fn merge_configuration_overwrite() {
+        let mut doc: DocumentMut = "[aws]\n# my symbol\nsymbol = 'A '\nstyle = 'red'\n"
+            .parse()
+            .unwrap();
+        let preset: DocumentMut = "[aws]\nsymbol = 'B '\nstyle = 'red'\n".parse().unwrap();
+
+        let stats = merge_configuration(&mut doc, &preset, true);
+
+        assert_eq!(
+            stats,
+            MergeStats {
+                added: 0,
+                replaced: 1,
+                kept: 1
+            }
+        );
+        assert_eq!(
+            doc.to_string(),
+            "[aws]\n# my symbol\nsymbol = 'B '\nstyle = 'red'\n"
+        );
+    }
+
     #[test]
     fn write_and_get_configuration_test() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -596,6 +806,20 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn try_write_configuration_returns_errors() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let doc = DocumentMut::from_str("[directory]\nformat = 'myformat'\n").unwrap();
+
+        let missing_dir = dir.path().join("missing").join("starship.toml");
+        assert!(try_write_configuration(&missing_dir, &doc).is_err());
+
+        let config_path = dir.path().join("starship.toml");
+        try_write_configuration(&config_path, &doc)?;
+        assert_eq!(fs::read_to_string(&config_path)?, doc.to_string());
+        dir.close()
+    }
+
     const PRINT_CONFIG_DEFAULT: &str = "[custom]";
     const PRINT_CONFIG_HOME: &str = "[custom.home]";
     const PRINT_CONFIG_ENV: &str = "[custom.STARSHIP_CONFIG]";
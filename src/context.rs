@@ -1,7 +1,9 @@
-use crate::config::{ModuleConfig, StarshipConfig};
-use crate::configs::StarshipRootConfig;
+use crate::background::{self, Background};
+use crate::config::{ModuleConfig, StarshipConfig, get_palette};
+use crate::configs::{Palette, StarshipRootConfig};
 use crate::context_env::Env;
 use crate::module::Module;
+use crate::timings::{RuntimeTracker, SpanRecorder};
 use crate::utils::{CommandOutput, PathExt, create_command, exec_timeout, read_file};
 
 use crate::modules;
@@ -25,7 +27,7 @@ use std::num::ParseIntError;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::String;
-use std::sync::{Arc, OnceLock, mpsc};
+use std::sync::{Arc, Mutex, OnceLock, mpsc};
 use std::thread;
 use std::time::{Duration, Instant};
 use terminal_size::terminal_size;
@@ -80,6 +82,21 @@ pub struct Context<'a> {
     /// Starship root config
     pub root_config: StarshipRootConfig,
 
+    /// Module configs with `format` replaced by their `format_compact`
+    compact_config: OnceLock<toml::Table>,
+
+    /// Modules the responsive layout renders with their `format_compact`
+    compact_modules: Mutex<HashSet<String>>,
+
+    /// Runtimes of slow modules, loaded when `auto_disable_threshold` is set
+    runtimes: OnceLock<Mutex<RuntimeTracker>>,
+
+    /// Spans recorded for `starship timings`, if any are being recorded
+    spans: OnceLock<SpanRecorder>,
+
+    /// The color palette, see `Context::palette`
+    palette: OnceLock<Option<Palette>>,
+
     /// Avoid issues with unused lifetimes when features are disabled
     _marker: PhantomData<&'a ()>,
 }
@@ -181,6 +198,11 @@ impl<'a> Context<'a> {
             #[cfg(feature = "battery")]
             battery_info_provider: &crate::modules::BatteryInfoProviderImpl,
             root_config,
+            compact_config: OnceLock::new(),
+            compact_modules: Mutex::new(HashSet::new()),
+            runtimes: OnceLock::new(),
+            spans: OnceLock::new(),
+            palette: OnceLock::new(),
             _marker: PhantomData,
         }
     }
@@ -188,17 +210,99 @@ impl<'a> Context<'a> {
     /// Sets the context config, overwriting the existing config
     pub fn set_config(mut self, config: toml::Table) -> Self {
         self.root_config = StarshipRootConfig::load(&config);
+        self.palette = OnceLock::new();
         self.config = StarshipConfig {
             config: Some(config),
         };
         self
     }
 
+    /// Returns the color palette. If the config has palettes for light and dark backgrounds, the
+    /// one matching the background of the terminal is used, falling back to `palette`.
+    pub fn palette(&self) -> Option<&Palette> {
+        self.palette
+            .get_or_init(|| {
+                let config = &self.root_config;
+                let name = if config.palette_light.is_none() && config.palette_dark.is_none() {
+                    config.palette.as_deref()
+                } else {
+                    match background::detect(self) {
+                        Some(Background::Light) => config.palette_light.as_deref(),
+                        Some(Background::Dark) => config.palette_dark.as_deref(),
+                        None => None,
+                    }
+                    .or(config.palette.as_deref())
+                };
+                get_palette(&config.palettes, name)
+            })
+            .as_ref()
+    }
+
     // Tries to retrieve home directory from a table in testing mode or else retrieves it from the os
     pub fn get_home(&self) -> Option<PathBuf> {
         home_dir(&self.env)
     }
 
+    /// Sets whether the module is rendered with its `format_compact` from now on.
+    /// Returns `false` if the module has no `format_compact`.
+    pub fn set_compact(&self, name: &str, compact: bool) -> bool {
+        if self.get_compact_module_config(name).is_none() {
+            return false;
+        }
+        let mut compact_modules = self.compact_modules.lock().unwrap();
+        if compact {
+            compact_modules.insert(name.to_string());
+        } else {
+            compact_modules.remove(name);
+        }
+        true
+    }
+
+    fn get_compact_module_config(&self, name: &str) -> Option<&toml::Value> {
+        self.compact_config
+            .get_or_init(|| {
+                let Some(config) = &self.config.config else {
+                    return toml::Table::new();
+                };
+                let compact = |value: &toml::Value| {
+                    let mut table = value.as_table()?.clone();
+                    let compact = table.remove("format_compact")?;
+                    table.insert("format".to_string(), compact);
+                    Some(toml::Value::Table(table))
+                };
+                // `env_var` and `custom` modules are configured in nested tables
+                let nested = ["env_var", "custom"].into_iter().flat_map(|parent| {
+                    config
+                        .get(parent)
+                        .and_then(toml::Value::as_table)
+                        .into_iter()
+                        .flatten()
+                        .map(move |(name, value)| (format!("{parent}.{name}"), value))
+                });
+                config
+                    .iter()
+                    .map(|(module, value)| (module.clone(), value))
+                    .chain(nested)
+                    .filter_map(|(module, value)| Some((module, compact(value)?)))
+                    .collect()
+            })
+            .get(name)
+    }
+
+    /// Returns the config of a module, using its `format_compact` as `format` while it is
+    /// compact. Names like `env_var.<name>` and `custom.<name>` refer to the nested tables.
+    pub fn get_module_config(&self, name: &str) -> Option<&toml::Value> {
+        if self.compact_modules.lock().unwrap().contains(name) {
+            return self.get_compact_module_config(name);
+        }
+        match name.split_once('.') {
+            Some((parent @ ("env_var" | "custom"), name)) => {
+                self.config.get_config(&[parent, name])
+            }
+            _ => self.config.get_module_config(name),
+        }
+    }
+
     // Retrieves a environment variable from the os or from a table if in testing mode
     #[inline]
     pub fn get_env<K: AsRef<str>>(&self, key: K) -> Option<String> {
@@ -222,7 +326,7 @@ impl<'a> Context<'a> {
 
     /// Create a new module
     pub fn new_module(&self, name: &str) -> Module<'_> {
-        let config = self.config.get_module_config(name);
+        let config = self.get_module_config(name);
         let desc = modules::description(name);
 
         Module::new(name, desc, config)
@@ -238,6 +342,72 @@ impl<'a> Context<'a> {
         disabled == Some(true)
     }
 
+    fn runtime_tracker(&self) -> Option<&Mutex<RuntimeTracker>> {
+        if self.root_config.auto_disable_threshold == 0 {
+            return None;
+        }
+        Some(
+            self.runtimes
+                .get_or_init(|| Mutex::new(RuntimeTracker::load(self))),
+        )
+    }
+
+    /// Check if the module was disabled for being slow in the current directory.
+    pub fn is_module_auto_disabled(&self, name: &str) -> bool {
+        self.runtime_tracker()
+            .is_some_and(|tracker| tracker.lock().unwrap().is_disabled(name))
+    }
+
+    /// Returns the modules that were disabled for being slow in the current directory.
+    pub fn auto_disabled_modules(&self) -> Vec<String> {
+        self.runtime_tracker()
+            .map(|tracker| {
+                let tracker = tracker.lock().unwrap();
+                tracker
+                    .disabled_modules()
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Records how long a module took to render, disabling it if it is repeatedly slow.
+    pub fn record_module_runtime(&self, name: &str, elapsed: Duration) {
+        if let Some(tracker) = self.runtime_tracker() {
+            tracker.lock().unwrap().record(
+                name,
+                elapsed,
+                Duration::from_millis(self.root_config.auto_disable_threshold),
+                self.root_config.auto_disable_strikes,
+            );
+        }
+    }
+
+    /// Persists the module runtimes recorded while rendering the prompt.
+    pub fn save_module_runtimes(&self) {
+        if let Some(tracker) = self.runtimes.get() {
+            tracker.lock().unwrap().save();
+        }
+    }
+
+    /// Starts recording spans of the prompt render, e.g. for the timings log.
+    pub fn record_spans(&self) -> &SpanRecorder {
+        self.spans.get_or_init(SpanRecorder::default)
+    }
+
+    /// Records a span that started at `start` and ends now, if spans are being recorded.
+    pub fn record_span(
+        &self,
+        category: &'static str,
+        name: impl FnOnce() -> String,
+        start: Instant,
+    ) {
+        if let Some(recorder) = self.spans.get() {
+            recorder.record(category, name(), start);
+        }
+    }
+
     /// Returns true when a negated environment variable is defined in `env_vars` and is present
     fn has_negated_env_var(&self, env_vars: &'a [&'a str]) -> bool {
         env_vars
@@ -312,82 +482,84 @@ impl<'a> Context<'a> {
     /// Will lazily get repo root and branch when a module requests it.
     pub fn get_repo(&self) -> Result<&Repo, &gix::discover::Error> {
         self.repo
-            .get_or_init(|| -> Result<Repo, Box<gix::discover::Error>> {
-                // custom open options
-                let mut git_open_opts_map =
-                    git_sec::trust::Mapping::<gix::open::Options>::default();
-
-                // Load all the configuration as it affects aspects of the
-                // `git_status` and `git_metrics` modules.
-                let config = gix::open::permissions::Config {
-                    git_binary: true,
-                    system: true,
-                    git: true,
-                    user: true,
-                    env: true,
-                    includes: true,
-                };
-                // change options for config permissions without touching anything else
-                git_open_opts_map.reduced =
-                    git_open_opts_map
-                        .reduced
-                        .permissions(gix::open::Permissions {
-                            config,
-                            ..gix::open::Permissions::default_for_level(git_sec::Trust::Reduced)
-                        });
-                git_open_opts_map.full =
-                    git_open_opts_map.full.permissions(gix::open::Permissions {
-                        config,
-                        ..gix::open::Permissions::default_for_level(git_sec::Trust::Full)
-                    });
-
-                let shared_repo =
-                    match ThreadSafeRepository::discover_with_environment_overrides_opts(
-                        &self.current_dir,
-                        gix::discover::upwards::Options {
-                            match_ceiling_dir_or_error: false,
-                            ..Default::default()
-                        },
-                        git_open_opts_map,
-                    ) {
-                        Ok(repo) => repo,
-                        Err(e) => {
-                            log::debug!("Failed to find git repo: {e}");
-                            return Err(Box::new(e));
-                        }
-                    };
-
-                let repository = shared_repo.to_thread_local();
-                log::trace!(
-                    "Found git repo: {repository:?}, (trust: {:?})",
-                    repository.git_dir_trust()
-                );
-
-                let branch = get_current_branch(&repository);
-                let remote =
-                    get_remote_repository_info(&repository, branch.as_ref().map(AsRef::as_ref));
-                let path = repository.path().to_path_buf();
-
-                let fs_monitor_value_is_true = repository
-                    .config_snapshot()
-                    .boolean("core.fsmonitor")
-                    .unwrap_or(false);
-
-                Ok(Repo {
-                    repo: shared_repo,
-                    branch: branch.map(|b| b.shorten().to_string()),
-                    workdir: repository.workdir().map(PathBuf::from),
-                    path,
-                    state: repository.state(),
-                    remote,
-                    fs_monitor_value_is_true,
-                    kind: repository.kind(),
-                })
+            .get_or_init(|| {
+                let start = Instant::now();
+                let repo = self.discover_repo();
+                self.record_span("get_repo", || "get_repo".to_string(), start);
+                repo
             })
             .as_ref()
             .map_err(std::convert::AsRef::as_ref)
     }
 
+    fn discover_repo(&self) -> Result<Repo, Box<gix::discover::Error>> {
+        // custom open options
+        let mut git_open_opts_map = git_sec::trust::Mapping::<gix::open::Options>::default();
+
+        // Load all the configuration as it affects aspects of the
+        // `git_status` and `git_metrics` modules.
+        let config = gix::open::permissions::Config {
+            git_binary: true,
+            system: true,
+            git: true,
+            user: true,
+            env: true,
+            includes: true,
+        };
+        // change options for config permissions without touching anything else
+        git_open_opts_map.reduced = git_open_opts_map
+            .reduced
+            .permissions(gix::open::Permissions {
+                config,
+                ..gix::open::Permissions::default_for_level(git_sec::Trust::Reduced)
+            });
+        git_open_opts_map.full = git_open_opts_map.full.permissions(gix::open::Permissions {
+            config,
+            ..gix::open::Permissions::default_for_level(git_sec::Trust::Full)
+        });
+
+        let shared_repo = match ThreadSafeRepository::discover_with_environment_overrides_opts(
+            &self.current_dir,
+            gix::discover::upwards::Options {
+                match_ceiling_dir_or_error: false,
+                ..Default::default()
+            },
+            git_open_opts_map,
+        ) {
+            Ok(repo) => repo,
+            Err(e) => {
+                log::debug!("Failed to find git repo: {e}");
+                return Err(Box::new(e));
+            }
+        };
+
+        let repository = shared_repo.to_thread_local();
+        log::trace!(
+            "Found git repo: {repository:?}, (trust: {:?})",
+            repository.git_dir_trust()
+        );
+
+        let branch = get_current_branch(&repository);
+        let remote = get_remote_repository_info(&repository, branch.as_ref().map(AsRef::as_ref));
+        let path = repository.path().to_path_buf();
+
+        let fs_monitor_value_is_true = repository
+            .config_snapshot()
+            .boolean("core.fsmonitor")
+            .unwrap_or(false);
+
+        Ok(Repo {
+            repo: shared_repo,
+            branch: branch.map(|b| b.shorten().to_string()),
+            workdir: repository.workdir().map(PathBuf::from),
+            path,
+            state: repository.state(),
+            remote,
+            fs_monitor_value_is_true,
+            kind: repository.kind(),
+        })
+    }
+
     pub fn dir_contents(&self) -> Result<&DirContents, &std::io::Error> {
         self.dir_contents
             .get_or_init(|| {
@@ -415,6 +587,11 @@ impl<'a> Context<'a> {
             "nu" => Shell::Nu,
             "xonsh" => Shell::Xonsh,
             "cmd" => Shell::Cmd,
+            "osh" => Shell::Osh,
+            "ysh" => Shell::Ysh,
+            "ksh" => Shell::Ksh,
+            "mksh" => Shell::Mksh,
+            "murex" => Shell::Murex,
             _ => Shell::Unknown,
         }
     }
@@ -447,12 +624,15 @@ impl<'a> Context<'a> {
                 return output;
             }
         }
-        let mut cmd = create_command(cmd).ok()?;
-        cmd.args(args).current_dir(&self.current_dir);
-        exec_timeout(
-            &mut cmd,
+        let start = Instant::now();
+        let mut command = create_command(&cmd).ok()?;
+        command.args(args).current_dir(&self.current_dir);
+        let output = exec_timeout(
+            &mut command,
             Duration::from_millis(self.root_config.command_timeout),
-        )
+        );
+        self.record_span("exec_cmd", || utils::display_command(&cmd, args), start);
+        output
     }
 
     /// Attempt to execute several commands with `exec_cmd`, return the results of the first that works
@@ -773,10 +953,34 @@ impl Repo {
         command.args(git_args);
         log::trace!("Executing git command: {command:?}");
 
-        exec_timeout(
+        let start = Instant::now();
+        let output = exec_timeout(
             &mut command,
             Duration::from_millis(context.root_config.command_timeout),
-        )
+        );
+        context.record_span(
+            "exec_cmd",
+            || {
+                let args = command.get_args().collect::<Vec<_>>();
+                utils::display_command(command.get_program(), &args)
+            },
+            start,
+        );
+        output
+    }
+
+    /// The fetch URL of the remote tracked by the current branch, or of `origin`
+    /// if the branch has no upstream.
+    pub fn remote_url(&self) -> Option<String> {
+        let name = self
+            .remote
+            .as_ref()
+            .and_then(|remote| remote.name.as_deref())
+            .unwrap_or("origin");
+        let repo = self.open();
+        let remote = repo.find_remote(name).ok()?;
+        let url = remote.url(gix::remote::Direction::Fetch)?;
+        Some(url.to_bstring().to_string())
     }
 }
 
@@ -939,6 +1143,11 @@ pub enum Shell {
     Nu,
     Xonsh,
     Cmd,
+    Osh,
+    Ysh,
+    Ksh,
+    Mksh,
+    Murex,
     Unknown,
 }
 
@@ -948,6 +1157,7 @@ pub enum Target {
     Main,
     Right,
     Continuation,
+    Transient,
     Profile(String),
 }
 
@@ -1,5 +1,5 @@
 pub mod model;
-mod parser;
+pub(crate) mod parser;
 pub mod string_formatter;
 mod version;
 
@@ -444,8 +444,9 @@ where
     // Handle other interpretable characters
     match shell {
         // Bash might interpret backslashes, backticks and $
-        // see #658 for more details
-        Shell::Bash => text
+        // see #658 for more details. OSH expands PS1 the same way, and ksh93 runs parameter
+        // expansion and command substitution on the value `PS1.get` sets.
+        Shell::Bash | Shell::Osh | Shell::Ksh => text
             .into()
             .replace('\\', r"\\")
             .replace('$', r"\$")
@@ -842,6 +843,28 @@ mod tests {
             test
         );
     }
+    #[test]
+    fn test_osh_and_ksh_escape() {
+        for shell in [Shell::Osh, Shell::Ksh] {
+            assert_eq!(
+                shell_prompt_escape("$(echo a)".to_owned(), shell),
+                r"\$(echo a)"
+            );
+            assert_eq!(
+                shell_prompt_escape(r"\$(echo a)".to_owned(), shell),
+                r"\\\$(echo a)"
+            );
+            assert_eq!(
+                shell_prompt_escape(r"`echo a`".to_owned(), shell),
+                r"\`echo a\`"
+            );
+        }
+        assert_eq!(
+            shell_prompt_escape("$(echo a)".to_owned(), Shell::Mksh),
+            "$(echo a)"
+        );
+    }
+
     #[test]
     fn test_zsh_escape() {
         let test = "10%";
@@ -0,0 +1,501 @@
+use nu_ansi_term::Color;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::bug_report;
+use crate::context::{Context, Target};
+use crate::timings;
+
+/// Arguments of the prompt that are passed by the init scripts, as long and short flag
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PromptArg {
+    Status,
+    CmdDuration,
+    Pipestatus,
+    Keymap,
+    Jobs,
+}
+
+impl PromptArg {
+    fn flags(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Self::Status => ("--status", Some("-s")),
+            Self::CmdDuration => ("--cmd-duration", Some("-d")),
+            Self::Pipestatus => ("--pipestatus", None),
+            Self::Keymap => ("--keymap", Some("-k")),
+            Self::Jobs => ("--jobs", Some("-j")),
+        }
+    }
+
+    /// The hook of the init script that provides the argument, shown when it is missing
+    fn source(self) -> &'static str {
+        match self {
+            Self::Status => "the precmd hook",
+            Self::CmdDuration => "the preexec hook",
+            Self::Pipestatus => "the precmd hook",
+            Self::Keymap => "the keymap hook",
+            Self::Jobs => "the precmd hook",
+        }
+    }
+
+    /// Returns the value of the argument in `args`, if it was passed
+    fn find(self, args: &[String]) -> Option<&str> {
+        let (long, short) = self.flags();
+        args.iter().enumerate().find_map(|(i, arg)| {
+            let value = arg
+                .strip_prefix(long)
+                .or_else(|| short.and_then(|short| arg.strip_prefix(short)))?;
+            match value.strip_prefix('=') {
+                Some(value) => Some(value),
+                // `--flag value`, as passed by nu
+                None if value.is_empty() => Some(args.get(i + 1).map_or("", String::as_str)),
+                None => None,
+            }
+        })
+    }
+}
+
+/// Returns the prompt arguments the init script of `shell` passes
+fn expected_args(shell: &str) -> &'static [PromptArg] {
+    use PromptArg::*;
+    match shell {
+        "zsh" | "fish" => &[Status, CmdDuration, Pipestatus, Keymap, Jobs],
+        "bash" | "osh" | "ysh" => &[Status, CmdDuration, Pipestatus, Jobs],
+        "powershell" | "pwsh" | "elvish" | "xonsh" | "ksh" | "ion" => &[Status, CmdDuration, Jobs],
+        "mksh" => &[Status, Jobs],
+        _ => &[Status, CmdDuration],
+    }
+}
+
+/// Prompt frameworks that replace the prompt when they are loaded after starship
+const PROMPT_FRAMEWORKS: &[(&str, &str)] = &[
+    ("oh-my-zsh.sh", "Oh My Zsh"),
+    ("powerlevel10k", "Powerlevel10k"),
+    ("p10k", "Powerlevel10k"),
+    ("oh-my-posh", "Oh My Posh"),
+    ("oh-my-bash", "Oh My Bash"),
+    ("bash_it.sh", "Bash-it"),
+    ("prezto", "Prezto"),
+    ("spaceship", "Spaceship"),
+    ("liquidprompt", "Liquid Prompt"),
+    ("gitprompt.sh", "bash-git-prompt"),
+];
+
+/// Shell variables that hold the prompt and are replaced by the starship init
+const PROMPT_VARIABLES: &[&str] = &["PS1", "PROMPT", "RPROMPT", "PROMPT_COMMAND"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Level {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Diagnostic {
+    level: Level,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new(level: Level, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+}
+
+/// Arguments of the last prompt rendered in a session, see [`record_prompt`]
+#[derive(Debug, PartialEq, Eq)]
+struct PromptRecord {
+    /// Time since the prompt was rendered
+    age: Duration,
+    args: Vec<String>,
+}
+
+/// Returns the file in which the arguments of the prompts of a session are recorded
+fn record_path(context: &Context, session_key: &str, target: &Target) -> Option<PathBuf> {
+    let name = match target {
+        Target::Main => "prompt",
+        Target::Right => "rprompt",
+        _ => return None,
+    };
+    Some(timings::cache_dir(context).join(format!("session_{session_key}_{name}.log")))
+}
+
+/// Whether `path` is a file written by [`record_prompt`], which holds the full prompt arguments
+pub fn is_prompt_record(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.starts_with("session_")
+                && (name.ends_with("_prompt.log") || name.ends_with("_rprompt.log"))
+        })
+}
+
+/// Returns the command that sets the `STARSHIP_CHECK` environment variable in `shell`
+fn enable_check_command(shell: &str) -> &'static str {
+    match shell {
+        "fish" => "set -gx STARSHIP_CHECK 1",
+        "powershell" | "pwsh" => "$env:STARSHIP_CHECK = 1",
+        "nu" => "$env.STARSHIP_CHECK = 1",
+        "elvish" => "set E:STARSHIP_CHECK = 1",
+        "xonsh" => "$STARSHIP_CHECK = 1",
+        "tcsh" => "setenv STARSHIP_CHECK 1",
+        "cmd" => "set STARSHIP_CHECK=1",
+        _ => "export STARSHIP_CHECK=1",
+    }
+}
+
+/// Records the arguments of the main and right prompts, for `starship init --check`
+///
+/// Nothing is written unless `STARSHIP_CHECK` is set, to keep disk writes off the prompt.
+/// The file is named like the session log, so it is cleaned up with it.
+pub fn record_prompt(context: &Context, args: impl Iterator<Item = String>) {
+    if context.get_env("STARSHIP_CHECK").is_none() {
+        return;
+    }
+    let Some(session_key) = context.get_env("STARSHIP_SESSION_KEY") else {
+        return;
+    };
+    let Some(path) = record_path(context, &session_key, &context.target) else {
+        return;
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let content = std::iter::once(now.to_string())
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Err(error) = fs::write(&path, content) {
+        log::debug!("Unable to record the prompt arguments to {path:?}: {error}");
+    }
+}
+
+fn read_record(path: &Path) -> Option<PromptRecord> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut lines = content.lines();
+    let rendered = Duration::from_millis(lines.next()?.parse().ok()?);
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+    Some(PromptRecord {
+        age: now.saturating_sub(rendered),
+        args: lines.map(str::to_string).collect(),
+    })
+}
+
+fn diagnose_args(shell: &str, record: &PromptRecord) -> Vec<Diagnostic> {
+    expected_args(shell)
+        .iter()
+        .map(|arg| {
+            let (flag, _) = arg.flags();
+            match arg.find(&record.args) {
+                Some("") => Diagnostic::new(
+                    Level::Warning,
+                    format!(
+                        "{flag} was empty on the last prompt, run a command and check again if it stays empty"
+                    ),
+                ),
+                Some(value) => Diagnostic::new(Level::Ok, format!("{flag} is passed ({value})")),
+                None => Diagnostic::new(
+                    Level::Error,
+                    format!("{flag} is not passed, {} of the init script did not run", arg.source()),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Finds prompt frameworks and prompt variables that are set after the starship init in the
+/// shell config, and would replace the starship prompt
+fn diagnose_shell_config(config_path: &Path, config: &str) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = config.lines().collect();
+    let Some(init_line) = lines
+        .iter()
+        .position(|line| line.contains("starship init") && !line.trim_start().starts_with('#'))
+    else {
+        return vec![Diagnostic::new(
+            Level::Warning,
+            format!(
+                "{} does not run `starship init`, check that another file loads it",
+                config_path.display()
+            ),
+        )];
+    };
+
+    let mut diagnostics = vec![Diagnostic::new(
+        Level::Ok,
+        format!(
+            "{} runs `starship init` on line {}",
+            config_path.display(),
+            init_line + 1
+        ),
+    )];
+    for (i, line) in lines.iter().enumerate().skip(init_line + 1) {
+        let line = line.trim_start();
+        if line.starts_with('#') {
+            continue;
+        }
+        let overrides = PROMPT_FRAMEWORKS
+            .iter()
+            .find(|(marker, _)| line.contains(marker))
+            .map(|(_, name)| format!("{name} is loaded"))
+            .or_else(|| {
+                PROMPT_VARIABLES
+                    .iter()
+                    .find(|variable| {
+                        let assignment = line.strip_prefix("export ").unwrap_or(line);
+                        assignment.starts_with(&format!("{variable}="))
+                            && !assignment.starts_with(&format!("{variable}=\"${variable}"))
+                    })
+                    .map(|variable| format!("{variable} is set"))
+            });
+        if let Some(overrides) = overrides {
+            diagnostics.push(Diagnostic::new(
+                Level::Warning,
+                format!(
+                    "{overrides} after `starship init` on line {} and may replace the prompt",
+                    i + 1
+                ),
+            ));
+        }
+    }
+    diagnostics
+}
+
+fn diagnose(context: &Context, shell: &str, config: Option<(PathBuf, String)>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    match context.get_env("STARSHIP_SHELL") {
+        Some(name) if name == shell || (shell == "powershell" && name == "pwsh") => {
+            diagnostics.push(Diagnostic::new(
+                Level::Ok,
+                format!("STARSHIP_SHELL is set to {name}"),
+            ));
+        }
+        Some(name) => diagnostics.push(Diagnostic::new(
+            Level::Error,
+            format!(
+                "STARSHIP_SHELL is set to {name}, the init script of {name} ran instead of {shell}"
+            ),
+        )),
+        None => diagnostics.push(Diagnostic::new(
+            Level::Error,
+            "STARSHIP_SHELL is not set, the init script did not run in this shell",
+        )),
+    }
+
+    let session_key = context.get_env("STARSHIP_SESSION_KEY");
+    match &session_key {
+        Some(_) => diagnostics.push(Diagnostic::new(Level::Ok, "STARSHIP_SESSION_KEY is set")),
+        None => diagnostics.push(Diagnostic::new(
+            Level::Error,
+            "STARSHIP_SESSION_KEY is not set, the prompt arguments cannot be checked",
+        )),
+    }
+
+    let recording = context.get_env("STARSHIP_CHECK").is_some();
+    if session_key.is_some() {
+        diagnostics.push(if recording {
+            Diagnostic::new(
+                Level::Ok,
+                "STARSHIP_CHECK is set, unset it after the check to stop recording the prompt arguments",
+            )
+        } else {
+            Diagnostic::new(
+                Level::Error,
+                format!(
+                    "STARSHIP_CHECK is not set, so the prompt arguments are not recorded. Run `{}`, press enter and check again",
+                    enable_check_command(shell)
+                ),
+            )
+        });
+    }
+
+    if let Some(session_key) = session_key.as_ref().filter(|_| recording) {
+        let main = record_path(context, session_key, &Target::Main).and_then(|p| read_record(&p));
+        match main {
+            Some(record) => {
+                diagnostics.push(Diagnostic::new(
+                    Level::Ok,
+                    format!("the prompt was last rendered {}s ago", record.age.as_secs()),
+                ));
+                diagnostics.extend(diagnose_args(shell, &record));
+            }
+            None => diagnostics.push(Diagnostic::new(
+                Level::Error,
+                "no prompt was rendered in this session, another prompt may replace starship",
+            )),
+        }
+
+        if !context.root_config.right_format.is_empty() {
+            let right =
+                record_path(context, session_key, &Target::Right).and_then(|p| read_record(&p));
+            diagnostics.push(match right {
+                Some(_) => Diagnostic::new(Level::Ok, "the right prompt is rendered"),
+                None => Diagnostic::new(
+                    Level::Error,
+                    format!("`right_format` is set, but {shell} never rendered the right prompt"),
+                ),
+            });
+        }
+    }
+
+    // Shell variables are only visible here when they are exported
+    if let Some(prompt_command) = context
+        .get_env("PROMPT_COMMAND")
+        .filter(|prompt_command| !prompt_command.contains("starship"))
+    {
+        diagnostics.push(Diagnostic::new(
+            Level::Warning,
+            format!("PROMPT_COMMAND is exported without the starship hook: {prompt_command}"),
+        ));
+    }
+    if context.get_env("POSH_THEME").is_some() {
+        diagnostics.push(Diagnostic::new(
+            Level::Warning,
+            "Oh My Posh is loaded (POSH_THEME is set) and may replace the prompt",
+        ));
+    }
+
+    match config {
+        Some((path, config)) => diagnostics.extend(diagnose_shell_config(&path, &config)),
+        None => diagnostics.push(Diagnostic::new(
+            Level::Warning,
+            format!("The config of {shell} was not found"),
+        )),
+    }
+
+    diagnostics
+}
+
+/// Checks the shell integration from within the shell and prints a report
+pub fn check(shell: &str) {
+    let context = Context::default();
+    let shell_info = bug_report::get_shell_info();
+    let config = bug_report::get_config_path(shell)
+        .and_then(|path| fs::read_to_string(&path).ok().map(|config| (path, config)));
+
+    println!(
+        "\n Checking the starship init of {shell} {}\n",
+        shell_info.version
+    );
+    let diagnostics = diagnose(&context, shell, config);
+    for diagnostic in &diagnostics {
+        let marker = match diagnostic.level {
+            Level::Ok => Color::Green.paint("✔"),
+            Level::Warning => Color::Yellow.paint("!"),
+            Level::Error => Color::Red.paint("✘"),
+        };
+        println!(" {marker} {}", diagnostic.message);
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == Level::Error)
+        .count();
+    if errors > 0 {
+        println!(
+            "\n {errors} problem(s) found, see https://starship.rs/guide/#step-2-set-up-your-shell-to-use-starship"
+        );
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::default_context;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_string()).collect()
+    }
+
+    #[test]
+    fn finds_prompt_args() {
+        let passed = args(&["prompt", "--status=1", "-j", "2", "--cmd-duration", "12"]);
+        assert_eq!(PromptArg::Status.find(&passed), Some("1"));
+        assert_eq!(PromptArg::Jobs.find(&passed), Some("2"));
+        assert_eq!(PromptArg::CmdDuration.find(&passed), Some("12"));
+        assert_eq!(PromptArg::Pipestatus.find(&passed), None);
+    }
+
+    #[test]
+    fn diagnoses_missing_args() {
+        let record = PromptRecord {
+            age: Duration::ZERO,
+            args: args(&["prompt", "--status=0", "--pipestatus=", "--jobs=0"]),
+        };
+        let levels: Vec<_> = diagnose_args("bash", &record)
+            .into_iter()
+            .map(|diagnostic| diagnostic.level)
+            .collect();
+        assert_eq!(levels, [Level::Ok, Level::Error, Level::Warning, Level::Ok]);
+    }
+
+    #[test]
+    fn flags_prompts_set_after_init() {
+        let config = "source $ZSH/oh-my-zsh.sh\neval \"$(starship init zsh)\"\n# PROMPT='%# '\nPROMPT_COMMAND=\"$PROMPT_COMMAND;history -a\"\nPROMPT='%# '\nsource ~/.p10k.zsh\n";
+        let diagnostics = diagnose_shell_config(Path::new(".zshrc"), config);
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                ".zshrc runs `starship init` on line 2",
+                "PROMPT is set after `starship init` on line 5 and may replace the prompt",
+                "Powerlevel10k is loaded after `starship init` on line 6 and may replace the prompt",
+            ]
+        );
+    }
+
+    #[test]
+    fn records_prompt_args() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", dir.path().to_string_lossy().into_owned());
+        context
+            .env
+            .insert("STARSHIP_SESSION_KEY", "0123456789abcdef".to_string());
+        context.env.insert("STARSHIP_SHELL", "zsh".to_string());
+        context.env.insert("STARSHIP_CHECK", "1".to_string());
+
+        record_prompt(&context, args(&["prompt", "--status=0"]).into_iter());
+
+        let diagnostics = diagnose(&context, "zsh", None);
+        assert!(diagnostics.contains(&Diagnostic::new(Level::Ok, "--status is passed (0)")));
+        assert!(diagnostics.contains(&Diagnostic::new(
+            Level::Error,
+            "--keymap is not passed, the keymap hook of the init script did not run"
+        )));
+        dir.close()
+    }
+
+    #[test]
+    fn records_nothing_without_opt_in() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", dir.path().to_string_lossy().into_owned());
+        context
+            .env
+            .insert("STARSHIP_SESSION_KEY", "0123456789abcdef".to_string());
+        context.env.insert("STARSHIP_SHELL", "fish".to_string());
+
+        record_prompt(&context, args(&["prompt", "--status=0"]).into_iter());
+
+        assert_eq!(fs::read_dir(dir.path())?.count(), 0);
+        let diagnostics = diagnose(&context, "fish", None);
+        assert!(diagnostics.contains(&Diagnostic::new(
+            Level::Error,
+            "STARSHIP_CHECK is not set, so the prompt arguments are not recorded. Run `set -gx STARSHIP_CHECK 1`, press enter and check again"
+        )));
+        dir.close()
+    }
+}
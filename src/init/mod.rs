@@ -1,3 +1,4 @@
+use crate::context::Context;
 use crate::utils::create_command;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -5,6 +6,10 @@ use std::{env, io};
 
 use which::which;
 
+mod check;
+
+pub use check::{check, is_prompt_record, record_prompt};
+
 /* We use a two-phase init here: the first phase gives a simple command to the
 shell. This command evaluates a more complicated script using `source` and
 process substitution.
@@ -180,6 +185,27 @@ pub fn init_stub(shell_name: &str) -> io::Result<()> {
             starship.sprint_posix()?
         ),
         "cmd" => print_script(CMDEXE_INIT, &StarshipPath::init()?.sprint_cmdexe()?),
+        "osh" => print!(
+            r#"eval -- "$({} init osh --print-full-init)""#,
+            starship.sprint_posix()?
+        ),
+        "ysh" => print!(
+            r"source <({} init ysh --print-full-init)",
+            starship.sprint_posix()?
+        ),
+        // ksh93 is also installed as ksh93
+        "ksh" | "ksh93" => print!(
+            r#"eval "$({} init ksh --print-full-init)""#,
+            starship.sprint_posix()?
+        ),
+        "mksh" => print!(
+            r#"eval "$({} init mksh --print-full-init)""#,
+            starship.sprint_posix()?
+        ),
+        "murex" => print!(
+            r"{} init murex --print-full-init -> source",
+            starship.sprint_posix()?
+        ),
         _ => {
             eprintln!(
                 "{shell_basename} is not yet supported by starship.\n\
@@ -194,6 +220,11 @@ pub fn init_stub(shell_name: &str) -> io::Result<()> {
                  * nu\n\
                  * xonsh\n\
                  * cmd\n\
+                 * osh\n\
+                 * ysh\n\
+                 * ksh\n\
+                 * mksh\n\
+                 * murex\n\
                  \n\
                  Please open an issue in the starship repo if you would like to \
                  see support for {shell_basename}:\n\
@@ -207,18 +238,9 @@ pub fn init_stub(shell_name: &str) -> io::Result<()> {
 /* This function (called when `--print-full-init` is passed to `starship init`)
 prints out the main initialization script */
 pub fn init_main(shell_name: &str) -> io::Result<()> {
-    let starship_path = StarshipPath::init()?;
-
-    match shell_name {
-        "bash" => print_script(BASH_INIT, &starship_path.sprint_posix()?),
-        "zsh" => print_script(ZSH_INIT, &starship_path.sprint_posix()?),
-        "fish" => print_script(FISH_INIT, &starship_path.sprint_posix()?),
-        "powershell" => print_script(PWSH_INIT, &starship_path.sprint_pwsh()?),
-        "ion" => print_script(ION_INIT, &starship_path.sprint()?),
-        "elvish" => print_script(ELVISH_INIT, &starship_path.sprint()?),
-        "tcsh" => print_script(TCSH_INIT, &starship_path.sprint_posix()?),
-        "xonsh" => print_script(XONSH_INIT, &starship_path.sprint_posix()?),
-        _ => {
+    match main_script(shell_name)? {
+        Some(script) => print!("{script}"),
+        None => {
             println!(
                 "printf \"Shell name detection failed on phase two init.\\n\
                  This probably indicates a bug within starship: please open\\n\
@@ -229,9 +251,52 @@ pub fn init_main(shell_name: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// Returns the main initialization script of a shell, with its tokens replaced
+pub fn main_script(shell_name: &str) -> io::Result<Option<String>> {
+    let starship_path = StarshipPath::init()?;
+
+    let (script, path) = match shell_name {
+        "bash" => (BASH_INIT, starship_path.sprint_posix()?),
+        "zsh" => (ZSH_INIT, starship_path.sprint_posix()?),
+        "fish" => (FISH_INIT, starship_path.sprint_posix()?),
+        "powershell" => (PWSH_INIT, starship_path.sprint_pwsh()?),
+        "ion" => (ION_INIT, starship_path.sprint()?),
+        "elvish" => (ELVISH_INIT, starship_path.sprint()?),
+        "tcsh" => (TCSH_INIT, starship_path.sprint_posix()?),
+        "xonsh" => (XONSH_INIT, starship_path.sprint_posix()?),
+        "osh" => (OSH_INIT, starship_path.sprint_posix()?),
+        "ysh" => (YSH_INIT, starship_path.sprint_posix()?),
+        "ksh" => (KSH_INIT, starship_path.sprint_posix()?),
+        "mksh" => (MKSH_INIT, starship_path.sprint_posix()?),
+        "murex" => (MUREX_INIT, starship_path.sprint_posix()?),
+        // nu and cmd load the main script directly from the stub
+        "nu" => (NU_INIT, starship_path.sprint()?),
+        "cmd" => (CMDEXE_INIT, starship_path.sprint_cmdexe()?),
+        _ => return Ok(None),
+    };
+    Ok(Some(render_script(script, &path)))
+}
+
 fn print_script(script: &str, path: &str) {
-    let script = script.replace("::STARSHIP::", path);
-    print!("{script}");
+    print!("{}", render_script(script, path));
+}
+
+fn render_script(script: &str, path: &str) -> String {
+    let config = Context::default().root_config;
+    let title = !config.title_format.is_empty();
+    let title_preexec = title && !config.title_preexec_format.is_empty();
+    let transient = !config.transient_format.is_empty();
+    let background = config.palette_light.is_some() || config.palette_dark.is_some();
+    script
+        .replace("::STARSHIP::", path)
+        .replace(
+            "::SHELL_INTEGRATION::",
+            &config.shell_integration.to_string(),
+        )
+        .replace("::TITLE_PREEXEC::", &title_preexec.to_string())
+        .replace("::TITLE::", &title.to_string())
+        .replace("::TRANSIENT::", &transient.to_string())
+        .replace("::BACKGROUND::", &background.to_string())
 }
 
 /* GENERAL INIT SCRIPT NOTES
@@ -246,7 +311,11 @@ to avoid the cost of an additional shell fork every shell draw.
 Note that the init scripts are not in their final form--they are processed by
 `starship init` prior to emitting the final form. In this processing, some tokens
 are replaced, e.g. `::STARSHIP::` is replaced by the full path to the
-starship binary.
+starship binary. `::SHELL_INTEGRATION::`, `::TITLE::`, `::TITLE_PREEXEC::`,
+`::TRANSIENT::` and `::BACKGROUND::` are replaced by `true` or `false`, depending on
+whether the config enables the OSC 133 marks of the preexec hooks, the terminal title,
+the title shown while a command runs, the transient prompt and palettes for light and
+dark backgrounds.
 */
 
 const BASH_INIT: &str = include_str!("starship.bash");
@@ -267,6 +336,16 @@ const NU_INIT: &str = include_str!("starship.nu");
 
 const XONSH_INIT: &str = include_str!("starship.xsh");
 
+const OSH_INIT: &str = include_str!("starship.osh");
+
+const YSH_INIT: &str = include_str!("starship.ysh");
+
+const KSH_INIT: &str = include_str!("starship.ksh");
+
+const MKSH_INIT: &str = include_str!("starship.mksh");
+
+const MUREX_INIT: &str = include_str!("starship.mx");
+
 const CMDEXE_INIT: &str = include_str!("starship.lua");
 
 #[cfg(test)]
@@ -15,6 +15,23 @@
 # A way to set '$?', since bash does not allow assigning to '$?' directly
 function _starship_set_return() { return "${1:-0}"; }
 
+# Whether to mark the start of command output for the terminal (OSC 133), see `shell_integration`
+STARSHIP_SHELL_INTEGRATION=::SHELL_INTEGRATION::
+
+# Whether to set the terminal title, see `title_format` and `title_preexec_format`
+STARSHIP_TITLE=::TITLE::
+STARSHIP_TITLE_PREEXEC=::TITLE_PREEXEC::
+
+# Whether to replace the prompt of submitted command lines (ble.sh only), see `transient_format`
+STARSHIP_TRANSIENT=::TRANSIENT::
+
+# Sets the terminal title to the command that is about to run, taken from the history
+starship_title_preexec() {
+    local cmd
+    cmd=$(HISTTIMEFORMAT= builtin history 1)
+    ::STARSHIP:: title --preexec="${cmd#*[0-9]  }"
+}
+
 # Will be run before *every* command (even ones in pipes!)
 starship_preexec() {
     # Save previous command's last argument, otherwise it will be set to "starship_preexec"
@@ -24,6 +41,12 @@ starship_preexec() {
     if [ "${STARSHIP_PREEXEC_READY:-}" = "true" ]; then
         STARSHIP_PREEXEC_READY=false
         STARSHIP_START_TIME=$(::STARSHIP:: time)
+        if [[ $STARSHIP_TITLE_PREEXEC == true ]]; then
+            starship_title_preexec
+        fi
+        if [[ $STARSHIP_SHELL_INTEGRATION == true ]]; then
+            printf '\e]133;C\a'
+        fi
     fi
 
     : "$PREV_LAST_ARG"
@@ -78,9 +101,16 @@ starship_precmd() {
         STARSHIP_START_TIME=""
     fi
     PS1="$(::STARSHIP:: prompt "${ARGS[@]}")"
+    if [[ $STARSHIP_TITLE == true ]]; then
+        ::STARSHIP:: title "${ARGS[@]}"
+    fi
     if [[ ${BLE_ATTACHED-} ]]; then
         local nlns=${PS1//[!$'\n']}
         bleopt prompt_rps1="$nlns$(::STARSHIP:: prompt --right "${ARGS[@]}")"
+        if [[ $STARSHIP_TRANSIENT == true ]]; then
+            bleopt prompt_ps1_final="$(::STARSHIP:: prompt --transient "${ARGS[@]}")"
+            bleopt prompt_rps1_transient=1
+        fi
     fi
     STARSHIP_PREEXEC_READY=true  # Signal that we can safely restart the timer
 }
@@ -106,6 +136,12 @@ else
         # To avoid printing anything, use the return value in an ${var:offset:length} substring expansion
         # with offset and length evaluating to 0.
         PS0='${STARSHIP_START_TIME:$((STARSHIP_START_TIME="$(starship_preexec_ps0)",STARSHIP_PREEXEC_READY=0,0)):0}'"${PS0-}"
+        if [[ $STARSHIP_TITLE_PREEXEC == true ]]; then
+            PS0+='$(starship_title_preexec)'
+        fi
+        if [[ $STARSHIP_SHELL_INTEGRATION == true ]]; then
+            PS0+='\e]133;C\a'
+        fi
     else
         # We want to avoid destroying an existing DEBUG hook. If we detect one, create
         # a new function that runs both the existing function AND our function, then
@@ -148,6 +184,11 @@ STARSHIP_SESSION_KEY="$RANDOM$RANDOM$RANDOM$RANDOM$RANDOM"; # Random generates a
 STARSHIP_SESSION_KEY="${STARSHIP_SESSION_KEY}0000000000000000" # Pad it to 16+ chars.
 export STARSHIP_SESSION_KEY=${STARSHIP_SESSION_KEY:0:16}; # Trim to 16-digits if excess.
 
+# Query the background color of the terminal once, if the config has palettes for light and dark backgrounds
+if [[ ::BACKGROUND:: == true && -z "${STARSHIP_BACKGROUND-}" ]]; then
+    export STARSHIP_BACKGROUND="$(::STARSHIP:: background)"
+fi
+
 # Set the continuation prompt
 PS2="$(::STARSHIP:: prompt --continuation)"
 
@@ -32,6 +32,8 @@ function fish_prompt
         printf \e\[0J
         if type -q starship_transient_prompt_func
             starship_transient_prompt_func --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --jobs=$STARSHIP_JOBS
+        else if test "$STARSHIP_TRANSIENT" = true
+            ::STARSHIP:: prompt --transient --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --jobs=$STARSHIP_JOBS
         else
             printf "\e[1;32m❯\e[0m "
         end
@@ -76,6 +78,26 @@ builtin functions -e fish_mode_prompt
 
 set -gx STARSHIP_SHELL "fish"
 
+# Mark the start of command output for the terminal (OSC 133), see `shell_integration`
+set -g STARSHIP_SHELL_INTEGRATION ::SHELL_INTEGRATION::
+function __starship_preexec --on-event fish_preexec
+    if test "$STARSHIP_SHELL_INTEGRATION" = true
+        printf '\e]133;C\a'
+    end
+end
+
+# Set the terminal title, see `title_format` and `title_preexec_format`
+if test "::TITLE::" = true
+    function fish_title
+        # fish passes the running command, if any
+        if set -q argv[1]; and test "::TITLE_PREEXEC::" = true
+            ::STARSHIP:: title --plain --preexec="$argv[1]"
+        else
+            ::STARSHIP:: title --plain --terminal-width="$COLUMNS" --jobs=$STARSHIP_JOBS
+        end
+    end
+end
+
 # Transience related functions
 function reset-transient --on-event fish_postexec
     set -g TRANSIENT 0
@@ -105,6 +127,17 @@ function disable_transience --description 'remove transient prompt keybindings'
     bind --user -M insert -e \r
 end
 
+# Use the transient prompt by default if the config sets `transient_format`
+set -g STARSHIP_TRANSIENT ::TRANSIENT::
+if test "$STARSHIP_TRANSIENT" = true
+    enable_transience
+end
+
 # Set up the session key that will be used to store logs
 # We don't use `random [min] [max]` because it is unavailable in older versions of fish shell
 set -gx STARSHIP_SESSION_KEY (string sub -s1 -l16 (random)(random)(random)(random)(random)0000000000000000)
+
+# Query the background color of the terminal once, if the config has palettes for light and dark backgrounds
+if test "::BACKGROUND::" = true; and not set -q STARSHIP_BACKGROUND
+    set -gx STARSHIP_BACKGROUND (::STARSHIP:: background)
+end
@@ -150,6 +150,9 @@ $null = New-Module starship {
             $script:TransientPrompt = $false
             if (Test-Path function:Invoke-Starship-TransientFunction) {
                 Invoke-Starship-TransientFunction
+            } elseif ("::TRANSIENT::" -eq "true") {
+                $transientArguments = @("prompt", "--transient") + ($arguments | Select-Object -Skip 1)
+                Invoke-Native -Executable ::STARSHIP:: -Arguments $transientArguments
             } else {
                 "$([char]0x1B)[1;32m❯$([char]0x1B)[0m "
             }
@@ -157,6 +160,12 @@ $null = New-Module starship {
             Invoke-Native -Executable ::STARSHIP:: -Arguments $arguments
         }
 
+        # Set the terminal title, see `title_format`
+        if ("::TITLE::" -eq "true") {
+            $titleArguments = @("title", "--plain") + ($arguments | Select-Object -Skip 1)
+            $Host.UI.RawUI.WindowTitle = Invoke-Native -Executable ::STARSHIP:: -Arguments $titleArguments
+        }
+
         # Set the number of extra lines in the prompt for PSReadLine prompt redraw.
         Set-PSReadLineOption -ExtraPromptLineCount ($promptText.Split("`n").Length - 1)
 
@@ -210,6 +219,36 @@ $null = New-Module starship {
         )
     )
 
+    # Mark the start of command output for the terminal (OSC 133), see `shell_integration`
+    # Wrap the existing function, defined by PSReadLine or overridden by the user or another module.
+    # If the init runs again, the wrapper of the first run is kept.
+    $script:OriginalReadLine = (Get-Command PSConsoleHostReadLine -CommandType Function -ErrorAction Ignore).ScriptBlock
+    if ("::SHELL_INTEGRATION::" -eq "true" -and -not "$script:OriginalReadLine".Contains("]133;C")) {
+        function global:PSConsoleHostReadLine {
+            # Get the status of the last command first, as any command run here changes it
+            $lastRunStatus = $?
+            $line = if ($script:OriginalReadLine) {
+                # Restore $? for the original function, which reads it as PSReadLine does
+                if ($lastRunStatus) {
+                    # An assignment sets $? = True without adding to the output of the function
+                    $null = 1+1
+                } else {
+                    Write-Error '' -ErrorAction 'Ignore'
+                }
+                & $script:OriginalReadLine
+            } else {
+                [Microsoft.PowerShell.PSConsoleReadLine]::ReadLine($Host.Runspace, $ExecutionContext, $lastRunStatus)
+            }
+            [Console]::Write("$([char]0x1B)]133;C$([char]0x07)")
+            $line
+        }
+    }
+
+    # Use the transient prompt by default if the config sets `transient_format`
+    if ("::TRANSIENT::" -eq "true") {
+        Enable-TransientPrompt
+    }
+
     try {
         # Combine user defined ViModeChangeHandler if it exists
         if((Get-PSReadLineOption).ViModeChangeHandler){
@@ -8,8 +8,12 @@ use std::thread::available_parallelism;
 shadow!(shadow);
 
 // Lib is present to allow for benchmarking
+pub mod background;
 pub mod bug_report;
+pub mod color;
 pub mod config;
+pub mod config_check;
+pub mod config_editor;
 pub mod configs;
 pub mod configure;
 pub mod context;
@@ -22,6 +26,7 @@ mod modules;
 pub mod print;
 mod segment;
 mod serde_utils;
+pub mod timings;
 mod utils;
 
 #[cfg(test)]
@@ -9,8 +9,10 @@ use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::generate;
 use rand::Rng;
 use starship::context::{Context, Properties, Target};
-use starship::module::ALL_MODULES;
-use starship::{bug_report, configure, init, logger, num_rayon_threads, print, shadow};
+use starship::{
+    background, bug_report, config_check, config_editor, configure, init, logger,
+    num_rayon_threads, print, shadow, timings,
+};
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -57,23 +59,60 @@ fn generate_completions(shell: CompletionShell) {
     }
 }
 
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Check the configuration for unknown keys, invalid styles and broken format strings
+    Check {
+        /// The config file to check instead of the current one
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PresetCommands {
+    /// Merge presets into the current configuration, keeping the values that are already set
+    Apply {
+        /// The presets to merge, later presets taking precedence over earlier ones
+        #[clap(required = true, value_enum)]
+        names: Vec<print::Preset>,
+        /// Replace the values that are already set with those of the presets
+        #[clap(long)]
+        overwrite: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum Commands {
+    /// Prints `light` or `dark` depending on the background color of the terminal
+    Background,
     /// Create a pre-populated GitHub issue with information about your configuration
-    BugReport,
+    BugReport {
+        /// Write a bundle for offline support to a directory, or a tar archive if it ends with .tar
+        #[clap(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Redact the config keys and environment variables that match this regex from the bundle.
+        /// Module output and logs are not redacted
+        #[clap(long, value_name = "REGEX", requires = "output", default_value = bug_report::DEFAULT_REDACT_PATTERN)]
+        redact: String,
+    },
     /// Generate starship shell completions for your shell to stdout
     Completions {
         #[clap(value_enum)]
         shell: CompletionShell,
     },
     /// Edit the starship configuration
+    #[clap(args_conflicts_with_subcommands = true)]
     Config {
+        #[clap(subcommand)]
+        command: Option<ConfigCommands>,
         /// Configuration key to edit
         #[clap(requires = "value")]
         name: Option<String>,
         /// Value to place into that key
         value: Option<String>,
     },
+    /// Browse and edit the starship configuration in the terminal, with a preview of the prompt
+    Configure,
     /// Explains the currently showing modules
     Explain(Properties),
     ///  Prints the shell function used to execute starship
@@ -81,6 +120,9 @@ enum Commands {
         shell: String,
         #[clap(long)]
         print_full_init: bool,
+        /// Check that the init script of the current shell works, from within the shell
+        #[clap(long, conflicts_with = "print_full_init")]
+        check: bool,
     },
     ///  Prints a specific prompt module
     Module {
@@ -90,11 +132,17 @@ enum Commands {
         /// List out all supported modules
         #[clap(short, long)]
         list: bool,
+        /// Also list the description and variables of each module
+        #[clap(long, requires = "list")]
+        verbose: bool,
         #[clap(flatten)]
         properties: Properties,
     },
     /// Prints a preset config
+    #[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Preset {
+        #[clap(subcommand)]
+        command: Option<PresetCommands>,
         /// The name of preset to be printed
         #[clap(required_unless_present("list"), value_enum)]
         name: Option<print::Preset>,
@@ -124,16 +172,50 @@ enum Commands {
         /// Print the continuation prompt (instead of the standard left prompt)
         #[clap(long, conflicts_with = "right", conflicts_with = "profile")]
         continuation: bool,
+        /// Print the transient prompt that replaces previous prompts, see `transient_format`
+        #[clap(
+            long,
+            conflicts_with = "right",
+            conflicts_with = "profile",
+            conflicts_with = "continuation"
+        )]
+        transient: bool,
         #[clap(flatten)]
         properties: Properties,
     },
     /// Generate random session key
     Session,
+    /// Prints the escape sequence that sets the terminal title, see `title_format`
+    Title {
+        /// Print the title shown while COMMAND runs, see `title_preexec_format`
+        #[clap(long, value_name = "COMMAND", allow_hyphen_values = true)]
+        preexec: Option<String>,
+        /// Print the title as plain text, without the escape sequence
+        #[clap(long)]
+        plain: bool,
+        #[clap(flatten)]
+        properties: Properties,
+    },
     /// Prints time in milliseconds
     #[clap(hide = true)]
     Time,
     /// Prints timings of all active modules
-    Timings(Properties),
+    Timings {
+        /// Re-enable the modules that were disabled for being slow in the current directory
+        #[clap(long)]
+        reset: bool,
+        /// Re-enable slow modules in all directories instead of only the current one
+        #[clap(long, requires = "reset")]
+        all: bool,
+        /// Print p50 and p95 render times from the timings log, see `record_timings`
+        #[clap(long, conflicts_with = "reset")]
+        report: bool,
+        /// Write a Chrome trace of rendering the prompt to FILE
+        #[clap(long, value_name = "FILE", conflicts_with_all = ["reset", "report"])]
+        trace: Option<PathBuf>,
+        #[clap(flatten)]
+        properties: Properties,
+    },
     /// Toggle a given starship module
     Toggle {
         /// The name of the module to be toggled
@@ -199,8 +281,11 @@ fn main() {
         Commands::Init {
             shell,
             print_full_init,
+            check,
         } => {
-            if print_full_init {
+            if check {
+                init::check(&shell);
+            } else if print_full_init {
                 init::init_main(&shell).expect("can't init_main");
             } else {
                 init::init_stub(&shell).expect("can't init_stub");
@@ -211,33 +296,42 @@ fn main() {
             right,
             profile,
             continuation,
+            transient,
         } => {
-            let target = match (right, profile, continuation) {
-                (true, _, _) => Target::Right,
-                (_, Some(profile_name), _) => Target::Profile(profile_name),
-                (_, _, true) => Target::Continuation,
-                (_, _, _) => Target::Main,
+            let target = match (right, profile, continuation, transient) {
+                (true, _, _, _) => Target::Right,
+                (_, Some(profile_name), _, _) => Target::Profile(profile_name),
+                (_, _, true, _) => Target::Continuation,
+                (_, _, _, true) => Target::Transient,
+                (_, _, _, _) => Target::Main,
             };
             print::prompt(properties, target);
         }
         Commands::Module {
             name,
             list,
+            verbose,
             properties,
         } => {
             if list {
-                println!("Supported modules list");
-                println!("----------------------");
-                for modules in ALL_MODULES {
-                    println!("{modules}");
-                }
+                print::list_modules(verbose);
             }
             if let Some(module_name) = name {
                 print::module(&module_name, properties);
             }
         }
-        Commands::Preset { name, list, output } => print::preset_command(name, output, list),
-        Commands::Config { name, value } => {
+        Commands::Preset {
+            command: Some(PresetCommands::Apply { names, overwrite }),
+            ..
+        } => configure::apply_presets(&Context::default(), &names, overwrite),
+        Commands::Preset {
+            name, list, output, ..
+        } => print::preset_command(name, output, list),
+        Commands::Config {
+            command: Some(ConfigCommands::Check { path }),
+            ..
+        } => config_check::check(path),
+        Commands::Config { name, value, .. } => {
             let context = Context::default();
             if let Some(name) = name {
                 if let Some(value) = value {
@@ -248,13 +342,22 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Commands::Configure => {
+            if let Err(error) = config_editor::run(&Context::default()) {
+                eprintln!("Could not run the config editor: {error}");
+                std::process::exit(1);
+            }
+        }
         Commands::PrintConfig { default, name } => {
             configure::print_configuration(&Context::default(), default, &name);
         }
         Commands::Toggle { name, value } => {
             configure::toggle_configuration(&Context::default(), &name, &value);
         }
-        Commands::BugReport => bug_report::create(),
+        Commands::BugReport { output, redact } => match output {
+            Some(output) => bug_report::create_bundle(&output, &redact),
+            None => bug_report::create(),
+        },
         Commands::Time => {
             match SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
@@ -265,8 +368,30 @@ fn main() {
             }
         }
         Commands::Explain(props) => print::explain(props),
-        Commands::Timings(props) => print::timings(props),
+        Commands::Title {
+            preexec,
+            plain,
+            properties,
+        } => print::title(properties, preexec.as_deref(), plain),
+        Commands::Timings {
+            reset,
+            all,
+            report,
+            trace,
+            properties,
+        } => {
+            if reset {
+                timings::reset(&Context::new(properties, Target::Main), all);
+            } else if report {
+                timings::report(&Context::new(properties, Target::Main));
+            } else if let Some(path) = trace {
+                timings::trace(&Context::new(properties, Target::Main), &path);
+            } else {
+                print::timings(properties);
+            }
+        }
         Commands::Completions { shell } => generate_completions(shell),
+        Commands::Background => background::print_background(),
         Commands::Session => println!(
             "{}",
             rand::rng()
@@ -15,11 +15,13 @@ pub const ALL_MODULES: &[&str] = &[
     "bun",
     "c",
     "character",
+    "cloud_context",
     "cmake",
     "cmd_duration",
     "cobol",
     "conda",
     "container",
+    "container_engine",
     "cpp",
     "crystal",
     "daml",
@@ -33,6 +35,7 @@ pub const ALL_MODULES: &[&str] = &[
     "elm",
     "erlang",
     "fennel",
+    "filesystem",
     "fill",
     "fortran",
     "fossil_branch",
@@ -56,6 +59,7 @@ pub const ALL_MODULES: &[&str] = &[
     "java",
     "jobs",
     "julia",
+    "k8s_manifest",
     "kotlin",
     "kubernetes",
     "line_break",
@@ -109,6 +113,48 @@ pub const ALL_MODULES: &[&str] = &[
     "zig",
 ];
 
+/// A variable that a module provides to its format strings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Variable {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Whether the variable holds a style, which can only be used in style strings
+    pub style: bool,
+    /// The config keys of the format strings the variable is provided to, empty for `format`
+    /// and every other format string of the module
+    pub formats: &'static [&'static str],
+}
+
+impl Variable {
+    pub const fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            style: false,
+            formats: &[],
+        }
+    }
+
+    pub const fn style(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            style: true,
+            formats: &[],
+        }
+    }
+
+    /// Limits the variable to the format strings at the config keys `formats`
+    pub const fn only_in(self, formats: &'static [&'static str]) -> Self {
+        Self { formats, ..self }
+    }
+
+    /// Whether the variable is provided to the format string at the config key `key`
+    pub fn is_in(&self, key: &str) -> bool {
+        self.formats.is_empty() || self.formats.contains(&key)
+    }
+}
+
 /// A module is a collection of segments showing data for a single integration
 /// (e.g. The git module shows the current git branch and status)
 pub struct Module<'a> {
@@ -8,7 +8,7 @@ use ini::Ini;
 use serde_json as json;
 use sha1::{Digest, Sha1};
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::aws::AwsConfig;
 use crate::formatter::StringFormatter;
@@ -252,6 +252,42 @@ fn has_source_profile(
     Some(has_credential_process || has_credentials)
 }
 
+fn has_any_credentials(
+    context: &Context,
+    aws_profile: Option<&Profile>,
+    aws_config: &AwsConfigFile,
+    aws_creds: &AwsCredsFile,
+) -> bool {
+    has_credential_process_or_sso(context, aws_profile, aws_config, aws_creds).unwrap_or(false)
+        || has_source_profile(context, aws_profile, aws_config, aws_creds).unwrap_or(false)
+        || has_defined_credentials(context, aws_profile, aws_creds).unwrap_or(false)
+}
+
+/// Returns the active profile and region, if credentials for the profile are available.
+/// Used by the `cloud_context` module.
+pub(super) fn get_profile_and_region(
+    context: &Context,
+) -> Option<(Option<Profile>, Option<Region>)> {
+    let aws_config = OnceCell::new();
+    let aws_creds = OnceCell::new();
+
+    let (aws_profile, aws_region) = get_aws_profile_and_region(context, &aws_config);
+    if aws_profile.is_none() && aws_region.is_none() {
+        return None;
+    }
+
+    has_any_credentials(context, aws_profile.as_ref(), &aws_config, &aws_creds)
+        .then_some((aws_profile, aws_region))
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("region", "The current AWS region"),
+    Variable::new("profile", "The current AWS profile"),
+    Variable::new("duration", "The temporary credentials validity duration"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("aws");
     let config: AwsConfig = AwsConfig::try_load(module.config);
@@ -266,11 +302,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     // only display in the presence of credential_process, source_profile or valid credentials
     if !config.force_display
-        && !has_credential_process_or_sso(context, aws_profile.as_ref(), &aws_config, &aws_creds)
-            .unwrap_or(false)
-        && !has_source_profile(context, aws_profile.as_ref(), &aws_config, &aws_creds)
-            .unwrap_or(false)
-        && !has_defined_credentials(context, aws_profile.as_ref(), &aws_creds).unwrap_or(false)
+        && !has_any_credentials(context, aws_profile.as_ref(), &aws_config, &aws_creds)
     {
         return None;
     }
@@ -1,11 +1,14 @@
+use ini::Ini;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::azure::AzureConfig;
 use crate::formatter::StringFormatter;
+use crate::utils::render_time;
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -23,11 +26,67 @@ struct User {
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct Subscription {
+    #[serde(default)]
+    id: Option<String>,
     name: String,
     user: User,
     is_default: bool,
+    #[serde(default)]
+    tenant_id: Option<String>,
+    #[serde(default)]
+    environment_name: Option<String>,
 }
 
+/// The subset of the MSAL token cache (`msal_token_cache.json`) used to determine
+/// when the cached `az` CLI credentials expire.
+#[derive(Deserialize, Default)]
+struct MsalTokenCache {
+    #[serde(rename = "AccessToken", default)]
+    access_tokens: HashMap<String, MsalAccessToken>,
+    #[serde(rename = "Account", default)]
+    accounts: HashMap<String, MsalAccount>,
+}
+
+#[derive(Deserialize)]
+struct MsalAccessToken {
+    #[serde(default)]
+    home_account_id: String,
+    #[serde(default)]
+    realm: String,
+    expires_on: String,
+}
+
+#[derive(Deserialize)]
+struct MsalAccount {
+    #[serde(default)]
+    home_account_id: String,
+    #[serde(default)]
+    username: String,
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "subscription",
+        "The name (or alias) of the default subscription",
+    ),
+    Variable::new("subscription_id", "The id of the default subscription"),
+    Variable::new("username", "The user logged into the default subscription"),
+    Variable::new(
+        "tenant_id",
+        "The tenant id (or alias) of the default subscription",
+    ),
+    Variable::new(
+        "environment",
+        "The cloud environment of the default subscription",
+    ),
+    Variable::new(
+        "duration",
+        "Time until the cached access token for the tenant expires",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("azure");
     let config = AzureConfig::try_load(module.config);
@@ -45,6 +104,22 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let subscription = subscription.unwrap();
 
+    let duration = get_token_cache_duration(context, &subscription).map(|duration| {
+        if duration > 0 {
+            render_time((duration * 1000) as u128, false)
+        } else {
+            config.expiration_symbol.to_string()
+        }
+    });
+
+    let tenant_id = subscription.tenant_id.as_ref().map(|tenant_id| {
+        config
+            .tenant_aliases
+            .get(tenant_id)
+            .copied()
+            .unwrap_or(tenant_id)
+    });
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -62,6 +137,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     .copied()
                     .unwrap_or(&subscription.name))),
                 "username" => Some(Ok(&subscription.user.name)),
+                "subscription_id" => subscription.id.as_deref().map(Ok),
+                "tenant_id" => tenant_id.map(Ok),
+                "environment" => subscription.environment_name.as_deref().map(Ok),
+                "duration" => duration.as_deref().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -100,6 +179,63 @@ fn load_azure_profile(config_path: &PathBuf) -> Option<AzureProfile> {
     }
 }
 
+/// Returns the name of the default subscription and the default location configured
+/// through `az config set defaults.location=...`. Used by the `cloud_context` module.
+pub(super) fn get_subscription_and_location(
+    context: &Context,
+) -> Option<(Option<String>, Option<String>)> {
+    let subscription = get_azure_profile_info(context)?;
+    let location = get_config_file_location(context)
+        .and_then(|config_dir| Ini::load_from_file(config_dir.join("config")).ok())
+        .and_then(|config| {
+            config
+                .section(Some("defaults"))?
+                .get("location")
+                .map(ToOwned::to_owned)
+        });
+    Some((Some(subscription.name), location))
+}
+
+/// Returns the number of seconds until the latest cached access token for the
+/// tenant and user of the given subscription expires (negative if already expired).
+fn get_token_cache_duration(context: &Context, subscription: &Subscription) -> Option<i64> {
+    let tenant_id = subscription.tenant_id.as_deref()?;
+    let mut cache_path = get_config_file_location(context)?;
+    cache_path.push("msal_token_cache.json");
+
+    let token_cache = load_msal_token_cache(&cache_path)?;
+    let account_ids: Vec<&str> = token_cache
+        .accounts
+        .values()
+        .filter(|account| {
+            account
+                .username
+                .eq_ignore_ascii_case(&subscription.user.name)
+        })
+        .map(|account| account.home_account_id.as_str())
+        .collect();
+
+    let expires_on = token_cache
+        .access_tokens
+        .values()
+        .filter(|token| token.realm.eq_ignore_ascii_case(tenant_id))
+        .filter(|token| {
+            account_ids.is_empty() || account_ids.contains(&token.home_account_id.as_str())
+        })
+        .filter_map(|token| token.expires_on.parse::<i64>().ok())
+        .max()?;
+
+    Some(expires_on - chrono::Local::now().timestamp())
+}
+
+fn load_msal_token_cache(cache_path: &Path) -> Option<MsalTokenCache> {
+    let json_data = fs::read_to_string(cache_path).ok()?;
+    let sanitized_json_data = json_data.strip_prefix('\u{feff}').unwrap_or(&json_data);
+    serde_json::from_str::<MsalTokenCache>(sanitized_json_data)
+        .map_err(|err| log::info!("Failed to parse MSAL token cache: {err}"))
+        .ok()
+}
+
 fn get_config_file_location(context: &Context) -> Option<PathBuf> {
     context
         .get_env("AZURE_CONFIG_DIR")
@@ -780,6 +916,243 @@ mod tests {
         dir.close()
     }
 
+    const SINGLE_SUBSCRIPTION_PROFILE: &str = r#"{
+        "installationId": "3deacd2a-b9db-77e1-aa42-23e2f8dfffc3",
+        "subscriptions": [
+            {
+            "id": "f3935dc9-92b5-9a93-da7b-42c325d86939",
+            "name": "Subscription 1",
+            "state": "Enabled",
+            "user": {
+              "name": "user@domain.com",
+              "type": "user"
+            },
+            "isDefault": true,
+            "tenantId": "f0273a19-7779-e40a-00a1-53b8331b3bb6",
+            "environmentName": "AzureChinaCloud",
+            "homeTenantId": "f0273a19-7779-e40a-00a1-53b8331b3bb6",
+            "managedByTenants": []
+          }
+        ]
+      }
+    "#;
+
+    fn generate_token_cache(dir: &TempDir, realm: &str, expires_on: i64) -> io::Result<()> {
+        let token_cache_contents = format!(
+            r#"{{
+            "Account": {{
+                "uid.utid-login.microsoftonline.com-utid": {{
+                    "home_account_id": "uid.utid",
+                    "environment": "login.microsoftonline.com",
+                    "realm": "organizations",
+                    "username": "user@domain.com",
+                    "authority_type": "MSSTS"
+                }}
+            }},
+            "AccessToken": {{
+                "uid.utid-login.microsoftonline.com-accesstoken-client-{realm}-scope": {{
+                    "credential_type": "AccessToken",
+                    "secret": "secret",
+                    "home_account_id": "uid.utid",
+                    "environment": "login.microsoftonline.com",
+                    "client_id": "04b07795-8ddb-461a-bbee-02f9e1bf7b46",
+                    "target": "https://management.core.windows.net//.default",
+                    "realm": "{realm}",
+                    "token_type": "Bearer",
+                    "cached_at": "{cached_at}",
+                    "expires_on": "{expires_on}",
+                    "extended_expires_on": "{expires_on}"
+                }},
+                "other.utid-login.microsoftonline.com-accesstoken-client-{realm}-scope": {{
+                    "home_account_id": "other.utid",
+                    "realm": "{realm}",
+                    "expires_on": "{other_expires_on}"
+                }}
+            }}
+        }}"#,
+            cached_at = expires_on - 3600,
+            other_expires_on = expires_on + 7200,
+        );
+        save_string_to_file(
+            dir,
+            &token_cache_contents,
+            String::from("msal_token_cache.json"),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn tenant_environment_and_subscription_id_set_correctly() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, SINGLE_SUBSCRIPTION_PROFILE)?;
+        let dir_path = &dir.path().to_string_lossy();
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "on [$symbol$tenant_id $environment $subscription_id]($style)"
+                disabled = false
+            })
+            .env("AZURE_CONFIG_DIR", dir_path.as_ref())
+            .collect();
+        let expected = Some(format!(
+            "on {}",
+            Color::Blue.bold().paint(
+                "󰠅 f0273a19-7779-e40a-00a1-53b8331b3bb6 AzureChinaCloud f3935dc9-92b5-9a93-da7b-42c325d86939"
+            )
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn tenant_id_with_alias() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, SINGLE_SUBSCRIPTION_PROFILE)?;
+        let dir_path = &dir.path().to_string_lossy();
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "on [$symbol($tenant_id:$subscription)]($style)"
+                disabled = false
+                [azure.tenant_aliases]
+                "f0273a19-7779-e40a-00a1-53b8331b3bb6" = "contoso"
+            })
+            .env("AZURE_CONFIG_DIR", dir_path.as_ref())
+            .collect();
+        let expected = Some(format!(
+            "on {}",
+            Color::Blue.bold().paint("󰠅 contoso:Subscription 1")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn tenant_id_missing_from_profile() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let azure_profile_contents = r#"{
+            "installationId": "3deacd2a-b9db-77e1-aa42-23e2f8dfffc3",
+            "subscriptions": [
+                {
+                "name": "Subscription 1",
+                "user": {
+                  "name": "user@domain.com"
+                },
+                "isDefault": true
+              }
+            ]
+          }
+        "#;
+
+        generate_test_config(&dir, azure_profile_contents)?;
+        let dir_path = &dir.path().to_string_lossy();
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "on [$symbol$subscription( \\($tenant_id\\))]($style)"
+                disabled = false
+            })
+            .env("AZURE_CONFIG_DIR", dir_path.as_ref())
+            .collect();
+        let expected = Some(format!(
+            "on {}",
+            Color::Blue.bold().paint("󰠅 Subscription 1")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn token_expiration_set_from_cache() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, SINGLE_SUBSCRIPTION_PROFILE)?;
+        generate_token_cache(
+            &dir,
+            "f0273a19-7779-e40a-00a1-53b8331b3bb6",
+            chrono::Local::now().timestamp() + 1800,
+        )?;
+        let dir_path = &dir.path().to_string_lossy();
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "on [$symbol$subscription \\[$duration\\]]($style)"
+                disabled = false
+            })
+            .env("AZURE_CONFIG_DIR", dir_path.as_ref())
+            .collect();
+
+        // In principle, "30m" should be correct. However, bad luck in scheduling
+        // on shared runners may delay it.
+        let possible_values = ["30m0s", "29m59s", "29m58s", "29m57s", "29m56s", "29m55s"];
+        let possible_values = possible_values.map(|duration| {
+            Some(format!(
+                "on {}",
+                Color::Blue
+                    .bold()
+                    .paint(format!("󰠅 Subscription 1 [{duration}]"))
+            ))
+        });
+        assert!(
+            possible_values.contains(&actual),
+            "time is not in range: {actual:?}"
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn token_expired_in_cache() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, SINGLE_SUBSCRIPTION_PROFILE)?;
+        generate_token_cache(
+            &dir,
+            "f0273a19-7779-e40a-00a1-53b8331b3bb6",
+            chrono::Local::now().timestamp() - 1800,
+        )?;
+        let dir_path = &dir.path().to_string_lossy();
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "on [$symbol$subscription \\[$duration\\]]($style)"
+                disabled = false
+                expiration_symbol = "expired"
+            })
+            .env("AZURE_CONFIG_DIR", dir_path.as_ref())
+            .collect();
+        let expected = Some(format!(
+            "on {}",
+            Color::Blue.bold().paint("󰠅 Subscription 1 [expired]")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn token_cache_for_other_tenant_is_ignored() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, SINGLE_SUBSCRIPTION_PROFILE)?;
+        generate_token_cache(
+            &dir,
+            "0e8a15ec-b0f5-d355-7062-8ece54c59aee",
+            chrono::Local::now().timestamp() + 1800,
+        )?;
+        let dir_path = &dir.path().to_string_lossy();
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "on [$symbol$subscription( \\[$duration\\])]($style)"
+                disabled = false
+            })
+            .env("AZURE_CONFIG_DIR", dir_path.as_ref())
+            .collect();
+        let expected = Some(format!(
+            "on {}",
+            Color::Blue.bold().paint("󰠅 Subscription 1")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
     fn save_string_to_file(
         dir: &TempDir,
         contents: &str,
@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::battery::BatteryConfig;
 #[cfg(test)]
 use mockall::automock;
@@ -6,6 +6,15 @@ use starship_battery as battery;
 
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "symbol",
+        "Mirrors the `charging_symbol`, `discharging_symbol`, etc. option for the current battery state",
+    ),
+    Variable::new("percentage", "The current charge of the battery"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module for the battery percentage and charging state
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let battery_status = get_battery_status(context)?;
@@ -1,8 +1,14 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::buf::BufConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `buf`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("buf");
     let config: BufConfig = BufConfig::try_load(module.config);
@@ -1,10 +1,16 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::bun::BunConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
 use crate::utils::get_command_string_output;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `bun`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Bun version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("bun");
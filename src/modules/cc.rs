@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::c::{CConfig, CConfigMarker};
 use crate::configs::cc::CcConfig;
@@ -109,6 +109,13 @@ fn create_module<'a, T>(
     Some(module)
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("name", "The name of the compiler"),
+    Variable::new("version", "The version of the compiler"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context, lang: Lang) -> Option<Module<'a>> {
     match lang {
         Lang::C => {
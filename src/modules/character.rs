@@ -1,7 +1,12 @@
-use super::{Context, Module, ModuleConfig, Shell};
+use super::{Context, Module, ModuleConfig, Shell, Variable};
 use crate::configs::character::CharacterConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[Variable::new(
+    "symbol",
+    "A mirror of either `success_symbol`, `error_symbol`, `vimcmd_symbol` or `vimcmd_replace_one_symbol` etc",
+)];
+
 /// Creates a module for the prompt character
 ///
 /// The character segment prints an arrow character in a color dependent on the
@@ -0,0 +1,459 @@
+use regex::Regex;
+use std::sync::LazyLock;
+use yaml_rust2::YamlLoader;
+
+use super::{Context, Module, ModuleConfig, Variable};
+
+use crate::configs::cloud_context::CloudContextConfig;
+use crate::formatter::StringFormatter;
+use crate::segment::Segment;
+use crate::utils;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Provider {
+    Aws,
+    Gcloud,
+    Azure,
+    Openstack,
+}
+
+impl Provider {
+    const ALL: [Self; 4] = [Self::Aws, Self::Gcloud, Self::Azure, Self::Openstack];
+
+    /// The provider name, matching the name of the module which detects its identity
+    fn name(self) -> &'static str {
+        match self {
+            Self::Aws => "aws",
+            Self::Gcloud => "gcloud",
+            Self::Azure => "azure",
+            Self::Openstack => "openstack",
+        }
+    }
+
+    /// Maps a Terraform provider type, `serverless.yml` provider name or CDKTF provider to a
+    /// cloud provider
+    fn from_project_name(name: &str) -> Option<Self> {
+        match name {
+            "aws" => Some(Self::Aws),
+            "google" | "google-beta" | "gcp" => Some(Self::Gcloud),
+            "azure" | "azurerm" | "azuread" | "azapi" => Some(Self::Azure),
+            "openstack" => Some(Self::Openstack),
+            _ => None,
+        }
+    }
+
+    /// Files whose presence alone marks a project as targeting this provider
+    fn marker_files(self) -> &'static [&'static str] {
+        match self {
+            Self::Aws => &["cdk.json", "samconfig.toml"],
+            Self::Gcloud => &["cloudbuild.yaml", "cloudbuild.yml"],
+            Self::Azure => &["azure.yaml"],
+            Self::Openstack => &["clouds.yaml"],
+        }
+    }
+
+    /// Returns the active account and region of this provider, as detected by its module
+    fn identity(self, context: &Context) -> Option<(Option<String>, Option<String>)> {
+        match self {
+            Self::Aws => super::aws::get_profile_and_region(context),
+            Self::Gcloud => super::gcloud::get_project_and_region(context),
+            Self::Azure => super::azure::get_subscription_and_location(context),
+            Self::Openstack => super::openstack::get_cloud_and_region(context),
+        }
+    }
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "provider",
+        "The provider, one of `aws`, `gcloud`, `azure` or `openstack`",
+    ),
+    Variable::new(
+        "account",
+        "The AWS profile, GCP project, Azure subscription or OpenStack cloud",
+    ),
+    Variable::new("region", "The region of the provider"),
+    Variable::new("symbol", "Mirrors the provider's entry in `symbols`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
+/// Creates a module with the active identity of the cloud providers used by the current project
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cloud_context");
+    let config = CloudContextConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let providers = detect_project_providers(context);
+    if providers.is_empty() {
+        return None;
+    }
+
+    let segments = providers
+        .into_iter()
+        .filter_map(|provider| {
+            let (account, region) = provider.identity(context)?;
+            Some(format_provider(context, &config, provider, account, region))
+        })
+        .collect::<Result<Vec<_>, _>>();
+
+    module.set_segments(match segments {
+        Ok(segments) if !segments.is_empty() => segments.into_iter().flatten().collect(),
+        Ok(_) => return None,
+        Err(error) => {
+            log::warn!("Error in module `cloud_context`:\n{error}");
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn format_provider(
+    context: &Context,
+    config: &CloudContextConfig,
+    provider: Provider,
+    account: Option<String>,
+    region: Option<String>,
+) -> Result<Vec<Segment>, crate::formatter::string_formatter::StringFormatterError> {
+    let account = alias_name(account, &config.account_aliases);
+    let region = alias_name(region, &config.region_aliases);
+
+    StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => config
+                    .get_symbol(provider.name())
+                    .or_else(|| CloudContextConfig::default().get_symbol(provider.name())),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "provider" => Some(Ok(provider.name())),
+                "account" => account.as_deref().map(Ok),
+                "region" => region.as_deref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    })
+}
+
+fn alias_name(
+    name: Option<String>,
+    aliases: &std::collections::HashMap<String, &str>,
+) -> Option<String> {
+    name.map(|name| aliases.get(&name).map_or(name, |&alias| alias.to_string()))
+}
+
+/// Determines which cloud providers the project in the current directory targets,
+/// in the order of `Provider::ALL`
+fn detect_project_providers(context: &Context) -> Vec<Provider> {
+    let Ok(dir_contents) = context.dir_contents() else {
+        return Vec::new();
+    };
+
+    let mut detected: Vec<Provider> = Provider::ALL
+        .into_iter()
+        .filter(|provider| {
+            provider
+                .marker_files()
+                .iter()
+                .any(|file| dir_contents.has_file(file))
+        })
+        .collect();
+
+    for file in ["serverless.yml", "serverless.yaml"] {
+        if dir_contents.has_file(file)
+            && let Some(provider) = utils::read_file(context.current_dir.join(file))
+                .ok()
+                .and_then(|contents| parse_serverless_provider(&contents))
+        {
+            detected.push(provider);
+        }
+    }
+
+    if dir_contents.has_file("cdktf.json")
+        && let Ok(contents) = utils::read_file(context.current_dir.join("cdktf.json"))
+    {
+        detected.extend(parse_cdktf_providers(&contents));
+    }
+
+    let terraform_files = dir_contents.files().filter(|file| {
+        file.extension()
+            .is_some_and(|ext| ext == "tf" || ext == "tofu")
+    });
+    for file in terraform_files {
+        if let Ok(contents) = utils::read_file(context.current_dir.join(file)) {
+            detected.extend(parse_terraform_providers(&contents));
+        }
+    }
+
+    Provider::ALL
+        .into_iter()
+        .filter(|provider| detected.contains(provider))
+        .collect()
+}
+
+/// Reads `provider.name` from a Serverless Framework configuration
+fn parse_serverless_provider(contents: &str) -> Option<Provider> {
+    let docs = YamlLoader::load_from_str(contents).ok()?;
+    let name = docs.first()?["provider"]["name"].as_str()?;
+    Provider::from_project_name(name)
+}
+
+/// Reads the `terraformProviders` list (e.g. `"aws@~> 5.0"`) from a CDK for Terraform project
+fn parse_cdktf_providers(contents: &str) -> Vec<Provider> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(contents) else {
+        return Vec::new();
+    };
+
+    json.get("terraformProviders")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+        .filter_map(|provider| {
+            let name = provider.split('@').next()?.trim();
+            Provider::from_project_name(name.rsplit('/').next()?)
+        })
+        .collect()
+}
+
+static TERRAFORM_PROVIDER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^\s*(?:provider\s+"([\w-]+)"|source\s*=\s*"(?:[^"/]+/)*([\w-]+)")"#).unwrap()
+});
+
+/// Finds `provider "<name>"` blocks and `source = "<namespace>/<name>"` provider requirements
+fn parse_terraform_providers(contents: &str) -> Vec<Provider> {
+    TERRAFORM_PROVIDER_REGEX
+        .captures_iter(contents)
+        .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+        .filter_map(|name| Provider::from_project_name(name.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+
+    fn write_gcloud_config(dir: &Path) -> io::Result<()> {
+        fs::write(dir.join("active_config"), "default")?;
+        fs::create_dir_all(dir.join("configurations"))?;
+        fs::write(
+            dir.join("configurations").join("config_default"),
+            "[core]\nproject = my-project\n\n[compute]\nregion = europe-west1\n",
+        )
+    }
+
+    #[test]
+    fn test_parse_terraform_providers() {
+        let input = r#"
+terraform {
+  required_providers {
+    google = {
+      source = "hashicorp/google"
+    }
+  }
+}
+
+provider "aws" {
+  region = "eu-west-1"
+}
+
+provider "kubernetes" {}
+"#;
+        assert_eq!(
+            parse_terraform_providers(input),
+            vec![Provider::Gcloud, Provider::Aws]
+        );
+    }
+
+    #[test]
+    fn test_parse_serverless_provider() {
+        let input = "service: api\nprovider:\n  name: azure\n  region: West US 2\n";
+        assert_eq!(parse_serverless_provider(input), Some(Provider::Azure));
+        assert_eq!(parse_serverless_provider("service: api\n"), None);
+    }
+
+    #[test]
+    fn test_parse_cdktf_providers() {
+        let input = r#"{
+            "language": "typescript",
+            "terraformProviders": ["aws@~> 5.0", "hashicorp/google@5.1.0", "random"]
+        }"#;
+        assert_eq!(
+            parse_cdktf_providers(input),
+            vec![Provider::Aws, Provider::Gcloud]
+        );
+    }
+
+    #[test]
+    fn disabled_by_default() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("cdk.json"), "{}")?;
+
+        let actual = ModuleRenderer::new("cloud_context")
+            .path(dir.path())
+            .env("AWS_PROFILE", "astronauts")
+            .env("AWS_REGION", "ap-northeast-2")
+            .env("AWS_ACCESS_KEY_ID", "dummy")
+            .collect();
+
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn no_cloud_project() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("main.tf"), "provider \"kubernetes\" {}\n")?;
+
+        let actual = ModuleRenderer::new("cloud_context")
+            .path(dir.path())
+            .env("AWS_PROFILE", "astronauts")
+            .env("AWS_REGION", "ap-northeast-2")
+            .env("AWS_ACCESS_KEY_ID", "dummy")
+            .config(toml::toml! {
+                [cloud_context]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn aws_project_with_cdk() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("cdk.json"), "{}")?;
+
+        let actual = ModuleRenderer::new("cloud_context")
+            .path(dir.path())
+            .env("AWS_PROFILE", "astronauts")
+            .env("AWS_REGION", "ap-northeast-2")
+            .env("AWS_ACCESS_KEY_ID", "dummy")
+            .config(toml::toml! {
+                [cloud_context]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!(
+            "on {} ",
+            Color::Blue
+                .bold()
+                .paint("☁️  aws astronauts (ap-northeast-2)")
+        ));
+
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn aws_project_without_credentials() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("cdk.json"), "{}")?;
+
+        let actual = ModuleRenderer::new("cloud_context")
+            .path(dir.path())
+            .env("AWS_PROFILE", "astronauts")
+            .env(
+                "AWS_CONFIG_FILE",
+                dir.path().join("missing").to_string_lossy(),
+            )
+            .env(
+                "AWS_SHARED_CREDENTIALS_FILE",
+                dir.path().join("missing").to_string_lossy(),
+            )
+            .config(toml::toml! {
+                [cloud_context]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn only_relevant_providers_are_shown() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let gcloud_dir = tempfile::tempdir()?;
+        write_gcloud_config(gcloud_dir.path())?;
+        fs::write(
+            dir.path().join("serverless.yml"),
+            "service: api\nprovider:\n  name: google\n",
+        )?;
+
+        let actual = ModuleRenderer::new("cloud_context")
+            .path(dir.path())
+            .env("AWS_PROFILE", "astronauts")
+            .env("AWS_REGION", "ap-northeast-2")
+            .env("AWS_ACCESS_KEY_ID", "dummy")
+            .env("CLOUDSDK_CONFIG", gcloud_dir.path().to_string_lossy())
+            .config(toml::toml! {
+                [cloud_context]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!(
+            "on {} ",
+            Color::Blue
+                .bold()
+                .paint("☁️  gcloud my-project (europe-west1)")
+        ));
+
+        assert_eq!(actual, expected);
+        gcloud_dir.close()?;
+        dir.close()
+    }
+
+    #[test]
+    fn multiple_providers_with_aliases() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let gcloud_dir = tempfile::tempdir()?;
+        write_gcloud_config(gcloud_dir.path())?;
+        fs::write(
+            dir.path().join("main.tf"),
+            "provider \"google\" {}\nprovider \"aws\" {}\nprovider \"openstack\" {}\n",
+        )?;
+
+        let actual = ModuleRenderer::new("cloud_context")
+            .path(dir.path())
+            .env("AWS_PROFILE", "astronauts")
+            .env("AWS_REGION", "ap-northeast-2")
+            .env("AWS_ACCESS_KEY_ID", "dummy")
+            .env("CLOUDSDK_CONFIG", gcloud_dir.path().to_string_lossy())
+            .config(toml::toml! {
+                [cloud_context]
+                disabled = false
+                format = "[$symbol$account@$region]($style) "
+                [cloud_context.symbols]
+                aws = "A "
+                [cloud_context.region_aliases]
+                ap-northeast-2 = "an2"
+                [cloud_context.account_aliases]
+                my-project = "prj"
+            })
+            .collect();
+        let expected = Some(format!(
+            "{} {} ",
+            Color::Blue.bold().paint("A astronauts@an2"),
+            Color::Blue.bold().paint("☁️  prj@europe-west1"),
+        ));
+
+        assert_eq!(actual, expected);
+        gcloud_dir.close()?;
+        dir.close()
+    }
+}
@@ -1,9 +1,15 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::formatter::VersionFormatter;
 
 use crate::configs::cmake::CMakeConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("version", "The version of `cmake`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current `CMake` version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("cmake");
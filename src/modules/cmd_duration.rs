@@ -1,9 +1,14 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::cmd_duration::CmdDurationConfig;
 use crate::formatter::StringFormatter;
 use crate::utils::render_time;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("duration", "The time it took to execute the command"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Outputs the time it took the last command to execute
 ///
 /// Will only print if last command took more than a certain amount of time to
@@ -1,9 +1,15 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::cobol::CobolConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("version", "The version of `cobol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current COBOL version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("cobol");
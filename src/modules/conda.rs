@@ -1,9 +1,15 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use super::utils::directory::truncate;
 use crate::configs::conda::CondaConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("environment", "The current conda environment"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Conda environment
 ///
 /// Will display the Conda environment iff `$CONDA_DEFAULT_ENV` is set.
@@ -0,0 +1,798 @@
+use std::path::{Path, PathBuf};
+
+use yaml_rust2::YamlLoader;
+
+use super::{Context, Module, ModuleConfig, Variable};
+
+use crate::configs::container_engine::ContainerEngineConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+const COMPOSE_FILES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Engine {
+    Docker,
+    Podman,
+    Nerdctl,
+}
+
+impl Engine {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+            Self::Nerdctl => "nerdctl",
+        }
+    }
+
+    /// Whether the engine serves the Docker-compatible HTTP API on its socket
+    const fn has_http_api(self) -> bool {
+        matches!(self, Self::Docker | Self::Podman)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Rootless,
+    Remote,
+    Local,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct EngineContext {
+    engine: Engine,
+    name: String,
+    endpoint: String,
+    location: Location,
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "engine",
+        "The container engine: `docker`, `podman` or `nerdctl`",
+    ),
+    Variable::new(
+        "context",
+        "The engine context, connection or containerd namespace",
+    ),
+    Variable::new(
+        "location",
+        "Mirrors `rootless_symbol`, `remote_symbol` or `local_symbol`",
+    ),
+    Variable::new(
+        "running",
+        "The number of running containers in the compose project",
+    ),
+    Variable::new(
+        "symbol",
+        "Mirrors the value of `symbols` for the current engine",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
+/// Creates a module with the active container engine context
+///
+/// Will display the engine context if one of the following is found, in order:
+///     - `CONTAINER_HOST` or `CONTAINER_CONNECTION` (Podman)
+///     - `CONTAINERD_ADDRESS` or `CONTAINERD_NAMESPACE` (nerdctl)
+///     - `DOCKER_HOST` or `DOCKER_CONTEXT` (Docker)
+///     - The default Podman connection from `podman-connections.json` or `containers.conf`
+///     - A `nerdctl.toml` configuration file
+///     - The current context from the Docker `config.json`
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("container_engine");
+    let config: ContainerEngineConfig = ContainerEngineConfig::try_load(module.config);
+
+    if config.only_with_files
+        && !context
+            .try_begin_scan()?
+            .set_files(&config.detect_files)
+            .set_extensions(&config.detect_extensions)
+            .set_folders(&config.detect_folders)
+            .is_match()
+    {
+        return None;
+    }
+
+    let engine_context = get_engine_context(context)?;
+    let engine = engine_context.engine;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => config.get_symbol(engine.name()),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "engine" => Some(Ok(engine.name().to_string())),
+                "context" => Some(Ok(engine_context.name.clone())),
+                "location" => {
+                    let symbol = match engine_context.location {
+                        Location::Rootless => config.rootless_symbol,
+                        Location::Remote => config.remote_symbol,
+                        Location::Local => config.local_symbol,
+                    };
+                    (!symbol.is_empty()).then(|| Ok(symbol.to_string()))
+                }
+                "running" if config.count_running => get_running_count(context, &engine_context)
+                    .filter(|count| *count > 0)
+                    .map(|count| Ok(count.to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `container_engine`:\n{error}");
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_engine_context(context: &Context) -> Option<EngineContext> {
+    // Environment variables take precedence over any engine's configuration files
+    get_podman_from_env(context)
+        .or_else(|| get_nerdctl_from_env(context))
+        .or_else(|| get_docker_from_env(context))
+        .or_else(|| get_podman_from_config(context))
+        .or_else(|| get_nerdctl_from_config(context))
+        .or_else(|| get_docker_from_config(context))
+}
+
+fn new_context(context: &Context, engine: Engine, name: String, endpoint: String) -> EngineContext {
+    let location = classify_endpoint(context, &endpoint);
+    EngineContext {
+        engine,
+        name,
+        endpoint,
+        location,
+    }
+}
+
+fn get_podman_from_env(context: &Context) -> Option<EngineContext> {
+    if let Some(host) = context.get_env("CONTAINER_HOST") {
+        let name = context
+            .get_env("CONTAINER_CONNECTION")
+            .unwrap_or_else(|| endpoint_name(&host));
+        return Some(new_context(context, Engine::Podman, name, host));
+    }
+
+    let name = context.get_env("CONTAINER_CONNECTION")?;
+    let endpoint = get_podman_connections(context)
+        .into_iter()
+        .find_map(|(_, connections)| {
+            connections
+                .into_iter()
+                .find_map(|(conn, uri)| (conn == name).then_some(uri))
+        })
+        .unwrap_or_else(|| default_podman_endpoint(context));
+    Some(new_context(context, Engine::Podman, name, endpoint))
+}
+
+fn get_podman_from_config(context: &Context) -> Option<EngineContext> {
+    let (name, endpoint) =
+        get_podman_connections(context)
+            .into_iter()
+            .find_map(|(default, connections)| {
+                let default = default?;
+                connections
+                    .into_iter()
+                    .find_map(|(conn, uri)| (conn == default).then_some((conn, uri)))
+            })?;
+    Some(new_context(context, Engine::Podman, name, endpoint))
+}
+
+type Connections = (Option<String>, Vec<(String, String)>);
+
+/// Reads the Podman connections, preferring `podman-connections.json` over `containers.conf`
+fn get_podman_connections(context: &Context) -> Vec<Connections> {
+    let Some(config_dir) = get_config_dir(context).map(|dir| dir.join("containers")) else {
+        return Vec::new();
+    };
+
+    let json_connections = utils::read_file(config_dir.join("podman-connections.json"))
+        .ok()
+        .and_then(|json| parse_podman_connections_json(&json));
+
+    let conf_path = context
+        .get_env("CONTAINERS_CONF")
+        .map_or_else(|| config_dir.join("containers.conf"), PathBuf::from);
+    let conf_connections = utils::read_file(conf_path)
+        .ok()
+        .and_then(|conf| parse_containers_conf(&conf));
+
+    json_connections
+        .into_iter()
+        .chain(conf_connections)
+        .collect()
+}
+
+fn parse_podman_connections_json(json: &str) -> Option<Connections> {
+    let parsed: serde_json::Value = serde_json::from_str(json).ok()?;
+    let connection = parsed.get("Connection")?;
+    let default = connection
+        .get("Default")
+        .and_then(|default| default.as_str())
+        .map(str::to_owned);
+    let connections = connection
+        .get("Connections")?
+        .as_object()?
+        .iter()
+        .filter_map(|(name, conn)| Some((name.clone(), conn.get("URI")?.as_str()?.to_owned())))
+        .collect();
+    Some((default, connections))
+}
+
+fn parse_containers_conf(conf: &str) -> Option<Connections> {
+    let parsed: toml::Table = toml::from_str(conf).ok()?;
+    let engine = parsed.get("engine")?.as_table()?;
+    let default = engine
+        .get("active_service")
+        .and_then(|service| service.as_str())
+        .map(str::to_owned);
+    let connections = engine
+        .get("service_destinations")
+        .and_then(|destinations| destinations.as_table())
+        .map(|destinations| {
+            destinations
+                .iter()
+                .filter_map(|(name, dest)| {
+                    Some((name.clone(), dest.get("uri")?.as_str()?.to_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some((default, connections))
+}
+
+fn default_podman_endpoint(context: &Context) -> String {
+    match context.get_env("XDG_RUNTIME_DIR") {
+        Some(runtime_dir) => format!("unix://{runtime_dir}/podman/podman.sock"),
+        None => "unix:///run/podman/podman.sock".to_string(),
+    }
+}
+
+fn get_nerdctl_from_env(context: &Context) -> Option<EngineContext> {
+    let address = context.get_env("CONTAINERD_ADDRESS");
+    let namespace = context.get_env("CONTAINERD_NAMESPACE");
+    if address.is_none() && namespace.is_none() {
+        return None;
+    }
+
+    let endpoint = address.unwrap_or_else(|| "/run/containerd/containerd.sock".to_string());
+    let name = namespace.unwrap_or_else(|| "default".to_string());
+    Some(new_context(context, Engine::Nerdctl, name, endpoint))
+}
+
+fn get_nerdctl_from_config(context: &Context) -> Option<EngineContext> {
+    // nerdctl only reads the per-user configuration file in rootless mode
+    let (path, rootless) = match context.get_env("NERDCTL_TOML") {
+        Some(path) => (PathBuf::from(path), false),
+        None => (get_config_dir(context)?.join("nerdctl/nerdctl.toml"), true),
+    };
+    let parsed: toml::Table = toml::from_str(&utils::read_file(path).ok()?).ok()?;
+
+    let name = parsed
+        .get("namespace")
+        .and_then(|namespace| namespace.as_str())
+        .unwrap_or("default")
+        .to_string();
+    let Some(address) = parsed.get("address").and_then(|address| address.as_str()) else {
+        let mut engine_context = new_context(
+            context,
+            Engine::Nerdctl,
+            name,
+            "/run/containerd/containerd.sock".to_string(),
+        );
+        if rootless {
+            engine_context.location = Location::Rootless;
+        }
+        return Some(engine_context);
+    };
+    Some(new_context(
+        context,
+        Engine::Nerdctl,
+        name,
+        address.to_string(),
+    ))
+}
+
+fn get_docker_from_env(context: &Context) -> Option<EngineContext> {
+    if let Some(host) = context.get_env("DOCKER_HOST") {
+        let name = endpoint_name(&host);
+        return Some(new_context(context, Engine::Docker, name, host));
+    }
+
+    let name = context.get_env("DOCKER_CONTEXT")?;
+    let endpoint = get_docker_context_endpoint(context, &name)?;
+    Some(new_context(context, Engine::Docker, name, endpoint))
+}
+
+fn get_docker_from_config(context: &Context) -> Option<EngineContext> {
+    let json = utils::read_file(get_docker_config_dir(context)?.join("config.json")).ok()?;
+    let parsed: serde_json::Value = serde_json::from_str(&json).ok()?;
+    let name = parsed
+        .get("currentContext")
+        .and_then(|current| current.as_str())
+        .unwrap_or("default")
+        .to_string();
+    let endpoint = get_docker_context_endpoint(context, &name)?;
+    Some(new_context(context, Engine::Docker, name, endpoint))
+}
+
+fn get_docker_config_dir(context: &Context) -> Option<PathBuf> {
+    context
+        .get_env("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| Some(context.get_home()?.join(".docker")))
+}
+
+/// Looks up the Docker endpoint of a context in the context metadata store
+fn get_docker_context_endpoint(context: &Context, name: &str) -> Option<String> {
+    if name == "default" {
+        return Some("unix:///var/run/docker.sock".to_string());
+    }
+
+    let meta_dir = get_docker_config_dir(context)?
+        .join("contexts")
+        .join("meta");
+    std::fs::read_dir(meta_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| utils::read_file(entry.path().join("meta.json")).ok())
+        .find_map(|json| {
+            let meta: serde_json::Value = serde_json::from_str(&json).ok()?;
+            if meta.get("Name")?.as_str()? != name {
+                return None;
+            }
+            meta.pointer("/Endpoints/docker/Host")?
+                .as_str()
+                .map(str::to_owned)
+        })
+}
+
+fn get_config_dir(context: &Context) -> Option<PathBuf> {
+    context
+        .get_env("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(context.get_home()?.join(".config")))
+}
+
+/// Returns a short name for an endpoint without a named context, e.g. the host of an ssh URI
+fn endpoint_name(endpoint: &str) -> String {
+    let authority = endpoint
+        .split_once("://")
+        .map(|(_, rest)| rest.split('/').next().unwrap_or_default())
+        .unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    if host.is_empty() {
+        "default".to_string()
+    } else {
+        host.to_string()
+    }
+}
+
+fn classify_endpoint(context: &Context, endpoint: &str) -> Location {
+    let path = match endpoint.split_once("://") {
+        Some(("unix" | "npipe", path)) => path,
+        Some(_) => return Location::Remote,
+        None => endpoint,
+    };
+
+    let runtime_dir = context
+        .get_env("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty());
+    let is_user_runtime_dir = runtime_dir.is_some_and(|dir| Path::new(path).starts_with(dir))
+        || path.starts_with("/run/user/");
+    if is_user_runtime_dir && !path.starts_with("/run/user/0/") {
+        Location::Rootless
+    } else {
+        Location::Local
+    }
+}
+
+/// Returns the compose project name for the current directory, if it contains a compose file
+fn get_compose_project(context: &Context) -> Option<String> {
+    let compose_file = COMPOSE_FILES
+        .iter()
+        .map(|file| context.current_dir.join(file))
+        .find(|path| path.is_file())?;
+
+    if let Some(name) = context.get_env("COMPOSE_PROJECT_NAME") {
+        return Some(name);
+    }
+
+    let name = utils::read_file(compose_file)
+        .ok()
+        .and_then(|contents| YamlLoader::load_from_str(&contents).ok())
+        .and_then(|docs| docs.first()?["name"].as_str().map(str::to_owned));
+    if name.is_some() {
+        return name;
+    }
+
+    let dir_name = context.current_dir.file_name()?.to_string_lossy();
+    let normalized: String = dir_name
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        .collect();
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+fn get_running_count(context: &Context, engine_context: &EngineContext) -> Option<usize> {
+    if !engine_context.engine.has_http_api() {
+        return None;
+    }
+    let socket = engine_context
+        .endpoint
+        .strip_prefix("unix://")
+        .unwrap_or(&engine_context.endpoint);
+    if socket.contains("://") {
+        return None;
+    }
+
+    let project = get_compose_project(context)?;
+    query_running_containers(
+        Path::new(socket),
+        &project,
+        std::time::Duration::from_millis(context.root_config.command_timeout),
+    )
+}
+
+#[cfg(unix)]
+fn query_running_containers(
+    socket: &Path,
+    project: &str,
+    timeout: std::time::Duration,
+) -> Option<usize> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let filters = serde_json::json!({
+        "label": [format!("com.docker.compose.project={project}")]
+    });
+    // HTTP/1.0 so the engine closes the connection instead of using chunked encoding
+    let request = format!(
+        "GET /containers/json?filters={} HTTP/1.0\r\nHost: localhost\r\n\r\n",
+        urlencoding::encode(&filters.to_string())
+    );
+
+    let mut stream = UnixStream::connect(socket)
+        .map_err(|e| log::debug!("Unable to connect to {}: {e}", socket.display()))
+        .ok()?;
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream.set_write_timeout(Some(timeout)).ok()?;
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|e| log::debug!("Unable to read response from {}: {e}", socket.display()))
+        .ok()?;
+    parse_container_list_response(&response)
+}
+
+#[cfg(not(unix))]
+fn query_running_containers(
+    _socket: &Path,
+    _project: &str,
+    _timeout: std::time::Duration,
+) -> Option<usize> {
+    None
+}
+
+fn parse_container_list_response(response: &str) -> Option<usize> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.lines().next()?.split_whitespace().nth(1)?;
+    if status != "200" {
+        log::debug!("Container engine responded with status {status}");
+        return None;
+    }
+    let containers: serde_json::Value = serde_json::from_str(body).ok()?;
+    Some(containers.as_array()?.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_container_list_response;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io::{self, Write};
+
+    fn render(dir: &tempfile::TempDir, home: &tempfile::TempDir) -> ModuleRenderer<'static> {
+        ModuleRenderer::new("container_engine")
+            .path(dir.path())
+            .env("HOME", home.path().to_string_lossy())
+            .env("XDG_RUNTIME_DIR", "/run/user/1000")
+            .config(toml::toml! {
+                [container_engine]
+                disabled = false
+            })
+    }
+
+    #[test]
+    fn not_in_container_project() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+
+        let actual = render(&dir, &home)
+            .env("DOCKER_HOST", "ssh://user@build-box")
+            .collect();
+
+        assert_eq!(None, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn no_engine_configured() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Containerfile"))?.sync_all()?;
+
+        let actual = render(&dir, &home).collect();
+
+        assert_eq!(None, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn docker_host_remote() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Dockerfile"))?.sync_all()?;
+
+        let actual = render(&dir, &home)
+            .env("DOCKER_HOST", "ssh://user@build-box:2222")
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🐳 build-box (remote)")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn docker_context_from_config() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Dockerfile"))?.sync_all()?;
+
+        let docker_dir = home.path().join(".docker");
+        let meta_dir = docker_dir.join("contexts/meta/0123abcd");
+        fs::create_dir_all(&meta_dir)?;
+        fs::write(
+            docker_dir.join("config.json"),
+            r#"{"currentContext": "rootless"}"#,
+        )?;
+        fs::write(
+            meta_dir.join("meta.json"),
+            r#"{"Name":"rootless","Endpoints":{"docker":{"Host":"unix:///run/user/1000/docker.sock"}}}"#,
+        )?;
+
+        let actual = render(&dir, &home).collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🐳 rootless (rootless)")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn docker_default_context_is_local() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Dockerfile"))?.sync_all()?;
+        fs::create_dir_all(home.path().join(".docker"))?;
+        fs::write(home.path().join(".docker/config.json"), "{}")?;
+
+        let actual = render(&dir, &home).collect();
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🐳 default")));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn podman_connection_from_containers_conf() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Containerfile"))?.sync_all()?;
+
+        let conf_dir = home.path().join(".config/containers");
+        fs::create_dir_all(&conf_dir)?;
+        let mut conf = File::create(conf_dir.join("containers.conf"))?;
+        conf.write_all(
+            b"[engine]
+active_service = \"staging\"
+
+[engine.service_destinations.staging]
+uri = \"ssh://core@staging.example.com/run/podman/podman.sock\"
+
+[engine.service_destinations.local]
+uri = \"unix:///run/user/1000/podman/podman.sock\"
+",
+        )?;
+        conf.sync_all()?;
+
+        let actual = render(&dir, &home).collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🦭 staging (remote)")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn podman_connections_json_preferred() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Containerfile"))?.sync_all()?;
+
+        let conf_dir = home.path().join(".config/containers");
+        fs::create_dir_all(&conf_dir)?;
+        fs::write(
+            conf_dir.join("podman-connections.json"),
+            r#"{"Connection":{"Default":"machine","Connections":{"machine":{"URI":"unix:///run/user/1000/podman/podman.sock"}}}}"#,
+        )?;
+        fs::write(
+            conf_dir.join("containers.conf"),
+            "[engine]\nactive_service = \"other\"\n[engine.service_destinations.other]\nuri = \"ssh://other\"\n",
+        )?;
+
+        let actual = render(&dir, &home).collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🦭 machine (rootless)")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn container_host_overrides_docker() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("compose.yaml"))?.sync_all()?;
+
+        let actual = render(&dir, &home)
+            .env("CONTAINER_HOST", "unix:///run/podman/podman.sock")
+            .env("DOCKER_HOST", "tcp://docker.example.com:2376")
+            .collect();
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🦭 default")));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn nerdctl_namespace_from_env() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Dockerfile"))?.sync_all()?;
+
+        let actual = render(&dir, &home)
+            .env("CONTAINERD_NAMESPACE", "k8s.io")
+            .collect();
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("📦 k8s.io")));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn nerdctl_rootless_config() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        File::create(dir.path().join("Dockerfile"))?.sync_all()?;
+
+        let conf_dir = home.path().join(".config/nerdctl");
+        fs::create_dir_all(&conf_dir)?;
+        fs::write(conf_dir.join("nerdctl.toml"), "namespace = \"dev\"\n")?;
+
+        let actual = render(&dir, &home)
+            .config(toml::toml! {
+                [container_engine]
+                disabled = false
+                format = "[$engine:$context( $location)]($style)"
+            })
+            .collect();
+        let expected = Some(format!(
+            "{}",
+            Color::Blue.bold().paint("nerdctl:dev rootless")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+
+    #[test]
+    fn parse_container_list() {
+        let ok = "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n[{\"Id\":\"a\"},{\"Id\":\"b\"}]";
+        assert_eq!(parse_container_list_response(ok), Some(2));
+
+        let error = "HTTP/1.0 500 Internal Server Error\r\n\r\n{\"message\":\"oops\"}";
+        assert_eq!(parse_container_list_response(error), None);
+
+        assert_eq!(parse_container_list_response("garbage"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn running_compose_containers() -> io::Result<()> {
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempfile::tempdir()?;
+        let home = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("compose.yaml"),
+            "name: shop\nservices: {}\n",
+        )?;
+
+        let socket = home.path().join("docker.sock");
+        let listener = UnixListener::bind(&socket)?;
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let body = if request_line.contains("com.docker.compose.project%3Dshop") {
+                "[{\"Id\":\"web\"},{\"Id\":\"db\"},{\"Id\":\"cache\"}]"
+            } else {
+                "[]"
+            };
+            let mut stream = reader.into_inner();
+            write!(stream, "HTTP/1.0 200 OK\r\n\r\n{body}").unwrap();
+        });
+
+        let actual = render(&dir, &home)
+            .env("DOCKER_HOST", format!("unix://{}", socket.display()))
+            .collect();
+        server.join().unwrap();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🐳 default 3 running")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()?;
+        home.close()
+    }
+}
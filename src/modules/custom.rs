@@ -7,13 +7,19 @@ use std::time::Duration;
 
 use process_control::{ChildExt, Control, Output};
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::{
     config::Either, configs::custom::CustomConfig, formatter::StringFormatter,
     utils::create_command,
 };
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("output", "The output of `command` run in `shell`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a custom module with some configuration
 ///
 /// The relevant TOML config will set the files, extensions, and directories needed
@@ -113,7 +119,7 @@ fn get_config<'a>(module_name: &str, context: &'a Context<'a>) -> Option<&'a tom
         }
     }
 
-    let config = context.config.get_custom_module_config(module_name);
+    let config = context.get_module_config(&format!("custom.{module_name}"));
 
     if config.is_some() {
         return config;
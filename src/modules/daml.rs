@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::daml::DamlConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
 
@@ -6,6 +6,12 @@ const DAML_SDK_VERSION: &str = "sdk-version";
 const DAML_SDK_VERSION_ENV: &str = "DAML_SDK_VERSION";
 const DAML_YAML: &str = "daml.yaml";
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `daml`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Daml version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("daml");
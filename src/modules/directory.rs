@@ -5,17 +5,48 @@ use super::utils::directory_win as directory_utils;
 use super::utils::path::PathExt as SPathExt;
 use indexmap::IndexMap;
 use path_slash::{PathBufExt, PathExt};
+use regex::Regex;
 use std::borrow::Cow;
 use std::iter::FromIterator;
 use std::path::{Path, PathBuf};
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 
 use super::utils::directory::truncate;
 use crate::config::ModuleConfig;
+use crate::config::parse_style_string;
 use crate::configs::directory::DirectoryConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::string_formatter::shell_prompt_escape;
+use crate::formatter::{StringFormatter, string_formatter::StringFormatterError};
+use crate::segment::Segment;
+use crate::utils::{file_url, hyperlink};
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("path", "The current directory path"),
+    Variable::new(
+        "before_root_path",
+        "The path before the git root directory path",
+    ),
+    Variable::new("repo_root", "The git root directory name"),
+    Variable::new(
+        "read_only",
+        "Mirrors the value of option `read_only` in a read-only directory",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+    Variable::style(
+        "read_only_style",
+        "Mirrors the value of option `read_only_style`",
+    ),
+    Variable::style(
+        "repo_root_style",
+        "Mirrors the value of option `repo_root_style`",
+    ),
+    Variable::style(
+        "before_repo_root_style",
+        "Mirrors the value of option `before_repo_root_style`",
+    ),
+];
 
 /// Creates a module with the current logical or physical directory
 ///
@@ -25,6 +56,7 @@ use crate::formatter::StringFormatter;
 /// - Paths beginning with the home directory or with a git repo right inside
 ///   the home directory will be contracted to `~`, or the set `HOME_SYMBOL`
 /// - Paths containing a git repo will contract to begin at the repo root
+/// - Paths beginning with a bookmarked directory will be contracted to the name of the bookmark
 ///
 /// **Substitution**
 /// Paths will undergo user-provided substitutions of substrings
@@ -32,6 +64,15 @@ use crate::formatter::StringFormatter;
 /// **Truncation**
 /// Paths will be limited in length to `3` path components by default.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    module_for_width(context, None)
+}
+
+/// Creates the directory module, shortening the path to fit in `max_width` columns
+///
+/// With `truncate_to_width` enabled, the path is shortened in stages until the rendered
+/// module fits: fish-style abbreviation of the leading components, then eliding the middle
+/// components and finally dropping the leading components.
+pub fn module_for_width<'a>(context: &'a Context, max_width: Option<usize>) -> Option<Module<'a>> {
     let mut module = context.new_module("directory");
     let config: DirectoryConfig = DirectoryConfig::try_load(module.config);
 
@@ -49,6 +90,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     log::debug!("Physical dir: {:?}", &physical_dir);
     log::debug!("Display dir: {:?}", &display_dir);
 
+    // Bookmarks contract the path like the home directory does
+    let bookmark = find_bookmark(display_dir, &home_dir, &config.bookmarks);
+    let (top_level_path, top_level_replacement) = match &bookmark {
+        Some((path, name)) => (path.as_path(), *name),
+        None => (home_dir.as_path(), config.home_symbol),
+    };
+
     // Attempt repository path contraction (if we are in a git repository)
     // Otherwise use the logical path, automatically contracting
     let repo = if config.truncate_to_repo || config.repo_root_style.is_some() {
@@ -56,8 +104,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     } else {
         None
     };
+    // A bookmark inside of the repo takes precedence over the repo root
+    let repo_root = repo.and_then(|r| r.workdir.as_ref()).filter(|&root| {
+        bookmark
+            .as_ref()
+            .is_none_or(|(path, _)| !path.normalised_starts_with(root))
+    });
     let dir_string = if config.truncate_to_repo {
-        repo.and_then(|r| r.workdir.as_ref())
+        repo_root
             .filter(|&root| root != &home_dir)
             .and_then(|root| contract_repo_path(display_dir, root))
     } else {
@@ -66,15 +120,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let mut is_truncated = dir_string.is_some();
 
-    // the home directory if required.
-    let dir_string = dir_string
-        .unwrap_or_else(|| contract_path(display_dir, &home_dir, config.home_symbol).to_string());
+    // the home directory or the bookmark if required.
+    let contracted_dir = contract_path(display_dir, top_level_path, top_level_replacement);
+    let dir_string = dir_string.unwrap_or_else(|| contracted_dir.to_string());
 
     #[cfg(windows)]
     let dir_string = remove_extended_path_prefix(dir_string);
 
     // Apply path substitutions
-    let dir_string = substitute_path(dir_string, &config.substitutions);
+    let substituted_dir = substitute_path(contracted_dir.to_string(), &config.substitutions);
+    let substituted_string = substitute_path(dir_string.clone(), &config.substitutions);
+    // Components before one changed by a substitution can't be matched to a directory
+    let unsubstituted_components = unsubstituted_components(&contracted_dir, &substituted_dir)
+        .min(unsubstituted_components(&dir_string, &substituted_string));
+    let dir_string = substituted_string;
 
     // Truncate the dir string to the maximum number of path components
     let dir_string =
@@ -86,24 +145,26 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         };
 
     let prefix = if is_truncated {
-        // Substitutions could have changed the prefix, so don't allow them and
-        // fish-style path contraction together
-        if config.fish_style_pwd_dir_length > 0 && config.substitutions.is_empty() {
-            // If user is using fish style path, we need to add the segment first
-            let contracted_home_dir = contract_path(display_dir, &home_dir, config.home_symbol);
-            to_fish_style(
-                config.fish_style_pwd_dir_length as usize,
-                &contracted_home_dir,
-                &dir_string,
-            )
-        } else {
-            String::from(config.truncation_symbol)
+        // If user is using fish style path, we need to add the segment first
+        match substituted_dir.strip_suffix(&dir_string) {
+            // Substitutions could have changed the part of the path that was truncated
+            Some(truncated_part) if config.fish_style_pwd_dir_length > 0 => {
+                let (kept, abbreviated) = match truncated_part.strip_prefix(top_level_replacement) {
+                    // The name of the bookmark is kept intact
+                    Some(rest) if bookmark.is_some() => (top_level_replacement, rest),
+                    _ => ("", truncated_part),
+                };
+                let abbreviated =
+                    to_fish_style(config.fish_style_pwd_dir_length as usize, abbreviated, "");
+                format!("{kept}{abbreviated}")
+            }
+            _ => String::from(config.truncation_symbol),
         }
     } else {
         String::new()
     };
 
-    let path_vec = match &repo.and_then(|r| r.workdir.as_ref()) {
+    let path_vec = match repo_root {
         Some(repo_root) if config.repo_root_style.is_some() => {
             let contracted_path = contract_repo_path(display_dir, repo_root)?;
             let repo_path_vec: Vec<&str> = contracted_path.split('/').collect();
@@ -123,21 +184,62 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         _ => [String::new(), String::new(), prefix + dir_string.as_str()],
     };
 
-    let path_vec = if config.use_os_path_sep {
-        path_vec.map(|i| convert_path_sep(&i))
-    } else {
-        path_vec
+    let is_readonly = is_readonly_dir(physical_dir);
+    let render = |path_vec: [String; 3]| {
+        render_path(
+            context,
+            &config,
+            path_vec,
+            is_readonly,
+            unsubstituted_components,
+        )
     };
 
+    let parsed = match max_width {
+        Some(max_width) if config.truncate_to_width => {
+            fit_path_to_width(&config, path_vec, max_width, render)
+        }
+        _ => render(path_vec),
+    };
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `directory`:\n{error}");
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn render_path(
+    context: &Context,
+    config: &DirectoryConfig,
+    path_vec: [String; 3],
+    is_readonly: bool,
+    unsubstituted_components: usize,
+) -> Result<Vec<Segment>, StringFormatterError> {
     let display_format = if path_vec[0].is_empty() && path_vec[1].is_empty() {
         config.format
     } else {
         config.repo_root_format
     };
+
+    // Hyperlinks and segment styles need every component of the path in its own segment
+    let component_segments = (config.hyperlinks || has_segment_styles(config))
+        .then(|| path_component_segments(context, config, &path_vec, unsubstituted_components));
+
+    let path_vec = if config.use_os_path_sep {
+        path_vec.map(|i| convert_path_sep(&i))
+    } else {
+        path_vec
+    };
+
     let repo_root_style = config.repo_root_style.unwrap_or(config.style);
     let before_repo_root_style = config.before_repo_root_style.unwrap_or(config.style);
 
-    let parsed = StringFormatter::new(display_format).and_then(|formatter| {
+    StringFormatter::new(display_format).and_then(|formatter| {
         formatter
             .map_style(|variable| match variable {
                 "style" => Some(Ok(config.style)),
@@ -146,12 +248,21 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "before_repo_root_style" => Some(Ok(before_repo_root_style)),
                 _ => None,
             })
+            .map_variables_to_segments(|variable| {
+                let [before_root_path, repo_root, path] = component_segments.as_ref()?;
+                match variable {
+                    "path" => Some(Ok(path.clone())),
+                    "before_root_path" => Some(Ok(before_root_path.clone())),
+                    "repo_root" => Some(Ok(repo_root.clone())),
+                    _ => None,
+                }
+            })
             .map(|variable| match variable {
                 "path" => Some(Ok(path_vec[2].as_str())),
                 "before_root_path" => Some(Ok(path_vec[0].as_str())),
                 "repo_root" => Some(Ok(path_vec[1].as_str())),
                 "read_only" => {
-                    if is_readonly_dir(physical_dir) {
+                    if is_readonly {
                         Some(Ok(config.read_only))
                     } else {
                         None
@@ -160,17 +271,253 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .parse(None, Some(context))
-    });
+    })
+}
 
-    module.set_segments(match parsed {
-        Ok(segments) => segments,
-        Err(error) => {
-            log::warn!("Error in module `directory`:\n{error}");
-            return None;
+fn has_segment_styles(config: &DirectoryConfig) -> bool {
+    config.mount_point_style.is_some()
+        || config.symlink_style.is_some()
+        || config.read_only_segment_style.is_some()
+        || !config.segment_styles.is_empty()
+}
+
+/// Splits the displayed path into a segment for each component and separator
+///
+/// Components are matched to directories from the end of the path, as contraction,
+/// truncation and abbreviation only change the leading components. Components matched
+/// to a directory are styled by the segment style rules and turned into `file://`
+/// hyperlinks. Components before an elided or truncated part of the path are left as is,
+/// as are all but the last `unsubstituted_components` components, which substitutions
+/// left unchanged.
+fn path_component_segments(
+    context: &Context,
+    config: &DirectoryConfig,
+    path_vec: &[String; 3],
+    unsubstituted_components: usize,
+) -> [Vec<Segment>; 3] {
+    let display_dir = if config.use_logical_path {
+        &context.logical_dir
+    } else {
+        &context.current_dir
+    };
+
+    let full_path = path_vec.concat();
+    let components: Vec<&str> = full_path.split('/').collect();
+    let mut dirs: Vec<Option<&Path>> = vec![None; components.len()];
+    for (dir, (component, ancestor)) in dirs
+        .iter_mut()
+        .rev()
+        .zip(components.iter().rev().zip(display_dir.ancestors()))
+        .take(unsubstituted_components)
+    {
+        let is_shortened = *component == config.elision_symbol
+            || (!config.truncation_symbol.is_empty()
+                && component.starts_with(config.truncation_symbol));
+        if is_shortened {
+            break;
         }
-    });
+        *dir = Some(ancestor);
+    }
 
-    Some(module)
+    let home_dir = context.get_home().unwrap_or_default();
+    let globs: Vec<(Regex, &str)> = config
+        .segment_styles
+        .iter()
+        .filter_map(|(pattern, &style)| Some((glob_to_regex(pattern, &home_dir)?, style)))
+        .collect();
+
+    let shell = context.shell;
+    let separator = if config.use_os_path_sep {
+        std::path::MAIN_SEPARATOR_STR
+    } else {
+        "/"
+    };
+    let mut index = 0;
+    path_vec.each_ref().map(|part| {
+        let mut segments = Vec::new();
+        for (i, component) in part.split('/').enumerate() {
+            if i > 0 {
+                index += 1;
+                segments.extend(Segment::from_text(
+                    None,
+                    shell_prompt_escape(separator, shell),
+                ));
+            }
+            if component.is_empty() {
+                continue;
+            }
+
+            let dir = dirs[index];
+            let text = match dir {
+                Some(dir) if config.hyperlinks => hyperlink(&file_url(dir), component, shell),
+                _ => shell_prompt_escape(component, shell),
+            };
+            let style = dir
+                .and_then(|dir| segment_style(config, &globs, dir))
+                .and_then(|style| parse_style_string(style, Some(context)));
+            segments.extend(Segment::from_text(style, text));
+        }
+        segments
+    })
+}
+
+/// The style of the path component for `dir`
+///
+/// The first matching glob pattern takes precedence over the styles of
+/// non-writable directories, symlinks and mount points, in this order.
+fn segment_style<'a>(
+    config: &DirectoryConfig<'a>,
+    globs: &[(Regex, &'a str)],
+    dir: &Path,
+) -> Option<&'a str> {
+    let path = dir.to_slash_lossy();
+    let name = dir.file_name().map(|name| name.to_string_lossy());
+
+    globs
+        .iter()
+        .find(|(glob, _)| {
+            glob.is_match(&path) || name.as_ref().is_some_and(|name| glob.is_match(name))
+        })
+        .map(|(_, style)| *style)
+        .or_else(|| {
+            config
+                .read_only_segment_style
+                .filter(|_| is_readonly_dir(dir))
+        })
+        .or_else(|| {
+            config.symlink_style.filter(|_| {
+                dir.symlink_metadata()
+                    .is_ok_and(|m| m.file_type().is_symlink())
+            })
+        })
+        .or_else(|| {
+            config
+                .mount_point_style
+                .filter(|_| directory_utils::is_mount_point(dir))
+        })
+}
+
+/// Converts a glob pattern matching directory paths into a regex
+///
+/// `*` and `?` don't match `/`, while `**` matches any number of components.
+/// Patterns without a `/` match the name of the directory instead of its path,
+/// and a leading `~` is expanded to the home directory.
+fn glob_to_regex(pattern: &str, home_dir: &Path) -> Option<Regex> {
+    let pattern = match pattern.strip_prefix('~') {
+        Some(rest) => Cow::from(format!("{}{rest}", home_dir.to_slash_lossy())),
+        None => Cow::from(pattern),
+    };
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex)
+        .map_err(|error| log::warn!("Invalid glob pattern `{pattern}` in `directory`: {error}"))
+        .ok()
+}
+
+/// Renders the first candidate path that fits in `max_width` columns
+///
+/// If none of the candidates fit, the most aggressively shortened one is used.
+fn fit_path_to_width<F>(
+    config: &DirectoryConfig,
+    path_vec: [String; 3],
+    max_width: usize,
+    render: F,
+) -> Result<Vec<Segment>, StringFormatterError>
+where
+    F: Fn([String; 3]) -> Result<Vec<Segment>, StringFormatterError>,
+{
+    let full_path = path_vec.concat();
+    let abbreviation_length = config.fish_style_pwd_dir_length.max(1) as usize;
+    let candidates = shorten_path(
+        &full_path,
+        abbreviation_length,
+        config.elision_symbol,
+        config.truncation_symbol,
+    );
+
+    let mut segments = render(path_vec.clone())?;
+    for candidate in candidates {
+        if segments_width(&segments) <= max_width {
+            break;
+        }
+        segments = render(split_repo_root(candidate, &path_vec))?;
+    }
+    Ok(segments)
+}
+
+fn segments_width(segments: &[Segment]) -> usize {
+    segments.iter().map(Segment::width_graphemes).sum()
+}
+
+/// Keeps the repo root styling if the shortened path still ends with the intact repo root
+fn split_repo_root(candidate: String, path_vec: &[String; 3]) -> [String; 3] {
+    if !path_vec[1].is_empty() {
+        let repo_path = format!("{}{}", path_vec[1], path_vec[2]);
+        if let Some(before) = candidate.strip_suffix(&repo_path) {
+            return [before.to_string(), path_vec[1].clone(), path_vec[2].clone()];
+        }
+    }
+    [String::new(), String::new(), candidate]
+}
+
+/// Returns increasingly shorter versions of `path`
+///
+/// The stages are applied in order, each one starting from the result of the previous one:
+/// 1. Abbreviate the leading components fish-style, one at a time from the left
+/// 2. Replace the middle components with `elision_symbol`, keeping the first component
+/// 3. Drop the leading components, prefixing the path with `truncation_symbol`
+///
+/// The last component is never shortened.
+fn shorten_path(
+    path: &str,
+    abbreviation_length: usize,
+    elision_symbol: &str,
+    truncation_symbol: &str,
+) -> Vec<String> {
+    let mut components: Vec<String> = path.split('/').map(String::from).collect();
+    let last = components.len() - 1;
+    let mut candidates = Vec::new();
+
+    for i in 0..last {
+        let abbreviated = to_fish_style(abbreviation_length, &components[i], "");
+        if abbreviated != components[i] {
+            components[i] = abbreviated;
+            candidates.push(components.join("/"));
+        }
+    }
+
+    // Keep the first component (e.g. `~` or the root) and elide more and more of the middle
+    for kept in (1..last.saturating_sub(1)).rev() {
+        let elided = std::iter::once(components[0].as_str())
+            .chain(std::iter::once(elision_symbol))
+            .chain(
+                components[components.len() - kept..]
+                    .iter()
+                    .map(String::as_str),
+            );
+        candidates.push(elided.collect::<Vec<_>>().join("/"));
+    }
+
+    for kept in (1..=last).rev() {
+        let dropped = components[components.len() - kept..].join("/");
+        candidates.push(format!("{truncation_symbol}{dropped}"));
+    }
+
+    candidates
 }
 
 #[cfg(windows)]
@@ -201,6 +548,28 @@ fn is_readonly_dir(path: &Path) -> bool {
     }
 }
 
+/// Finds the most specific bookmark containing `path`
+///
+/// Bookmarks map absolute paths, optionally starting with `~`, to the name
+/// replacing them in the displayed path.
+fn find_bookmark<'a>(
+    path: &Path,
+    home_dir: &Path,
+    bookmarks: &IndexMap<String, &'a str>,
+) -> Option<(PathBuf, &'a str)> {
+    bookmarks
+        .iter()
+        .map(|(bookmark, &name)| {
+            let bookmark_path = match bookmark.strip_prefix('~') {
+                Some(rest) => home_dir.join(rest.trim_start_matches(['/', '\\'])),
+                None => PathBuf::from(bookmark),
+            };
+            (bookmark_path, name)
+        })
+        .filter(|(bookmark_path, _)| path.normalised_starts_with(bookmark_path))
+        .max_by_key(|(bookmark_path, _)| bookmark_path.components().count())
+}
+
 /// Contract the root component of a path
 ///
 /// Replaces the `top_level_path` in a given `full_path` with the provided
@@ -299,6 +668,20 @@ fn substitute_path(dir_string: String, substitutions: &IndexMap<String, &str>) -
     substituted_dir
 }
 
+/// The number of trailing components of `path` that are unchanged in `substituted`
+///
+/// Returns `usize::MAX` if no substitution applied to the path.
+fn unsubstituted_components(path: &str, substituted: &str) -> usize {
+    if path == substituted {
+        return usize::MAX;
+    }
+    path.split('/')
+        .rev()
+        .zip(substituted.split('/').rev())
+        .take_while(|(component, substituted)| component == substituted)
+        .count()
+}
+
 /// Takes part before contracted path and replaces it with fish style path
 ///
 /// Will take the first letter of each directory before the contracted path and
@@ -350,10 +733,11 @@ fn before_root_dir<'a>(path: &'a str, repo: &'a str) -> &'a str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::context::Shell;
     use crate::test::ModuleRenderer;
     use crate::utils::create_command;
     use crate::utils::home_dir;
-    use nu_ansi_term::Color;
+    use nu_ansi_term::{AnsiStrings, Color, Style};
     #[cfg(not(target_os = "windows"))]
     use std::os::unix::fs::symlink;
     #[cfg(target_os = "windows")]
@@ -495,6 +879,41 @@ mod tests {
         assert_eq!(output, "~/s/t/目/a̐/");
     }
 
+    #[test]
+    fn shorten_path_stages() {
+        let candidates = shorten_path("~/projects/starship/src/utils", 1, "…", "");
+        assert_eq!(
+            candidates,
+            vec![
+                "~/p/starship/src/utils",
+                "~/p/s/src/utils",
+                "~/p/s/s/utils",
+                "~/…/s/utils",
+                "~/…/utils",
+                "p/s/s/utils",
+                "s/s/utils",
+                "s/utils",
+                "utils",
+            ]
+        );
+    }
+
+    #[test]
+    fn shorten_path_with_root_and_truncation_symbol() {
+        let candidates = shorten_path("/usr/.local/share", 2, "...", "…/");
+        assert_eq!(
+            candidates,
+            vec![
+                "/us/.local/share",
+                "/us/.lo/share",
+                "/.../share",
+                "…/us/.lo/share",
+                "…/.lo/share",
+                "…/share",
+            ]
+        );
+    }
+
     fn init_repo(path: &Path) -> io::Result<()> {
         create_command("git")?
             .args(["init"])
@@ -676,7 +1095,7 @@ mod tests {
             .config(toml::toml! {
                 [directory]
                 truncation_length = 0
-                fish_style_pwd_dir_length = 2 // Overridden by substitutions
+                fish_style_pwd_dir_length = 2 // Ignored, as the path isn't truncated
                 [directory.substitutions]
                 "regular" = strange_sub
             })
@@ -1783,6 +2202,206 @@ mod tests {
         tmp_dir.close()
     }
 
+    #[test]
+    fn hyperlinks() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let projects = tmp_dir.path().join("my projects");
+        let dir = projects.join("starship");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                hyperlinks = true
+                use_os_path_sep = false
+            })
+            .env("HOME", tmp_dir.path().to_str().unwrap())
+            .path(&dir)
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan.bold().paint(format!(
+                "{}/{}/{}",
+                hyperlink(&file_url(tmp_dir.path()), "~", Shell::Unknown),
+                hyperlink(&file_url(&projects), "my projects", Shell::Unknown),
+                hyperlink(&file_url(&dir), "starship", Shell::Unknown),
+            ))
+        ));
+
+        assert_eq!(expected, actual);
+        assert!(file_url(&projects).ends_with("/my%20projects"));
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn hyperlinks_after_substitution() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path().join("src/main/x");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                hyperlinks = true
+                use_os_path_sep = false
+                [directory.substitutions]
+                "src/main" = "SM"
+                [directory.segment_styles]
+                "~/src" = "red"
+            })
+            .env("HOME", tmp_dir.path().to_str().unwrap())
+            .path(&dir)
+            .collect();
+        // `~` and `SM` don't match a single directory, so only `x` is linked
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan.bold().paint(format!(
+                "~/SM/{}",
+                hyperlink(&file_url(&dir), "x", Shell::Unknown),
+            ))
+        ));
+
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn bookmark_contracts_path() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path().join("work/acme/src/deep");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                use_os_path_sep = false
+                [directory.bookmarks]
+                "~/work" = "work"
+                "~/work/acme" = "🏢 acme"
+            })
+            .env("HOME", tmp_dir.path().to_str().unwrap())
+            .path(&dir)
+            .collect();
+        let expected = Some(format!("{} ", Color::Cyan.bold().paint("🏢 acme/src/deep")));
+
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn bookmark_with_fish_style() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path().join("work/acme/src/deep");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                truncation_length = 1
+                fish_style_pwd_dir_length = 1
+                use_os_path_sep = false
+                [directory.bookmarks]
+                "~/work" = "work"
+            })
+            .env("HOME", tmp_dir.path().to_str().unwrap())
+            .path(&dir)
+            .collect();
+        let expected = Some(format!("{} ", Color::Cyan.bold().paint("work/a/s/deep")));
+
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn substitutions_with_fish_style() {
+        let actual = ModuleRenderer::new("directory")
+            .path("/some/long/network/path/workspace/a/b/c/dev")
+            .config(toml::toml! {
+                [directory]
+                truncation_length = 2
+                fish_style_pwd_dir_length = 1
+                use_os_path_sep = false
+                [directory.substitutions]
+                "/some/long/network/path" = "/net"
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Cyan.bold().paint("/n/w/a/b/c/dev")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn segment_styles_from_globs() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path().join("node_modules/left-pad");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                truncation_length = 2
+                use_os_path_sep = false
+                [directory.segment_styles]
+                "node_*" = "red"
+                "/nonexistent/**" = "green"
+            })
+            .path(&dir)
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            AnsiStrings(&[
+                Color::Red.paint("node_modules"),
+                Color::Cyan.bold().paint("/left-pad"),
+            ])
+        ));
+
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn segment_style_for_symlink() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let real_dir = tmp_dir.path().join("real");
+        fs::create_dir_all(real_dir.join("sub"))?;
+        let link = tmp_dir.path().join("link");
+        symlink(&real_dir, &link)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                truncation_length = 2
+                symlink_style = "italic"
+            })
+            .path(link.join("sub"))
+            .logical_path(link.join("sub"))
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            AnsiStrings(&[
+                Style::new().italic().paint("link"),
+                Color::Cyan.bold().paint("/sub"),
+            ])
+        ));
+
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn glob_patterns() {
+        let home = Path::new("/home/user");
+        let matches =
+            |pattern: &str, path: &str| glob_to_regex(pattern, home).unwrap().is_match(path);
+
+        assert!(matches("~/src/*", "/home/user/src/starship"));
+        assert!(!matches("~/src/*", "/home/user/src/starship/docs"));
+        assert!(matches("/mnt/**", "/mnt/nas/media/films"));
+        assert!(matches("?ocs", "docs"));
+        assert!(!matches("*.git", "starship"));
+    }
+
     #[test]
     fn parent_and_sub_git_repo_are_in_same_name_folder() {
         assert_eq!(
@@ -2,13 +2,21 @@ use std::borrow::Cow;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::direnv::DirenvConfig;
 use crate::formatter::StringFormatter;
 
 use serde::Deserialize;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("loaded", "Whether the current rc file is loaded"),
+    Variable::new("allowed", "Whether the current rc file is allowed"),
+    Variable::new("rc_path", "The current rc file path"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current direnv rc
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("direnv");
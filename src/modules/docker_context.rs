@@ -1,11 +1,17 @@
 use std::path::PathBuf;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::docker_context::DockerContextConfig;
 use crate::formatter::StringFormatter;
 use crate::utils;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("context", "The current docker context"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the currently active Docker context
 ///
 /// Will display the Docker context if the following criteria are met:
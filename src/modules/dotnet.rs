@@ -5,7 +5,7 @@ use std::iter::Iterator;
 use std::path::{Path, PathBuf};
 use std::str;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::dotnet::DotnetConfig;
 use crate::formatter::StringFormatter;
 use crate::utils;
@@ -16,6 +16,16 @@ use crate::formatter::VersionFormatter;
 const GLOBAL_JSON_FILE: &str = "global.json";
 const PROJECT_JSON_FILE: &str = "project.json";
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `dotnet` sdk"),
+    Variable::new(
+        "tfm",
+        "The Target Framework Moniker that the current project is targeting",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// A module which shows the latest (or pinned) version of the dotnet SDK
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("dotnet");
@@ -117,10 +127,8 @@ fn get_tfm_from_project_file(path: &Path) -> Option<String> {
                 }
             }
             // unescape and decode the text event using the reader encoding
-            Ok(Event::Text(e)) => {
-                if in_tfm {
-                    return e.decode().ok().map(std::borrow::Cow::into_owned);
-                }
+            Ok(Event::Text(e)) if in_tfm => {
+                return e.decode().ok().map(std::borrow::Cow::into_owned);
             }
             Ok(Event::Eof) => break, // exits the loop when reaching end of file
             Err(e) => {
@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::elixir::ElixirConfig;
 use crate::formatter::StringFormatter;
@@ -7,6 +7,13 @@ use crate::formatter::VersionFormatter;
 use std::ops::Deref;
 use std::sync::LazyLock;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `elixir`"),
+    Variable::new("otp_version", "The otp version of `elixir`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Create a module with the current Elixir version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("elixir");
@@ -1,10 +1,16 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 use std::borrow::Cow;
 
 use crate::config::ModuleConfig;
 use crate::configs::env_var::EnvVarConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("env_value", "The environment value of option `variable`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the value of the chosen environment variable
 ///
 /// Will display the environment variable's value if all of the following criteria are met:
@@ -14,8 +20,7 @@ use crate::formatter::StringFormatter;
 pub fn module<'a>(name: Option<&str>, context: &'a Context) -> Option<Module<'a>> {
     let toml_config = match name {
         Some(name) => context
-            .config
-            .get_config(&["env_var", name])
+            .get_module_config(&format!("env_var.{name}"))
             .map(Cow::Borrowed),
         None => context
             .config
@@ -0,0 +1,396 @@
+use std::path::{Path, PathBuf};
+
+use systemstat::data::ByteSize;
+
+use super::memory_usage::display_bs;
+use super::{Context, Module, ModuleConfig, Variable};
+
+use crate::configs::filesystem::FilesystemConfig;
+use crate::formatter::StringFormatter;
+
+/// The filesystem mounted at `mount_point`
+#[derive(Debug, PartialEq, Eq)]
+struct Mount {
+    mount_point: PathBuf,
+    fs_type: String,
+    source: String,
+}
+
+/// Available and total space of a filesystem in bytes
+struct Usage {
+    avail: u64,
+    total: u64,
+}
+
+impl Usage {
+    fn used_pct(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        100.0 * self.total.saturating_sub(self.avail) as f64 / self.total as f64
+    }
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("fs_type", "The type of the filesystem"),
+    Variable::new(
+        "source",
+        "The device or remote location the filesystem is mounted from",
+    ),
+    Variable::new("mount_point", "The directory the filesystem is mounted on"),
+    Variable::new("free", "The available space on the filesystem"),
+    Variable::new("total", "The total size of the filesystem"),
+    Variable::new("used_pct", "The percentage of the filesystem in use"),
+    Variable::new(
+        "symbol",
+        "Mirrors the value of option `symbol`, or `network_symbol` on network filesystems",
+    ),
+    Variable::style(
+        "style",
+        "Mirrors the value of option `style`, `network_style` or `threshold_style`",
+    ),
+];
+
+/// Creates a module with the type, mount source and free space of the filesystem
+/// containing the current directory
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("filesystem");
+    let config = FilesystemConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let mount = find_mount(context, &context.current_dir)?;
+    let is_network = config.network_fs_types.contains(&mount.fs_type.as_str());
+    // Usage is only shown once it reaches the threshold
+    let usage =
+        mount_usage(&mount).filter(|usage| usage.used_pct().round() as i64 >= config.threshold);
+
+    let symbol = if is_network {
+        config.network_symbol
+    } else {
+        config.symbol
+    };
+    let style = if usage.is_some() {
+        config.threshold_style
+    } else if is_network {
+        config.network_style
+    } else {
+        config.style
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "fs_type" => Some(Ok(mount.fs_type.clone())),
+                "source" => Some(Ok(mount.source.clone())),
+                "mount_point" => Some(Ok(mount.mount_point.to_string_lossy().into_owned())),
+                "free" => Some(Ok(display_bs(ByteSize(usage.as_ref()?.avail)))),
+                "total" => Some(Ok(display_bs(ByteSize(usage.as_ref()?.total)))),
+                "used_pct" => Some(Ok(format!("{:.0}%", usage.as_ref()?.used_pct()))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `filesystem`:\n{error}");
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Whether `git_status` should be skipped, as the current directory is on one of
+/// the filesystem types listed in `filesystem.disable_git_status_on`
+pub fn disables_git_status(context: &Context) -> bool {
+    let config = FilesystemConfig::try_load(context.config.get_module_config("filesystem"));
+    if config.disable_git_status_on.is_empty() {
+        return false;
+    }
+
+    find_mount(context, &context.current_dir).is_some_and(|mount| {
+        config
+            .disable_git_status_on
+            .contains(&mount.fs_type.as_str())
+    })
+}
+
+/// Finds the mount containing `path`, without querying the filesystem itself
+///
+/// Only `/proc/self/mountinfo` is read, so this does not block on an unresponsive
+/// network filesystem the way `statvfs` can.
+#[cfg(target_os = "linux")]
+fn find_mount(context: &Context, path: &Path) -> Option<Mount> {
+    let mountinfo =
+        crate::utils::read_file(crate::utils::context_path(context, "/proc/self/mountinfo"))
+            .map_err(|e| log::debug!("Unable to read mountinfo: {e}"))
+            .ok()?;
+    parse_mountinfo(&mountinfo, path)
+}
+
+#[cfg(target_os = "linux")]
+#[allow(clippy::useless_conversion)] // On some platforms these are not u64
+fn mount_usage(mount: &Mount) -> Option<Usage> {
+    let stat = nix::sys::statvfs::statvfs(&mount.mount_point)
+        .map_err(|e| log::debug!("Unable to statvfs {:?}: {e}", mount.mount_point))
+        .ok()?;
+    let fragment_size = u64::from(stat.fragment_size());
+    Some(Usage {
+        avail: u64::from(stat.blocks_available()) * fragment_size,
+        total: u64::from(stat.blocks()) * fragment_size,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn find_mount(_context: &Context, path: &Path) -> Option<Mount> {
+    use systemstat::{Platform, System};
+
+    let filesystems = System::new()
+        .mounts()
+        .map_err(|e| log::debug!("Unable to list mounts: {e}"))
+        .ok()?;
+    let filesystem = filesystems
+        .into_iter()
+        .filter(|fs| path.starts_with(&fs.fs_mounted_on))
+        .max_by_key(|fs| fs.fs_mounted_on.len())?;
+
+    Some(Mount {
+        mount_point: PathBuf::from(filesystem.fs_mounted_on),
+        fs_type: filesystem.fs_type,
+        source: filesystem.fs_mounted_from,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mount_usage(mount: &Mount) -> Option<Usage> {
+    use systemstat::{Platform, System};
+
+    let filesystem = System::new()
+        .mount_at(&mount.mount_point)
+        .map_err(|e| log::debug!("Unable to get the usage of {:?}: {e}", mount.mount_point))
+        .ok()?;
+    Some(Usage {
+        avail: filesystem.avail.as_u64(),
+        total: filesystem.total.as_u64(),
+    })
+}
+
+/// Finds the mount containing `path` in the contents of `/proc/self/mountinfo`
+///
+/// Each line describes a mount, e.g.
+/// `36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue`
+/// where the 5th field is the mount point, and the filesystem type and the mount source
+/// follow the `-` separator. Later mounts on the same mount point hide the earlier ones.
+#[cfg(any(target_os = "linux", test))]
+fn parse_mountinfo(mountinfo: &str, path: &Path) -> Option<Mount> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let (mount_fields, fs_fields) = line.split_once(" - ")?;
+            let mount_point = PathBuf::from(unescape_mountinfo(mount_fields.split(' ').nth(4)?));
+            let mut fs_fields = fs_fields.split(' ');
+            let fs_type = unescape_mountinfo(fs_fields.next()?);
+            let source = unescape_mountinfo(fs_fields.next()?);
+            Some(Mount {
+                mount_point,
+                fs_type,
+                source,
+            })
+        })
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .fold(None, |best: Option<Mount>, mount| match best {
+            Some(best)
+                if best.mount_point.components().count()
+                    > mount.mount_point.components().count() =>
+            {
+                Some(best)
+            }
+            _ => Some(mount),
+        })
+}
+
+/// Decodes the octal escapes (e.g. `\040` for a space) of a mountinfo field
+#[cfg(any(target_os = "linux", test))]
+fn unescape_mountinfo(field: &str) -> String {
+    let mut bytes = Vec::with_capacity(field.len());
+    let mut rest = field.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..3)
+            .filter(|digits| byte == b'\\' && digits.iter().all(|d| (b'0'..=b'7').contains(d)))
+            .and_then(|digits| u8::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok());
+        match escaped {
+            Some(escaped) => {
+                bytes.push(escaped);
+                rest = &tail[3..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    const MOUNTINFO: &str = "\
+22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw
+35 22 0:31 / /home/user rw,relatime shared:20 - nfs4 fileserver:/export/home rw,vers=4.2
+36 35 0:45 / /home/user/remote\\040box rw,nosuid - fuse.sshfs user@box:/srv rw,user_id=1000
+37 22 0:32 / /tmp rw,nosuid,nodev shared:14 - tmpfs tmpfs rw
+38 22 0:33 / /tmp rw,nosuid,nodev shared:15 - zfs tank/scratch rw
+";
+
+    #[test]
+    fn parse_mountinfo_longest_mount_point() {
+        let mount = parse_mountinfo(MOUNTINFO, Path::new("/home/user/projects"));
+        assert_eq!(
+            mount,
+            Some(Mount {
+                mount_point: PathBuf::from("/home/user"),
+                fs_type: "nfs4".to_string(),
+                source: "fileserver:/export/home".to_string(),
+            })
+        );
+
+        let mount = parse_mountinfo(MOUNTINFO, Path::new("/home/username")).unwrap();
+        assert_eq!(mount.fs_type, "ext4");
+    }
+
+    #[test]
+    fn parse_mountinfo_escaped_mount_point() {
+        let mount = parse_mountinfo(MOUNTINFO, Path::new("/home/user/remote box/src")).unwrap();
+        assert_eq!(mount.mount_point, PathBuf::from("/home/user/remote box"));
+        assert_eq!(mount.fs_type, "fuse.sshfs");
+        assert_eq!(mount.source, "user@box:/srv");
+    }
+
+    #[test]
+    fn parse_mountinfo_stacked_mounts() {
+        let mount = parse_mountinfo(MOUNTINFO, Path::new("/tmp/scratch")).unwrap();
+        assert_eq!(mount.fs_type, "zfs");
+        assert_eq!(mount.source, "tank/scratch");
+    }
+
+    #[test]
+    fn module_disabled_by_default() {
+        let actual = ModuleRenderer::new("filesystem").collect();
+        assert_eq!(actual, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn renderer_with_mountinfo(dir: &Path, fs_type: &str) -> io::Result<ModuleRenderer<'static>> {
+        let renderer = ModuleRenderer::new("filesystem").path(dir);
+        let proc_dir = renderer.root_path().join("proc/self");
+        fs::create_dir_all(&proc_dir)?;
+        fs::write(
+            proc_dir.join("mountinfo"),
+            format!(
+                "22 1 259:2 / / rw - ext4 /dev/sda1 rw\n\
+                 40 22 0:50 / {} rw - {fs_type} server:/export rw\n",
+                dir.display()
+            ),
+        )?;
+        Ok(renderer)
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn local_filesystem() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = renderer_with_mountinfo(dir.path(), "xfs")?
+            .config(toml::toml! {
+                [filesystem]
+                disabled = false
+                threshold = 101
+            })
+            .collect();
+        let expected = Some(format!("on {} ", Color::White.bold().paint("💾 xfs")));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn network_filesystem() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = renderer_with_mountinfo(dir.path(), "nfs4")?
+            .config(toml::toml! {
+                [filesystem]
+                disabled = false
+                threshold = 101
+                format = "[$symbol$fs_type from $source]($style)"
+            })
+            .collect();
+        let expected = Some(format!(
+            "{}",
+            Color::Yellow.bold().paint("🌐 nfs4 from server:/export")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn usage_above_threshold() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = renderer_with_mountinfo(dir.path(), "ext4")?
+            .config(toml::toml! {
+                [filesystem]
+                disabled = false
+                threshold = 0
+                format = "[$used_pct]($style)"
+            })
+            .collect()
+            .unwrap();
+
+        assert!(actual.starts_with(&Color::Red.bold().prefix().to_string()));
+        assert!(actual.contains('%'));
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn git_status_disabled_on_slow_filesystem() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let renderer = renderer_with_mountinfo(dir.path(), "fuse.sshfs")?.config(toml::toml! {
+            [filesystem]
+            disable_git_status_on = ["fuse.sshfs"]
+        });
+        let context: Context = renderer.into();
+        assert!(disables_git_status(&context));
+
+        let renderer = renderer_with_mountinfo(dir.path(), "ext4")?.config(toml::toml! {
+            [filesystem]
+            disable_git_status_on = ["fuse.sshfs"]
+        });
+        let context: Context = renderer.into();
+        assert!(!disables_git_status(&context));
+        dir.close()
+    }
+}
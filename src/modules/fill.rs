@@ -1,9 +1,11 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 
 use crate::config::{ModuleConfig, parse_style_string};
 use crate::configs::fill::FillConfig;
 use crate::segment::Segment;
 
+pub const VARIABLES: &[Variable] = &[];
+
 /// Creates a module that fills the any extra space on the line.
 ///
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
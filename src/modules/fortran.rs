@@ -8,7 +8,14 @@ use crate::{
     formatter::{StringFormatter, VersionFormatter},
 };
 
-use super::{Context, Module};
+use super::{Context, Module, Variable};
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("name", "The name of the compiler"),
+    Variable::new("version", "The version of the Fortran compiler"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
 
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("fortran");
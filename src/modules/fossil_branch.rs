@@ -1,9 +1,15 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::fossil_branch::FossilBranchConfig;
 use crate::formatter::StringFormatter;
 use crate::modules::utils::truncate::truncate_text;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("branch", "The active Fossil branch"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the Fossil branch of the check-out in the current directory
 ///
 /// Will display the branch name if the current directory is a Fossil check-out
@@ -1,10 +1,20 @@
 use regex::Regex;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::fossil_metrics::FossilMetricsConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("added", "The current number of added lines"),
+    Variable::new("deleted", "The current number of deleted lines"),
+    Variable::style("added_style", "Mirrors the value of option `added_style`"),
+    Variable::style(
+        "deleted_style",
+        "Mirrors the value of option `deleted_style`",
+    ),
+];
+
 /// Creates a module with currently added/deleted lines in the Fossil check-out in the current
 /// directory.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
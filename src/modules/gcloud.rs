@@ -4,7 +4,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::{LazyLock, OnceLock};
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::gcloud::GcloudConfig;
 use crate::formatter::StringFormatter;
@@ -81,6 +81,37 @@ fn get_active_config(context: &Context, config_dir: &Path) -> Option<String> {
     })
 }
 
+/// Returns the active project and region of the current gcloud configuration.
+/// Used by the `cloud_context` module.
+pub(super) fn get_project_and_region(
+    context: &Context,
+) -> Option<(Option<String>, Option<String>)> {
+    let (config_name, config_path) = get_current_config(context)?;
+    if config_name == "NONE" {
+        return None;
+    }
+
+    let gcloud_context = GcloudContext::new(&config_name, &config_path);
+    let project = context
+        .get_env("CLOUDSDK_CORE_PROJECT")
+        .or_else(|| gcloud_context.get_project().map(ToOwned::to_owned));
+    let region = gcloud_context.get_region().map(ToOwned::to_owned);
+    Some((project, region))
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("region", "The current GCP region"),
+    Variable::new("account", "The current GCP profile"),
+    Variable::new("domain", "The current GCP profile domain"),
+    Variable::new("project", "The current GCP project"),
+    Variable::new(
+        "active",
+        "The active config name written in `~/.config/gcloud/active_config`",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("gcloud");
     let config: GcloudConfig = GcloudConfig::try_load(module.config);
@@ -1,9 +1,25 @@
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::git_branch::GitBranchConfig;
 use crate::formatter::StringFormatter;
+use crate::modules::utils::git_remote::expand_url_template;
+use crate::utils::hyperlink;
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "branch",
+        "The current branch name, falls back to `HEAD` if there's no current branch (e.g. git detached `HEAD`)",
+    ),
+    Variable::new("remote_name", "The remote name"),
+    Variable::new(
+        "remote_branch",
+        "The name of the branch tracked on `remote_name`",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
 
 /// Creates a module with the Git branch in the current directory
 ///
@@ -75,6 +91,18 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let show_remote = config.always_show_remote
         || (!graphemes.eq(&remote_branch_graphemes) && !remote_branch_graphemes.is_empty());
 
+    let branch_url = if config.hyperlinks {
+        repo.remote
+            .as_ref()
+            .and_then(|remote| remote.branch.as_deref())
+            .zip(repo.remote_url())
+            .and_then(|(branch, url)| {
+                expand_url_template(&config.url_templates, &url, &[("branch", branch)])
+            })
+    } else {
+        None
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -85,6 +113,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "style" => Some(Ok(config.style)),
                 _ => None,
             })
+            .map_no_escaping(|variable| match variable {
+                "branch" => branch_url
+                    .as_deref()
+                    .map(|url| Ok(hyperlink(url, &graphemes.concat(), context.shell))),
+                _ => None,
+            })
             .map(|variable| match variable {
                 "branch" => Some(Ok(graphemes.concat())),
                 "remote_branch" => {
@@ -128,8 +162,9 @@ mod tests {
     use nu_ansi_term::Color;
     use std::io;
 
+    use crate::context::Shell;
     use crate::test::{FixtureProvider, ModuleRenderer, fixture_repo};
-    use crate::utils::create_command;
+    use crate::utils::{create_command, hyperlink};
 
     #[test]
     fn show_nothing_on_empty_dir() -> io::Result<()> {
@@ -452,6 +487,47 @@ mod tests {
         remote_dir.close()
     }
 
+    #[test]
+    fn test_branch_hyperlink() -> io::Result<()> {
+        let repo_dir = fixture_repo(FixtureProvider::Git)?;
+
+        for args in [
+            &[
+                "remote",
+                "set-url",
+                "origin",
+                "git@gitlab.example.com:team/app.git",
+            ][..],
+            &["config", "branch.master.remote", "origin"],
+            &["config", "branch.master.merge", "refs/heads/main"],
+        ] {
+            create_command("git")?
+                .args(args)
+                .current_dir(repo_dir.path())
+                .output()?;
+        }
+
+        let actual = ModuleRenderer::new("git_branch")
+            .path(repo_dir.path())
+            .config(toml::toml! {
+                [git_branch]
+                format = "$branch"
+                hyperlinks = true
+                [git_branch.url_templates]
+                "gitlab.example.com" = "{base_url}/-/tree/{branch}"
+            })
+            .collect();
+
+        let expected = hyperlink(
+            "https://gitlab.example.com/team/app/-/tree/main",
+            "master",
+            Shell::Unknown,
+        );
+
+        assert_eq!(Some(expected), actual);
+        repo_dir.close()
+    }
+
     #[test]
     fn test_branch_fallback_on_detached() -> io::Result<()> {
         let repo_dir = fixture_repo(FixtureProvider::Git)?;
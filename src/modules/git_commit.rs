@@ -1,9 +1,17 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use gix::commit::describe::SelectRef::AllTags;
 
 use crate::configs::git_commit::GitCommitConfig;
 use crate::context::Repo;
 use crate::formatter::StringFormatter;
+use crate::modules::utils::git_remote::expand_url_template;
+use crate::utils::hyperlink;
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("hash", "The current git commit hash"),
+    Variable::new("tag", "The tag name if showing tag info is enabled"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
 
 /// Creates a module with the Git commit in the current directory
 ///
@@ -27,6 +35,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "style" => Some(Ok(config.style)),
                 _ => None,
             })
+            .map_no_escaping(|variable| match variable {
+                "hash" if config.hyperlinks => {
+                    let repo = context.get_repo().ok()?;
+                    let hash = git_hash(repo, &config)?;
+                    let url = git_commit_url(repo, &config)?;
+                    Some(Ok(hyperlink(&url, &hash, context.shell)))
+                }
+                _ => None,
+            })
             .map(|variable| match variable {
                 "hash" => Some(Ok(git_hash(context.get_repo().ok()?, &config)?)),
                 "tag" if !config.tag_disabled => Some(Ok(format!(
@@ -76,13 +93,25 @@ fn git_hash(repo: &Repo, config: &GitCommitConfig) -> Option<String> {
     ))
 }
 
+fn git_commit_url(repo: &Repo, config: &GitCommitConfig) -> Option<String> {
+    let head_id = repo.open().head_id().ok()?.to_string();
+    let remote_url = repo.remote_url()?;
+
+    expand_url_template(
+        &config.url_templates,
+        &remote_url,
+        &[("commit", head_id.as_str())],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use nu_ansi_term::Color;
     use std::{io, str};
 
+    use crate::context::Shell;
     use crate::test::{FixtureProvider, ModuleRenderer, fixture_repo};
-    use crate::utils::create_command;
+    use crate::utils::{create_command, hyperlink};
 
     #[test]
     fn show_nothing_on_empty_dir() -> io::Result<()> {
@@ -462,4 +491,50 @@ mod tests {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_render_commit_hash_hyperlink() -> io::Result<()> {
+        let repo_dir = fixture_repo(FixtureProvider::Git)?;
+
+        create_command("git")?
+            .args([
+                "remote",
+                "set-url",
+                "origin",
+                "https://github.com/starship/starship.git",
+            ])
+            .current_dir(repo_dir.path())
+            .output()?;
+
+        let git_output = create_command("git")?
+            .args(["rev-parse", "HEAD"])
+            .current_dir(repo_dir.path())
+            .output()?
+            .stdout;
+        let commit = str::from_utf8(&git_output).unwrap().trim();
+
+        let actual = ModuleRenderer::new("git_commit")
+            .config(toml::toml! {
+                [git_commit]
+                    only_detached = false
+                    hyperlinks = true
+            })
+            .path(repo_dir.path())
+            .collect();
+
+        let expected = Some(format!(
+            "{} ",
+            Color::Green.bold().paint(format!(
+                "({})",
+                hyperlink(
+                    &format!("https://github.com/starship/starship/commit/{commit}"),
+                    &commit[..7],
+                    Shell::Unknown
+                )
+            ))
+        ));
+
+        assert_eq!(expected, actual);
+        repo_dir.close()
+    }
 }
@@ -4,13 +4,23 @@ use gix::diff::blob::pipeline::WorktreeRoots;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use regex::Regex;
 
-use super::Context;
+use super::{Context, Variable};
 use crate::configs::git_status::GitStatusConfig;
 use crate::{
     config::ModuleConfig, configs::git_metrics::GitMetricsConfig, formatter::StringFormatter,
     formatter::string_formatter::StringFormatterError, module::Module,
 };
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("added", "The current number of added lines"),
+    Variable::new("deleted", "The current number of deleted lines"),
+    Variable::style("added_style", "Mirrors the value of option `added_style`"),
+    Variable::style(
+        "deleted_style",
+        "Mirrors the value of option `deleted_style`",
+    ),
+];
+
 /// Creates a module with the current added/deleted lines in the git repository at the
 /// current directory
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -1,11 +1,18 @@
 use gix::state::InProgress;
 use std::path::PathBuf;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::git_state::GitStateConfig;
 use crate::context::Repo;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("state", "The current state of the repo"),
+    Variable::new("progress_current", "The current operation progress"),
+    Variable::new("progress_total", "The total operation progress"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the state of the git repository at the current directory
 ///
 /// During a git operation it will show: REBASING, BISECTING, MERGING, etc.
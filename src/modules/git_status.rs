@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::git_status::GitStatusConfig;
 use crate::formatter::StringFormatter;
 use crate::segment::Segment;
@@ -13,6 +13,76 @@ use std::sync::{Arc, OnceLock};
 const ALL_STATUS_FORMAT: &str =
     "$conflicted$stashed$deleted$renamed$modified$typechanged$staged$untracked";
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "all_status",
+        "Shortcut for `$conflicted$stashed$deleted$renamed$modified$typechanged$staged$untracked`",
+    ),
+    Variable::new(
+        "stashed",
+        "Displays `stashed` when a stash exists for the local repository",
+    ),
+    Variable::new(
+        "ahead_behind",
+        "Displays `diverged`, `ahead`, `behind` or `up_to_date` based on the current status of the repo",
+    ),
+    Variable::new(
+        "ahead_count",
+        "Number of commits ahead of the tracking branch, in `diverged`",
+    )
+    .only_in(&["diverged"]),
+    Variable::new(
+        "behind_count",
+        "Number of commits behind the tracking branch, in `diverged`",
+    )
+    .only_in(&["diverged"]),
+    Variable::new(
+        "conflicted",
+        "Displays `conflicted` when this branch has merge conflicts",
+    ),
+    Variable::new(
+        "deleted",
+        "Displays `deleted` when a file's deletion has been added to the staging area",
+    ),
+    Variable::new(
+        "renamed",
+        "Displays `renamed` when a renamed file has been added to the staging area",
+    ),
+    Variable::new(
+        "modified",
+        "Displays `modified` when there are file modifications in the working directory",
+    ),
+    Variable::new(
+        "staged",
+        "Displays `staged` when a new file has been added to the staging area",
+    ),
+    Variable::new(
+        "untracked",
+        "Displays `untracked` when there are untracked files in the working directory",
+    ),
+    Variable::new(
+        "typechanged",
+        "Displays `typechanged` when a file's type has been changed in the staging area",
+    ),
+    Variable::new(
+        "count",
+        "The number of files, in the format strings of each status",
+    )
+    .only_in(&[
+        "stashed",
+        "ahead",
+        "behind",
+        "conflicted",
+        "deleted",
+        "renamed",
+        "modified",
+        "staged",
+        "untracked",
+        "typechanged",
+    ]),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the Git branch in the current directory
 ///
 /// Will display the branch name if the current directory is a git repo
@@ -35,6 +105,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     // Return None if not in git repository
     let repo = context.get_repo().ok()?;
 
+    if super::filesystem::disables_git_status(context) {
+        log::debug!("git_status is disabled on the filesystem of the current directory");
+        return None;
+    }
+
     if repo.kind.is_bare() {
         log::debug!("This is a bare repository, git_status is not applicable");
         return None;
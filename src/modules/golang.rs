@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::go::GoConfig;
 use crate::formatter::StringFormatter;
@@ -10,6 +10,16 @@ use semver::VersionReq;
 use std::ops::Deref;
 use std::sync::LazyLock;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `go`"),
+    Variable::new(
+        "mod_version",
+        "`go` version requirement as set in the go directive of `go.mod`. Will only show if the version requirement does not match the `go` version",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Go version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("golang");
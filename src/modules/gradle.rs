@@ -5,10 +5,16 @@ use crate::{
     configs::gradle::GradleConfig,
     context::Context,
     formatter::{StringFormatter, VersionFormatter},
-    module::Module,
+    module::{Module, Variable},
     utils,
 };
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("version", "The version of `gradle`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("gradle");
     let config = GradleConfig::try_load(module.config);
@@ -1,8 +1,13 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::guix_shell::GuixShellConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module showing if inside a guix-shell
 ///
 /// The module will use the `$GUIX_ENVIRONMENT` environment variable to determine if it's
@@ -1,8 +1,19 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::haskell::HaskellConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new(
+        "version",
+        "`ghc_version` or `snapshot` depending on the current project",
+    ),
+    Variable::new("ghc_version", "The version of `ghc`"),
+    Variable::new("snapshot", "The currently selected Stack snapshot"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Haskell version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("haskell");
@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::haxe::HaxeConfig;
 use crate::formatter::StringFormatter;
@@ -8,6 +8,12 @@ use serde_json as json;
 use regex::Regex;
 const HAXERC_VERSION_PATTERN: &str = "(?:[0-9a-zA-Z][-+0-9.a-zA-Z]+)";
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `haxe`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Haxe version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("haxe");
@@ -2,12 +2,19 @@ use std::io::Error;
 use std::path::Path;
 
 use super::utils::truncate::truncate_text;
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::hg_branch::HgBranchConfig;
 use crate::formatter::StringFormatter;
 use crate::utils::read_file;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("branch", "The active mercurial branch"),
+    Variable::new("topic", "The active mercurial topic"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the Hg bookmark or branch in the current directory
 ///
 /// Will display the bookmark or branch name if the current directory is an hg repo
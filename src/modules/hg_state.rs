@@ -2,10 +2,15 @@ use std::fs::File;
 use std::io::{self, Read};
 use std::path::Path;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::hg_state::HgStateConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("state", "The current state of the repo"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the state of hg repository at the current directory
 ///
 /// During a mercurial operation it will show: MERGING, REBASING, UPDATING etc.
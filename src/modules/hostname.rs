@@ -1,4 +1,4 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 
 use crate::config::ModuleConfig;
 use crate::configs::hostname::HostnameConfig;
@@ -6,6 +6,15 @@ use crate::formatter::StringFormatter;
 
 use whoami::fallible::hostname;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("hostname", "The hostname of the computer"),
+    Variable::new(
+        "ssh_symbol",
+        "The symbol to represent when connected to SSH session",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the system hostname
 ///
 /// Will display the hostname if all of the following criteria are met:
@@ -0,0 +1,459 @@
+use std::path::{Component, Path};
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use super::{Context, Module, ModuleConfig, Variable};
+
+use crate::configs::k8s_manifest::K8sManifestConfig;
+use crate::formatter::StringFormatter;
+use crate::modules::kubernetes;
+use crate::utils;
+
+const KUSTOMIZATION_FILES: &[&str] = &["kustomization.yaml", "kustomization.yml", "Kustomization"];
+
+// Argo CD manifests are looked up among all YAML files, so skip anything unreasonably large
+const MAX_MANIFEST_SIZE: u64 = 64 * 1024;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Manifest {
+    kind: &'static str,
+    name: Option<String>,
+    app_version: Option<String>,
+    overlay: Option<String>,
+    namespace: Option<String>,
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "kind",
+        "The kind of manifest: `helm`, `kustomize` or `argocd`",
+    ),
+    Variable::new("name", "The chart, application or Kustomize project name"),
+    Variable::new(
+        "app_version",
+        "The chart `appVersion` or the Argo CD target revision",
+    ),
+    Variable::new("overlay", "The Kustomize overlay name"),
+    Variable::new("namespace", "The namespace declared in the manifest"),
+    Variable::new(
+        "mismatch",
+        "Mirrors `mismatch_symbol` when the namespace differs from the active one",
+    ),
+    Variable::new(
+        "symbol",
+        "Mirrors the value of `symbols` for the current kind of manifest",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+    Variable::style(
+        "namespace_style",
+        "Mirrors `namespace_style` or `mismatch_style`",
+    ),
+];
+
+/// Creates a module with information about the Kubernetes manifests in the current directory
+///
+/// Will display a Helm chart (`Chart.yaml`), a Kustomize overlay (`kustomization.yaml`) or an
+/// Argo CD `Application` manifest, in that order, along with the namespace it targets.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("k8s_manifest");
+    let config: K8sManifestConfig = K8sManifestConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let manifest = get_helm_chart(context)
+        .or_else(|| get_kustomization(context, config.overlay_dir))
+        .or_else(|| get_argocd_application(context, config.overlay_dir))?;
+
+    let is_mismatch = manifest.namespace.as_deref().is_some_and(|namespace| {
+        kubernetes::get_current_namespace(context).is_some_and(|active| active != namespace)
+    });
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => config.get_symbol(manifest.kind),
+                "mismatch" => is_mismatch.then_some(config.mismatch_symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                "namespace_style" if is_mismatch => Some(Ok(config.mismatch_style)),
+                "namespace_style" => Some(Ok(config.namespace_style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "kind" => Some(Ok(manifest.kind)),
+                "name" => manifest.name.as_deref().map(Ok),
+                "app_version" => manifest.app_version.as_deref().map(Ok),
+                "overlay" => manifest.overlay.as_deref().map(Ok),
+                "namespace" => manifest.namespace.as_deref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `k8s_manifest`:\n{error}");
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_helm_chart(context: &Context) -> Option<Manifest> {
+    if !context.dir_contents().ok()?.has_file_name("Chart.yaml") {
+        return None;
+    }
+
+    let chart = load_yaml(&context.current_dir.join("Chart.yaml"))?;
+    let chart = chart.first()?;
+    Some(Manifest {
+        kind: "helm",
+        name: yaml_str(&chart["name"]),
+        app_version: yaml_str(&chart["appVersion"]),
+        ..Default::default()
+    })
+}
+
+fn get_kustomization(context: &Context, overlay_dir: &str) -> Option<Manifest> {
+    let dir_contents = context.dir_contents().ok()?;
+    let file = KUSTOMIZATION_FILES
+        .iter()
+        .find(|file| dir_contents.has_file_name(file))?;
+
+    let kustomization = load_yaml(&context.current_dir.join(file))?;
+    let kustomization = kustomization.first()?;
+
+    let (name, overlay) = match split_overlay_path(&context.current_dir, overlay_dir) {
+        Some((name, overlay)) => (name, Some(overlay)),
+        None => (dir_name(&context.current_dir), None),
+    };
+
+    Some(Manifest {
+        kind: "kustomize",
+        name,
+        overlay,
+        namespace: yaml_str(&kustomization["namespace"]),
+        ..Default::default()
+    })
+}
+
+fn get_argocd_application(context: &Context, overlay_dir: &str) -> Option<Manifest> {
+    let mut files: Vec<_> = context
+        .dir_contents()
+        .ok()?
+        .files()
+        .filter(|file| {
+            file.extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    files.sort();
+
+    files.into_iter().find_map(|file| {
+        let path = context.current_dir.join(file);
+        if path.metadata().ok()?.len() > MAX_MANIFEST_SIZE {
+            return None;
+        }
+        let contents = utils::read_file(&path).ok()?;
+        // Avoid parsing YAML files that can't be Argo CD manifests
+        if !contents.contains("argoproj.io") {
+            return None;
+        }
+        YamlLoader::load_from_str(&contents)
+            .ok()?
+            .iter()
+            .find_map(|doc| parse_argocd_application(doc, overlay_dir))
+    })
+}
+
+fn parse_argocd_application(doc: &Yaml, overlay_dir: &str) -> Option<Manifest> {
+    let is_application = doc["apiVersion"]
+        .as_str()
+        .is_some_and(|api| api.starts_with("argoproj.io/"))
+        && doc["kind"].as_str() == Some("Application");
+    if !is_application {
+        return None;
+    }
+
+    let spec = &doc["spec"];
+    // Multi-source applications list their sources in `sources`
+    let source = match &spec["source"] {
+        Yaml::BadValue => &spec["sources"][0],
+        source => source,
+    };
+    let overlay = source["path"]
+        .as_str()
+        .and_then(|path| split_overlay_path(Path::new(path), overlay_dir))
+        .map(|(_, overlay)| overlay);
+
+    Some(Manifest {
+        kind: "argocd",
+        name: yaml_str(&doc["metadata"]["name"]),
+        app_version: yaml_str(&source["targetRevision"]),
+        overlay,
+        namespace: yaml_str(&spec["destination"]["namespace"]),
+    })
+}
+
+/// Splits a path like `app/overlays/prod/...` into the application (`app`) and overlay (`prod`)
+fn split_overlay_path(path: &Path, overlay_dir: &str) -> Option<(Option<String>, String)> {
+    let components: Vec<_> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect();
+    let index = components.iter().rposition(|name| name == overlay_dir)?;
+    let overlay = components.get(index + 1)?.to_string();
+    let name = index
+        .checked_sub(1)
+        .map(|app_index| components[app_index].to_string());
+    Some((name, overlay))
+}
+
+fn dir_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+fn load_yaml(path: &Path) -> Option<Vec<Yaml>> {
+    let contents = utils::read_file(path).ok()?;
+    YamlLoader::load_from_str(&contents).ok()
+}
+
+fn yaml_str(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Real(r) => Some(r.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_overlay_path;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+
+    const KUBECONFIG: &str = "
+apiVersion: v1
+clusters: []
+contexts:
+  - context:
+      cluster: test_cluster
+      user: test_user
+      namespace: shop-dev
+    name: test_context
+current-context: test_context
+kind: Config
+preferences: {}
+users: []
+";
+
+    fn render(dir: &Path, kubeconfig: &Path) -> Option<String> {
+        ModuleRenderer::new("k8s_manifest")
+            .path(dir)
+            .env("KUBECONFIG", kubeconfig.to_string_lossy())
+            .config(toml::toml! {
+                [k8s_manifest]
+                disabled = false
+            })
+            .collect()
+    }
+
+    fn setup() -> io::Result<(tempfile::TempDir, std::path::PathBuf)> {
+        let dir = tempfile::tempdir()?;
+        let kubeconfig = dir.path().join("kubeconfig");
+        fs::write(&kubeconfig, KUBECONFIG)?;
+        Ok((dir, kubeconfig))
+    }
+
+    #[test]
+    fn folder_without_manifests() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let project = dir.path().join("project");
+        fs::create_dir(&project)?;
+        fs::write(project.join("deployment.yaml"), "kind: Deployment\n")?;
+
+        assert_eq!(None, render(&project, &kubeconfig));
+        dir.close()
+    }
+
+    #[test]
+    fn disabled_by_default() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let project = dir.path().join("chart");
+        fs::create_dir(&project)?;
+        fs::write(project.join("Chart.yaml"), "name: shop\n")?;
+
+        let actual = ModuleRenderer::new("k8s_manifest")
+            .path(&project)
+            .env("KUBECONFIG", kubeconfig.to_string_lossy())
+            .collect();
+
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn helm_chart() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let project = dir.path().join("chart");
+        fs::create_dir(&project)?;
+        fs::write(
+            project.join("Chart.yaml"),
+            "apiVersion: v2\nname: shop\nversion: 0.3.1\nappVersion: \"1.16.0\"\n",
+        )?;
+
+        let actual = render(&project, &kubeconfig);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("⎈ shop v1.16.0")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn kustomize_overlay_with_matching_namespace() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let overlay = dir.path().join("shop/overlays/dev");
+        fs::create_dir_all(&overlay)?;
+        fs::write(
+            overlay.join("kustomization.yaml"),
+            "namespace: shop-dev\nresources:\n  - ../../base\n",
+        )?;
+
+        let actual = render(&overlay, &kubeconfig);
+        let expected = Some(format!(
+            "via {} {} ",
+            Color::Blue.bold().paint("☸ shop (dev)"),
+            Color::Cyan.paint("shop-dev")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn kustomize_overlay_with_mismatched_namespace() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let overlay = dir.path().join("shop/overlays/prod");
+        fs::create_dir_all(&overlay)?;
+        fs::write(overlay.join("kustomization.yaml"), "namespace: shop-prod\n")?;
+
+        let actual = render(&overlay, &kubeconfig);
+        let expected = Some(format!(
+            "via {} {} ",
+            Color::Blue.bold().paint("☸ shop (prod)"),
+            Color::Red.bold().paint("⚠ shop-prod")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn kustomize_base_without_namespace() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let base = dir.path().join("base");
+        fs::create_dir_all(&base)?;
+        fs::write(base.join("kustomization.yml"), "resources: []\n")?;
+
+        let actual = render(&base, &kubeconfig);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("☸ base")));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn argocd_application() -> io::Result<()> {
+        let (dir, kubeconfig) = setup()?;
+        let project = dir.path().join("apps");
+        fs::create_dir_all(&project)?;
+        fs::write(project.join("deployment.yaml"), "kind: Deployment\n")?;
+        fs::write(
+            project.join("shop.yaml"),
+            "---
+apiVersion: v1
+kind: Namespace
+metadata:
+  name: shop-staging
+---
+apiVersion: argoproj.io/v1alpha1
+kind: Application
+metadata:
+  name: shop-staging
+  namespace: argocd
+spec:
+  source:
+    repoURL: https://github.com/example/shop.git
+    targetRevision: 2.4.0
+    path: deploy/overlays/staging
+  destination:
+    server: https://kubernetes.default.svc
+    namespace: shop-staging
+",
+        )?;
+
+        let actual = render(&project, &kubeconfig);
+        let expected = Some(format!(
+            "via {} {} ",
+            Color::Blue.bold().paint("🐙 shop-staging v2.4.0 (staging)"),
+            Color::Red.bold().paint("⚠ shop-staging")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn namespace_without_kubeconfig() -> io::Result<()> {
+        let (dir, _) = setup()?;
+        let overlay = dir.path().join("shop/overlays/prod");
+        fs::create_dir_all(&overlay)?;
+        fs::write(overlay.join("kustomization.yaml"), "namespace: shop-prod\n")?;
+
+        let actual = render(&overlay, &dir.path().join("missing"));
+        let expected = Some(format!(
+            "via {} {} ",
+            Color::Blue.bold().paint("☸ shop (prod)"),
+            Color::Cyan.paint("shop-prod")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn overlay_path_splitting() {
+        assert_eq!(
+            split_overlay_path(Path::new("deploy/overlays/prod"), "overlays"),
+            Some((Some("deploy".to_string()), "prod".to_string()))
+        );
+        assert_eq!(
+            split_overlay_path(Path::new("overlays/dev/patches"), "overlays"),
+            Some((None, "dev".to_string()))
+        );
+        assert_eq!(
+            split_overlay_path(Path::new("shop/overlays"), "overlays"),
+            None
+        );
+        assert_eq!(split_overlay_path(Path::new("shop/base"), "overlays"), None);
+    }
+}
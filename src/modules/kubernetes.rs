@@ -4,7 +4,7 @@ use yaml_rust2::{Yaml, YamlLoader};
 use std::borrow::Cow;
 use std::env;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::kubernetes::KubernetesConfig;
 use crate::formatter::StringFormatter;
@@ -132,6 +132,15 @@ impl DataValue for Yaml {
     }
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("context", "The current kubernetes context name"),
+    Variable::new("namespace", "If set, the current kubernetes namespace"),
+    Variable::new("user", "If set, the current kubernetes user"),
+    Variable::new("cluster", "If set, the current kubernetes cluster"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("kubernetes");
     let config: KubernetesConfig = KubernetesConfig::try_load(module.config);
@@ -169,30 +178,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let default_config_file = context.get_home()?.join(".kube").join("config");
-
-    let kube_cfg = context
-        .get_env("KUBECONFIG")
-        .unwrap_or(default_config_file.to_str()?.to_string());
-
-    let raw_kubeconfigs = env::split_paths(&kube_cfg).map(|file| utils::read_file(file).ok());
-    let kubeconfigs = parse_kubeconfigs(raw_kubeconfigs);
+    let (kube_cfg, kubeconfigs) = load_kubeconfigs(context)?;
 
-    let current_kube_ctx_name = kubeconfigs.iter().find_map(|v| match v {
-        Document::Json(json) => get_current_kube_context_name(json),
-        Document::Yaml(yaml) => get_current_kube_context_name(yaml),
-    })?;
+    let current_kube_ctx_name = get_current_kube_context_name_from(&kubeconfigs)?;
 
-    // Even if we have multiple config files, the first key wins
-    // https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
-    // > Never change the value or map key. ... Example: If two files specify a red-user,
-    // > use only values from the first file's red-user. Even if the second file has
-    // > non-conflicting entries under red-user, discard them.
-    // for that reason, we can pick the first context with that name
-    let ctx_components: KubeCtxComponents = kubeconfigs.iter().find_map(|kubeconfig|  match kubeconfig {
-        Document::Json(json) => get_kube_ctx_components(json, current_kube_ctx_name),
-        Document::Yaml(yaml) => get_kube_ctx_components(yaml, current_kube_ctx_name),
-    }).unwrap_or_else(|| {
+    let ctx_components: KubeCtxComponents = find_kube_ctx_components(&kubeconfigs, current_kube_ctx_name).unwrap_or_else(|| {
             // TODO: figure out if returning is more sensible. But currently we have tests depending on this
             log::warn!(
                 "Invalid KUBECONFIG: identified current-context `{}`, but couldn't find the context in any config file(s): `{}`.\n",
@@ -280,6 +270,51 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Returns the value of `KUBECONFIG` (or the default config path) and the parsed config files
+fn load_kubeconfigs(context: &Context) -> Option<(String, Vec<Document>)> {
+    let default_config_file = context.get_home()?.join(".kube").join("config");
+
+    let kube_cfg = context
+        .get_env("KUBECONFIG")
+        .unwrap_or(default_config_file.to_str()?.to_string());
+
+    let raw_kubeconfigs = env::split_paths(&kube_cfg).map(|file| utils::read_file(file).ok());
+    let kubeconfigs = parse_kubeconfigs(raw_kubeconfigs);
+    Some((kube_cfg, kubeconfigs))
+}
+
+fn get_current_kube_context_name_from(kubeconfigs: &[Document]) -> Option<&str> {
+    kubeconfigs.iter().find_map(|v| match v {
+        Document::Json(json) => get_current_kube_context_name(json),
+        Document::Yaml(yaml) => get_current_kube_context_name(yaml),
+    })
+}
+
+fn find_kube_ctx_components(
+    kubeconfigs: &[Document],
+    current_kube_ctx_name: &str,
+) -> Option<KubeCtxComponents> {
+    // Even if we have multiple config files, the first key wins
+    // https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    // > Never change the value or map key. ... Example: If two files specify a red-user,
+    // > use only values from the first file's red-user. Even if the second file has
+    // > non-conflicting entries under red-user, discard them.
+    // for that reason, we can pick the first context with that name
+    kubeconfigs.iter().find_map(|kubeconfig| match kubeconfig {
+        Document::Json(json) => get_kube_ctx_components(json, current_kube_ctx_name),
+        Document::Yaml(yaml) => get_kube_ctx_components(yaml, current_kube_ctx_name),
+    })
+}
+
+/// Returns the namespace of the current kubernetes context, falling back to `default`
+pub(super) fn get_current_namespace(context: &Context) -> Option<String> {
+    let (_, kubeconfigs) = load_kubeconfigs(context)?;
+    let current_kube_ctx_name = get_current_kube_context_name_from(&kubeconfigs)?;
+    let namespace = find_kube_ctx_components(&kubeconfigs, current_kube_ctx_name)
+        .and_then(|components| components.namespace);
+    Some(namespace.unwrap_or_else(|| "default".to_string()))
+}
+
 fn parse_kubeconfigs<I>(raw_kubeconfigs: I) -> Vec<Document>
 where
     I: Iterator<Item = Option<String>>,
@@ -1,6 +1,8 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 use crate::segment::Segment;
 
+pub const VARIABLES: &[Variable] = &[];
+
 /// Creates a module for the line break
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("line_break");
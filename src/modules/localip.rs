@@ -1,4 +1,4 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 
 use crate::config::ModuleConfig;
 use crate::configs::localip::LocalipConfig;
@@ -16,6 +16,11 @@ fn get_local_ipv4() -> Result<String, Error> {
     Ok(addr.ip().to_string())
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("localipv4", "Contains the primary IPv4 address"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the ipv4 address of the local machine.
 ///
 /// The IP address is gathered from the local endpoint of an UDP socket
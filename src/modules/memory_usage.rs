@@ -3,13 +3,13 @@ use systemstat::{
     data::{ByteSize, saturating_sub_bytes},
 };
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::memory_usage::MemoryConfig;
 use crate::formatter::StringFormatter;
 
 // Display a `ByteSize` in a human readable format.
-fn display_bs(bs: ByteSize) -> String {
+pub(super) fn display_bs(bs: ByteSize) -> String {
     let mut display_bytes = bs.to_string_as(true);
     let mut keep = true;
     // Skip decimals and the space before the byte unit.
@@ -41,6 +41,21 @@ fn format_usage_total(total: ByteSize, free: ByteSize) -> String {
     )
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("ram", "The usage/total RAM of the current system memory"),
+    Variable::new("ram_pct", "The percentage of the current system memory"),
+    Variable::new(
+        "swap",
+        "The swap memory size of the current system swap memory file",
+    ),
+    Variable::new(
+        "swap_pct",
+        "The swap memory percentage of the current system swap memory file",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with system memory usage information
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("memory_usage");
@@ -1,9 +1,15 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use super::utils::truncate::truncate_text;
 use crate::configs::meson::MesonConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("project", "The current Meson project name"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Meson dev environment
 ///
 /// Will display the Meson environment if `$MESON_DEVENV` and `MESON_PROJECT_NAME` are set.
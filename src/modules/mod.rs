@@ -6,18 +6,20 @@ mod bun;
 mod c;
 mod cc;
 mod character;
+mod cloud_context;
 mod cmake;
 mod cmd_duration;
 mod cobol;
 mod conda;
 mod container;
+mod container_engine;
 mod cpp;
 mod crystal;
 pub mod custom;
 mod daml;
 mod dart;
 mod deno;
-mod directory;
+pub(crate) mod directory;
 mod direnv;
 mod docker_context;
 mod dotnet;
@@ -26,6 +28,7 @@ mod elm;
 mod env_var;
 mod erlang;
 mod fennel;
+mod filesystem;
 mod fill;
 mod fortran;
 mod fossil_branch;
@@ -49,6 +52,7 @@ mod hostname;
 mod java;
 mod jobs;
 mod julia;
+mod k8s_manifest;
 mod kotlin;
 mod kubernetes;
 mod line_break;
@@ -110,10 +114,15 @@ pub use self::battery::{BatteryInfoProvider, BatteryInfoProviderImpl};
 
 use crate::config::ModuleConfig;
 use crate::context::{Context, Detected, Shell};
-use crate::module::Module;
+use crate::formatter::StringFormatter;
+use crate::module::{Module, Variable};
 use std::time::Instant;
 
 pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
+    if context.is_module_auto_disabled(module) {
+        return auto_disabled_module(module, context);
+    }
+
     let start: Instant = Instant::now();
     let mut m: Option<Module> = {
         match module {
@@ -127,11 +136,13 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "bun" => bun::module(context),
             "c" => c::module(context),
             "character" => character::module(context),
+            "cloud_context" => cloud_context::module(context),
             "cmake" => cmake::module(context),
             "cmd_duration" => cmd_duration::module(context),
             "cobol" => cobol::module(context),
             "conda" => conda::module(context),
             "container" => container::module(context),
+            "container_engine" => container_engine::module(context),
             "cpp" => cpp::module(context),
             "daml" => daml::module(context),
             "dart" => dart::module(context),
@@ -145,6 +156,7 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "erlang" => erlang::module(context),
             "env_var" => env_var::module(None, context),
             "fennel" => fennel::module(context),
+            "filesystem" => filesystem::module(context),
             "fill" => fill::module(context),
             "fortran" => fortran::module(context),
             "fossil_branch" => fossil_branch::module(context),
@@ -168,6 +180,7 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "java" => java::module(context),
             "jobs" => jobs::module(context),
             "julia" => julia::module(context),
+            "k8s_manifest" => k8s_manifest::module(context),
             "kotlin" => kotlin::module(context),
             "kubernetes" => kubernetes::module(context),
             "line_break" => line_break::module(context),
@@ -238,6 +251,8 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
 
     let elapsed = start.elapsed();
     log::trace!("Took {elapsed:?} to compute module {module:?}");
+    context.record_module_runtime(module, elapsed);
+    context.record_span("module", || module.to_string(), start);
     if elapsed.as_millis() >= 1 {
         // If we take less than 1ms to compute a None, then we will not return a module at all
         // if we have a module: default duration is 0 so no need to change it
@@ -248,6 +263,148 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
     m
 }
 
+/// Renders the marker shown in place of a module that was disabled for being slow
+fn auto_disabled_module<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
+    let mut m = context.new_module(module);
+
+    let parsed =
+        StringFormatter::new(&context.root_config.auto_disabled_format).and_then(|formatter| {
+            formatter
+                .map(|variable| match variable {
+                    "name" => Some(Ok(module)),
+                    _ => None,
+                })
+                .parse(None, Some(context))
+        });
+
+    m.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in `auto_disabled_format`:\n{error}");
+            return None;
+        }
+    });
+
+    Some(m)
+}
+
+/// Returns the variables that `module` provides to its format strings, see `Variable`
+///
+/// `custom.<name>` and `env_var.<name>` modules provide the variables of `custom` and `env_var`.
+pub fn variables(module: &str) -> &'static [Variable] {
+    let module = module.split_once('.').map_or(module, |(parent, _)| parent);
+    match module {
+        // Keep these ordered like `handle`
+        "aws" => aws::VARIABLES,
+        "azure" => azure::VARIABLES,
+        #[cfg(feature = "battery")]
+        "battery" => battery::VARIABLES,
+        "buf" => buf::VARIABLES,
+        "bun" => bun::VARIABLES,
+        "c" => cc::VARIABLES,
+        "character" => character::VARIABLES,
+        "cloud_context" => cloud_context::VARIABLES,
+        "cmake" => cmake::VARIABLES,
+        "cmd_duration" => cmd_duration::VARIABLES,
+        "cobol" => cobol::VARIABLES,
+        "conda" => conda::VARIABLES,
+        "container" => container::VARIABLES,
+        "container_engine" => container_engine::VARIABLES,
+        "cpp" => cc::VARIABLES,
+        "daml" => daml::VARIABLES,
+        "dart" => dart::VARIABLES,
+        "deno" => deno::VARIABLES,
+        "directory" => directory::VARIABLES,
+        "direnv" => direnv::VARIABLES,
+        "docker_context" => docker_context::VARIABLES,
+        "dotnet" => dotnet::VARIABLES,
+        "elixir" => elixir::VARIABLES,
+        "elm" => elm::VARIABLES,
+        "erlang" => erlang::VARIABLES,
+        "env_var" => env_var::VARIABLES,
+        "fennel" => fennel::VARIABLES,
+        "filesystem" => filesystem::VARIABLES,
+        "fill" => fill::VARIABLES,
+        "fortran" => fortran::VARIABLES,
+        "fossil_branch" => fossil_branch::VARIABLES,
+        "fossil_metrics" => fossil_metrics::VARIABLES,
+        "gcloud" => gcloud::VARIABLES,
+        "git_branch" => git_branch::VARIABLES,
+        "git_commit" => git_commit::VARIABLES,
+        "git_metrics" => git_metrics::VARIABLES,
+        "git_state" => git_state::VARIABLES,
+        "git_status" => git_status::VARIABLES,
+        "gleam" => gleam::VARIABLES,
+        "golang" => golang::VARIABLES,
+        "gradle" => gradle::VARIABLES,
+        "guix_shell" => guix_shell::VARIABLES,
+        "haskell" => haskell::VARIABLES,
+        "haxe" => haxe::VARIABLES,
+        "helm" => helm::VARIABLES,
+        "hg_branch" => hg_branch::VARIABLES,
+        "hg_state" => hg_state::VARIABLES,
+        "hostname" => hostname::VARIABLES,
+        "java" => java::VARIABLES,
+        "jobs" => jobs::VARIABLES,
+        "julia" => julia::VARIABLES,
+        "k8s_manifest" => k8s_manifest::VARIABLES,
+        "kotlin" => kotlin::VARIABLES,
+        "kubernetes" => kubernetes::VARIABLES,
+        "line_break" => line_break::VARIABLES,
+        "localip" => localip::VARIABLES,
+        "lua" => lua::VARIABLES,
+        "memory_usage" => memory_usage::VARIABLES,
+        "meson" => meson::VARIABLES,
+        "mise" => mise::VARIABLES,
+        "mojo" => mojo::VARIABLES,
+        "nats" => nats::VARIABLES,
+        "netns" => netns::VARIABLES,
+        "nim" => nim::VARIABLES,
+        "nix_shell" => nix_shell::VARIABLES,
+        "nodejs" => nodejs::VARIABLES,
+        "ocaml" => ocaml::VARIABLES,
+        "odin" => odin::VARIABLES,
+        "opa" => opa::VARIABLES,
+        "openstack" => openstack::VARIABLES,
+        "os" => os::VARIABLES,
+        "package" => package::VARIABLES,
+        "perl" => perl::VARIABLES,
+        "php" => php::VARIABLES,
+        "pijul_channel" => pijul_channel::VARIABLES,
+        "pixi" => pixi::VARIABLES,
+        "pulumi" => pulumi::VARIABLES,
+        "purescript" => purescript::VARIABLES,
+        "python" => python::VARIABLES,
+        "quarto" => quarto::VARIABLES,
+        "raku" => raku::VARIABLES,
+        "rlang" => rlang::VARIABLES,
+        "red" => red::VARIABLES,
+        "ruby" => ruby::VARIABLES,
+        "rust" => rust::VARIABLES,
+        "scala" => scala::VARIABLES,
+        "shell" => shell::VARIABLES,
+        "shlvl" => shlvl::VARIABLES,
+        "singularity" => singularity::VARIABLES,
+        "solidity" => solidity::VARIABLES,
+        "spack" => spack::VARIABLES,
+        "swift" => swift::VARIABLES,
+        "status" => status::VARIABLES,
+        "sudo" => sudo::VARIABLES,
+        "terraform" => terraform::VARIABLES,
+        "time" => time::VARIABLES,
+        "typst" => typst::VARIABLES,
+        "crystal" => crystal::VARIABLES,
+        "username" => username::VARIABLES,
+        "vlang" => vlang::VARIABLES,
+        "vagrant" => vagrant::VARIABLES,
+        "vcsh" => vcsh::VARIABLES,
+        "xmake" => xmake::VARIABLES,
+        "zig" => zig::VARIABLES,
+        "custom" => custom::VARIABLES,
+        _ => &[],
+    }
+}
+
 pub fn description(module: &str) -> &'static str {
     match module {
         "aws" => "The current AWS region and profile",
@@ -259,13 +416,16 @@ pub fn description(module: &str) -> &'static str {
         "character" => {
             "A character (usually an arrow) beside where the text is entered in your terminal"
         }
+        "cloud_context" => "The active identity of the cloud providers used by the current project",
         "cmake" => "The currently installed version of CMake",
         "cmd_duration" => "How long the last command took to execute",
         "cobol" => "The currently installed version of COBOL/GNUCOBOL",
         "conda" => "The current conda environment, if $CONDA_DEFAULT_ENV is set",
         "container" => "The container indicator, if inside a container.",
+        "container_engine" => "The active container engine context and running compose containers",
         "cpp" => "your cpp compiler type",
         "crystal" => "The currently installed version of Crystal",
+        "custom" => "The output of a custom command",
         "daml" => "The Daml SDK version of your project",
         "dart" => "The currently installed version of Dart",
         "deno" => "The currently installed version of Deno",
@@ -275,8 +435,10 @@ pub fn description(module: &str) -> &'static str {
         "dotnet" => "The relevant version of the .NET Core SDK for the current directory",
         "elixir" => "The currently installed versions of Elixir and OTP",
         "elm" => "The currently installed version of Elm",
+        "env_var" => "The value of an environment variable",
         "erlang" => "Current OTP version",
         "fennel" => "The currently installed version of Fennel",
+        "filesystem" => "The type, mount source and free space of the current filesystem",
         "fill" => "Fills the remaining space on the line with a pad string",
         "fortran" => "The currently used version of Fortran",
         "fossil_branch" => "The active branch of the check-out in your current directory",
@@ -300,6 +462,9 @@ pub fn description(module: &str) -> &'static str {
         "java" => "The currently installed version of Java",
         "jobs" => "The current number of jobs running",
         "julia" => "The currently installed version of Julia",
+        "k8s_manifest" => {
+            "The Helm chart, Kustomize overlay or Argo CD application in the current directory"
+        }
         "kotlin" => "The currently installed version of Kotlin",
         "kubernetes" => "The current Kubernetes context name and, if set, the namespace",
         "line_break" => "Separates the prompt into two lines",
@@ -371,4 +536,44 @@ mod test {
             assert_ne!(description(module), "<no description>");
         }
     }
+
+    #[test]
+    fn auto_disabled_module_shows_marker() -> std::io::Result<()> {
+        use crate::test::ModuleRenderer;
+        use crate::timings::RuntimeStore;
+        use nu_ansi_term::Color;
+        use std::time::Duration;
+
+        let cache_dir = tempfile::tempdir()?;
+        let dir = tempfile::tempdir()?;
+        let current_dir = dunce::canonicalize(dir.path())?;
+
+        let mut store = RuntimeStore::default();
+        store.record(
+            &current_dir.to_string_lossy(),
+            "directory",
+            Duration::from_secs(2),
+            Duration::from_millis(500),
+            1,
+        );
+        store.save(&cache_dir.path().join("module_runtimes.json"))?;
+
+        let render = |threshold: i64| {
+            ModuleRenderer::new("directory")
+                .path(&current_dir)
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .config(toml::toml! {
+                    auto_disable_threshold = threshold
+                })
+                .collect()
+        };
+
+        let expected = Some(format!("{} ", Color::DarkGray.paint("⏱ directory")));
+        assert_eq!(render(500), expected);
+        // The store is ignored unless a threshold is set
+        assert_ne!(render(0), expected);
+
+        cache_dir.close()?;
+        dir.close()
+    }
 }
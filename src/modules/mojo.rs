@@ -1,10 +1,17 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::mojo::MojoConfig;
 use crate::formatter::StringFormatter;
 
 use std::sync::LazyLock;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `mojo`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("hash", "The commit hash of `mojo`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Mojo version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("mojo");
@@ -1,4 +1,10 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("name", "The name of the current network namespace"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
 
 #[cfg(not(target_os = "linux"))]
 pub fn module<'a>(_context: &'a Context) -> Option<Module<'a>> {
@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::nix_shell::NixShellConfig;
 use crate::formatter::StringFormatter;
@@ -40,6 +40,13 @@ impl NixShellType {
     }
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("state", "The state of the nix-shell"),
+    Variable::new("name", "The name of the nix-shell"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module showing if inside a nix-shell
 ///
 /// The module will use the `$IN_NIX_SHELL` and `$name` environment variable to
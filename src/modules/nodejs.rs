@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::nodejs::NodejsConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
@@ -10,6 +10,16 @@ use serde_json as json;
 use std::ops::Deref;
 use std::sync::LazyLock;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `node`"),
+    Variable::new(
+        "engines_version",
+        "`node` version requirement as set in the engines property of `package.json`. Will only show if the version requirement does not match the `node` version",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Node.js version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("nodejs");
@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use std::ops::Deref;
 use std::path::Path;
 use std::sync::LazyLock;
@@ -14,6 +14,17 @@ enum SwitchType {
 }
 type OpamSwitch = (SwitchType, String);
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `ocaml`"),
+    Variable::new("switch_name", "The active OPAM switch"),
+    Variable::new(
+        "switch_indicator",
+        "Mirrors the value of `indicator` for currently active OPAM switch",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current OCaml version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("ocaml");
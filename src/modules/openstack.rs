@@ -1,6 +1,6 @@
 use yaml_rust2::YamlLoader;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::openstack::OspConfig;
 use crate::formatter::StringFormatter;
@@ -46,6 +46,20 @@ fn get_osp_cloud_and_project(context: &Context) -> (Option<Cloud>, Option<Projec
     }
 }
 
+/// Returns the active cloud and its region. Used by the `cloud_context` module.
+pub(super) fn get_cloud_and_region(context: &Context) -> Option<(Option<Cloud>, Option<String>)> {
+    let (osp_cloud, _) = get_osp_cloud_and_project(context);
+    osp_cloud.as_ref()?;
+    Some((osp_cloud, context.get_env("OS_REGION_NAME")))
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("cloud", "The current OpenStack cloud"),
+    Variable::new("project", "The current OpenStack project"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("openstack");
     let config: OspConfig = OspConfig::try_load(module.config);
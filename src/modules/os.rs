@@ -1,8 +1,30 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::os::OSConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "symbol",
+        "The current operating system symbol from advanced option `symbols`",
+    ),
+    Variable::new("name", "The current operating system name"),
+    Variable::new("type", "The current operating system type"),
+    Variable::new(
+        "codename",
+        "The current operating system codename, if applicable",
+    ),
+    Variable::new(
+        "edition",
+        "The current operating system edition, if applicable",
+    ),
+    Variable::new(
+        "version",
+        "The current operating system version, if applicable",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current operating system
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("os");
@@ -1,9 +1,15 @@
 use super::utils::truncate::truncate_text;
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::pijul_channel::PijulConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("channel", "The active channel"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the Pijul channel in the current directory
 ///
 /// Will display the channel lame if the current directory is a pijul repo
@@ -1,9 +1,16 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::pixi::PixiConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils::get_command_string_output;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `pixi`"),
+    Variable::new("environment", "The current pixi environment"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Pixi environment
 ///
 /// Will display the Pixi environment iff `$PIXI_ENVIRONMENT_NAME` is set.
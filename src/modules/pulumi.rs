@@ -9,7 +9,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use yaml_rust2::{Yaml, YamlLoader};
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::pulumi::PulumiConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
 
@@ -26,6 +26,14 @@ struct Account {
     username: Option<String>,
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `pulumi`"),
+    Variable::new("stack", "The current Pulumi stack"),
+    Variable::new("username", "The current Pulumi username"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Pulumi version and stack name.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("pulumi");
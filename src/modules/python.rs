@@ -1,12 +1,20 @@
 use ini::Ini;
 use std::path::Path;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 use crate::configs::python::PythonConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
 use crate::utils::get_command_string_output;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `python`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("pyenv_prefix", "Mirrors the value of option `pyenv_prefix`"),
+    Variable::new("virtualenv", "The current `virtualenv` name"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Python version and, if active, virtual environment.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("python");
@@ -1,4 +1,4 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::raku::RakuConfig;
 use crate::formatter::StringFormatter;
@@ -6,6 +6,13 @@ use crate::formatter::VersionFormatter;
 use std::ops::Deref;
 use std::sync::LazyLock;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `raku`"),
+    Variable::new("vm_version", "The version of VM `raku` is built on"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current raku version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("raku");
@@ -1,10 +1,17 @@
 use regex::Regex;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::ruby::RubyConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `ruby`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::new("gemset", "Optional, gets the current RVM gemset name"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Ruby version
 ///
 /// Will display the Ruby version if any of the following criteria are met:
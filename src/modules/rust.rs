@@ -5,7 +5,7 @@ use std::process::Output;
 use serde::Deserialize;
 use std::collections::HashMap;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::rust::RustConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
@@ -155,6 +155,14 @@ impl RustToolingEnvironmentInfo {
     }
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `rustc`"),
+    Variable::new("numver", "The numeric component of the `rustc` version"),
+    Variable::new("toolchain", "The toolchain version"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module with the current Rust version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("rust");
@@ -1,8 +1,80 @@
-use super::{Context, Module, ModuleConfig, Shell};
+use super::{Context, Module, ModuleConfig, Shell, Variable};
 
 use crate::configs::shell::ShellConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new(
+        "indicator",
+        "Mirrors the value of `indicator` for currently used shell",
+    ),
+    Variable::new(
+        "bash_indicator",
+        "Mirrors the value of option `bash_indicator`",
+    ),
+    Variable::new(
+        "fish_indicator",
+        "Mirrors the value of option `fish_indicator`",
+    ),
+    Variable::new(
+        "zsh_indicator",
+        "Mirrors the value of option `zsh_indicator`",
+    ),
+    Variable::new(
+        "powershell_indicator",
+        "Mirrors the value of option `powershell_indicator`",
+    ),
+    Variable::new(
+        "pwsh_indicator",
+        "Mirrors the value of option `pwsh_indicator`",
+    ),
+    Variable::new(
+        "ion_indicator",
+        "Mirrors the value of option `ion_indicator`",
+    ),
+    Variable::new(
+        "elvish_indicator",
+        "Mirrors the value of option `elvish_indicator`",
+    ),
+    Variable::new(
+        "tcsh_indicator",
+        "Mirrors the value of option `tcsh_indicator`",
+    ),
+    Variable::new(
+        "xonsh_indicator",
+        "Mirrors the value of option `xonsh_indicator`",
+    ),
+    Variable::new(
+        "cmd_indicator",
+        "Mirrors the value of option `cmd_indicator`",
+    ),
+    Variable::new(
+        "osh_indicator",
+        "Mirrors the value of option `osh_indicator`",
+    ),
+    Variable::new(
+        "ysh_indicator",
+        "Mirrors the value of option `ysh_indicator`",
+    ),
+    Variable::new(
+        "ksh_indicator",
+        "Mirrors the value of option `ksh_indicator`",
+    ),
+    Variable::new(
+        "mksh_indicator",
+        "Mirrors the value of option `mksh_indicator`",
+    ),
+    Variable::new(
+        "murex_indicator",
+        "Mirrors the value of option `murex_indicator`",
+    ),
+    Variable::new(
+        "unknown_indicator",
+        "Mirrors the value of option `unknown_indicator`",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("shell");
     let config: ShellConfig = ShellConfig::try_load(module.config);
@@ -28,6 +100,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     Shell::Nu => Some(config.nu_indicator),
                     Shell::Xonsh => Some(config.xonsh_indicator),
                     Shell::Cmd => Some(config.cmd_indicator),
+                    Shell::Osh => Some(config.osh_indicator),
+                    Shell::Ysh => Some(config.ysh_indicator),
+                    Shell::Ksh => Some(config.ksh_indicator),
+                    Shell::Mksh => Some(config.mksh_indicator),
+                    Shell::Murex => Some(config.murex_indicator),
                     Shell::Unknown => Some(config.unknown_indicator),
                 },
                 _ => None,
@@ -47,6 +124,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "tcsh_indicator" => Some(Ok(config.tcsh_indicator)),
                 "xonsh_indicator" => Some(Ok(config.xonsh_indicator)),
                 "cmd_indicator" => Some(Ok(config.cmd_indicator)),
+                "osh_indicator" => Some(Ok(config.osh_indicator)),
+                "ysh_indicator" => Some(Ok(config.ysh_indicator)),
+                "ksh_indicator" => Some(Ok(config.ksh_indicator)),
+                "mksh_indicator" => Some(Ok(config.mksh_indicator)),
+                "murex_indicator" => Some(Ok(config.murex_indicator)),
                 "unknown_indicator" => Some(Ok(config.unknown_indicator)),
                 _ => None,
             })
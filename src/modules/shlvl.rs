@@ -1,4 +1,4 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 
 use crate::config::ModuleConfig;
 use crate::configs::shlvl::ShLvlConfig;
@@ -8,6 +8,12 @@ use std::convert::TryInto;
 
 const SHLVL_ENV_VAR: &str = "SHLVL";
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("shlvl", "The current value of `SHLVL`"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let props = &context.properties;
     let shlvl = props
@@ -1,6 +1,6 @@
 use std::string::ToString;
 
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::status::StatusConfig;
 use crate::formatter::{StringFormatter, string_formatter::StringFormatterError};
@@ -15,6 +15,34 @@ enum PipeStatusStatus<'a> {
     Pipe(&'a Vec<std::string::String>),
 }
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("status", "The exit code of the last command"),
+    Variable::new("hex_status", "The exit code of the last command in hex"),
+    Variable::new("int", "The exit code of the last command"),
+    Variable::new("common_meaning", "Meaning of the code if not a signal"),
+    Variable::new(
+        "signal_number",
+        "Signal number corresponding to the exit code, only if signalled",
+    ),
+    Variable::new(
+        "signal_name",
+        "Name of the signal corresponding to the exit code, only if signalled",
+    ),
+    Variable::new(
+        "maybe_int",
+        "Contains the exit code number when no meaning has been found",
+    ),
+    Variable::new(
+        "pipestatus",
+        "Rendering of in pipeline programs' exit codes, this is only available in pipestatus_format",
+    ),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style(
+        "style",
+        "Mirrors the value of option `success_style` on program success and `failure_style` otherwise",
+    ),
+];
+
 /// Creates a module with the status of the last command
 ///
 /// Will display the status
@@ -1,33 +1,125 @@
-use super::{Context, Module, ModuleConfig};
+use super::{Context, Module, ModuleConfig, Variable};
 
 use crate::configs::terraform::TerraformConfig;
 use crate::formatter::StringFormatter;
 use crate::utils;
 
 use crate::formatter::VersionFormatter;
+use std::collections::HashSet;
 use std::io;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const TERRAFORM_REGISTRY: &str = "registry.terraform.io";
+const OPENTOFU_REGISTRY: &str = "registry.opentofu.org";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tool {
+    Terraform,
+    OpenTofu,
+}
+
+impl Tool {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Terraform => "terraform",
+            Self::OpenTofu => "tofu",
+        }
+    }
+
+    fn default_registry(self) -> &'static str {
+        match self {
+            Self::Terraform => TERRAFORM_REGISTRY,
+            Self::OpenTofu => OPENTOFU_REGISTRY,
+        }
+    }
+}
+
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("version", "The version of `terraform`"),
+    Variable::new("workspace", "The current Terraform workspace"),
+    Variable::new("tool", "The detected tool, either `terraform` or `tofu`"),
+    Variable::new(
+        "backend",
+        "The backend type from `.terraform/terraform.tfstate`, or `local`",
+    ),
+    Variable::new(
+        "lock",
+        "Mirrors `lock_outdated_symbol` if the lock file is out of sync",
+    ),
+    Variable::new(
+        "symbol",
+        "Mirrors the value of option `symbol` (or `tofu_symbol`)",
+    ),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
 
 /// Creates a module with the current Terraform version and workspace
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("terraform");
     let config: TerraformConfig = TerraformConfig::try_load(module.config);
 
-    let is_terraform_project = context
+    let is_tofu_project = context
         .try_begin_scan()?
-        .set_files(&config.detect_files)
-        .set_folders(&config.detect_folders)
-        .set_extensions(&config.detect_extensions)
+        .set_files(&config.tofu_detect_files)
+        .set_folders(&config.tofu_detect_folders)
+        .set_extensions(&config.tofu_detect_extensions)
         .is_match();
 
+    let is_terraform_project = is_tofu_project
+        || context
+            .try_begin_scan()?
+            .set_files(&config.detect_files)
+            .set_folders(&config.detect_folders)
+            .set_extensions(&config.detect_extensions)
+            .is_match();
+
     if !is_terraform_project {
         return None;
     }
 
+    let lock_file = utils::read_file(context.current_dir.join(".terraform.lock.hcl")).ok();
+    let locked_providers = lock_file.as_deref().map(parse_lock_file_providers);
+
+    // OpenTofu projects are recognized by their own files or by a lock file pointing at the
+    // OpenTofu registry. Otherwise the tool is only known once its version has been queried.
+    let detected_tool = if is_tofu_project
+        || locked_providers
+            .as_ref()
+            .is_some_and(|providers| providers.iter().any(|p| p.starts_with(OPENTOFU_REGISTRY)))
+    {
+        Some(Tool::OpenTofu)
+    } else {
+        None
+    };
+
+    let version_output: OnceLock<Option<String>> = OnceLock::new();
+    let get_version_output = || {
+        version_output
+            .get_or_init(|| {
+                let commands = ordered_commands(&config.commands, detected_tool);
+                Some(context.exec_cmds_return_first(&commands)?.stdout)
+            })
+            .as_deref()
+    };
+    let get_tool = |query_version: bool| {
+        detected_tool.unwrap_or_else(|| {
+            // Only run the version command if its output is going to be used anyway
+            if query_version {
+                get_version_output().map_or(Tool::Terraform, parse_terraform_tool)
+            } else {
+                Tool::Terraform
+            }
+        })
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
-            .map_meta(|variable, _| match variable {
-                "symbol" => Some(config.symbol),
+            .map_meta(|variable, variables| match variable {
+                "symbol" => match get_tool(variables.contains("version")) {
+                    Tool::Terraform => Some(config.symbol),
+                    Tool::OpenTofu => Some(config.tofu_symbol),
+                },
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -36,12 +128,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "version" => {
-                    let terraform_version = parse_terraform_version(
-                        context
-                            .exec_cmds_return_first(&config.commands)?
-                            .stdout
-                            .as_str(),
-                    )?;
+                    let terraform_version = parse_terraform_version(get_version_output()?)?;
                     VersionFormatter::format_module_version(
                         module.get_name(),
                         &terraform_version,
@@ -49,7 +136,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     )
                 }
                 .map(Ok),
+                "tool" => Some(Ok(get_tool(true).name().to_string())),
                 "workspace" => get_terraform_workspace(context).map(Ok),
+                "backend" => get_terraform_backend(context).map(Ok),
+                "lock" => {
+                    let required =
+                        get_required_providers(context, detected_tool.unwrap_or(Tool::Terraform));
+                    is_lock_file_out_of_sync(&required, locked_providers.as_ref())
+                        .then(|| Ok(config.lock_outdated_symbol.to_string()))
+                }
                 _ => None,
             })
             .parse(None, Some(context))
@@ -66,7 +161,34 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Returns the version commands to try, preferring `tofu` inside of `OpenTofu` projects
+fn ordered_commands<'a>(commands: &[Vec<&'a str>], tool: Option<Tool>) -> Vec<Vec<&'a str>> {
+    let mut commands = commands
+        .iter()
+        .filter(|cmd| !cmd.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+    if tool == Some(Tool::OpenTofu) {
+        commands.sort_by_key(|cmd| cmd[0] != Tool::OpenTofu.name());
+    }
+    commands
+}
+
+/// Returns the directory terraform stores its local data in (`.terraform` by default)
+fn get_terraform_datadir(context: &Context) -> PathBuf {
+    // Data directory containing current workspace can be overwritten by an env var
+    match context.get_env("TF_DATA_DIR") {
+        Some(s) => PathBuf::from(s),
+        None => context.current_dir.join(".terraform"),
+    }
+}
+
 // Determines the currently selected workspace (see https://github.com/hashicorp/terraform/blob/master/command/meta.go for the original implementation)
+//
+// The workspace is resolved in the following order:
+// 1. The `TF_WORKSPACE` env var, which always takes precedence
+// 2. The `environment` file in the data directory (`TF_DATA_DIR` or `.terraform`)
+// 3. `default`, if the data directory doesn't record a workspace
 fn get_terraform_workspace(context: &Context) -> Option<String> {
     // Workspace can be explicitly overwritten by an env var
     let workspace_override = context.get_env("TF_WORKSPACE");
@@ -74,18 +196,202 @@ fn get_terraform_workspace(context: &Context) -> Option<String> {
         return workspace_override;
     }
 
-    // Data directory containing current workspace can be overwritten by an env var
-    let datadir = match context.get_env("TF_DATA_DIR") {
-        Some(s) => PathBuf::from(s),
-        None => context.current_dir.join(".terraform"),
-    };
-    match utils::read_file(datadir.join("environment")) {
+    match utils::read_file(get_terraform_datadir(context).join("environment")) {
         Err(ref e) if e.kind() == io::ErrorKind::NotFound => Some("default".to_string()),
         Ok(s) => Some(s),
         _ => None,
     }
 }
 
+// Determines the configured backend type from the backend state saved by `terraform init`.
+// Without a saved backend state, the state is stored locally.
+fn get_terraform_backend(context: &Context) -> Option<String> {
+    let backend_state =
+        match utils::read_file(get_terraform_datadir(context).join("terraform.tfstate")) {
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Some("local".to_string()),
+            Ok(s) => s,
+            _ => return None,
+        };
+
+    let backend_state: serde_json::Value = serde_json::from_str(&backend_state)
+        .map_err(|e| log::debug!("Failed to parse terraform backend state: {e}"))
+        .ok()?;
+    backend_state
+        .get("backend")
+        .and_then(|backend| backend.get("type"))
+        .and_then(serde_json::Value::as_str)
+        .map(ToString::to_string)
+}
+
+/// Returns the full provider addresses (e.g. `registry.terraform.io/hashicorp/aws`) of all
+/// providers recorded in `.terraform.lock.hcl`
+fn parse_lock_file_providers(lock_file: &str) -> HashSet<String> {
+    lock_file
+        .lines()
+        .filter_map(|line| {
+            let address = line.trim().strip_prefix("provider")?.trim_start();
+            let address = address.strip_prefix('"')?;
+            let (address, _) = address.split_once('"')?;
+            Some(address.to_lowercase())
+        })
+        .collect()
+}
+
+/// Returns the provider sources declared in `required_providers` blocks of the configuration
+/// files in the current directory
+fn get_required_providers(context: &Context, tool: Tool) -> HashSet<String> {
+    let Ok(dir_contents) = context.dir_contents() else {
+        return HashSet::new();
+    };
+
+    dir_contents
+        .files()
+        .filter(|file| {
+            file.extension()
+                .is_some_and(|ext| ext == "tf" || ext == "tofu")
+        })
+        .filter_map(|file| utils::read_file(context.current_dir.join(file)).ok())
+        .flat_map(|contents| parse_required_providers(&contents))
+        .map(|source| normalize_provider_source(&source, tool))
+        .collect()
+}
+
+/// A minimal parser for the `required_providers` block, which supports both the
+/// `name = { source = "namespace/type" }` and the legacy `name = "version"` syntax
+fn parse_required_providers(contents: &str) -> Vec<String> {
+    let mut providers = Vec::new();
+    let mut depth = None;
+    let mut current_provider: Option<(String, Option<String>)> = None;
+
+    for line in strip_hcl_comments(contents).lines() {
+        let line = line.trim();
+        let Some(block_depth) = depth else {
+            if line.starts_with("required_providers") && line.ends_with('{') {
+                depth = Some(1);
+            }
+            continue;
+        };
+
+        if block_depth == 1 {
+            if let Some((name, value)) = line.split_once('=') {
+                let name = name.trim().to_string();
+                if value.trim() == "{" {
+                    current_provider = Some((name, None));
+                    depth = Some(2);
+                } else if let Some(source) = extract_hcl_attribute(line, "source") {
+                    // Single-line object syntax: `aws = { source = "hashicorp/aws" }`
+                    providers.push(source);
+                } else {
+                    providers.push(name);
+                }
+            } else if line.starts_with('}') {
+                depth = None;
+            }
+        } else if line.starts_with('}') {
+            if let Some((name, source)) = current_provider.take() {
+                providers.push(source.unwrap_or(name));
+            }
+            depth = Some(1);
+        } else if let Some(source) = extract_hcl_attribute(line, "source")
+            && let Some((_, provider_source)) = current_provider.as_mut()
+        {
+            *provider_source = Some(source);
+        }
+    }
+
+    providers
+}
+
+/// Removes `#`, `//` and `/* */` comments outside of string literals, keeping the line breaks
+fn strip_hcl_comments(contents: &str) -> String {
+    let mut stripped = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next_if(|c| *c != '\n')),
+                '"' | '\n' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                stripped.push(c);
+            }
+            ('#', _) | ('/', Some('/')) => while chars.next_if(|c| *c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = None;
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push(c);
+                    } else if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    previous = Some(c);
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+fn extract_hcl_attribute(line: &str, key: &str) -> Option<String> {
+    let (_, rest) = line.split_once(key)?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let (value, _) = rest.split_once('"')?;
+    Some(value.to_string())
+}
+
+/// Expands a provider source to its full address: `aws` and `hashicorp/aws` both become
+/// `<registry>/hashicorp/aws`
+fn normalize_provider_source(source: &str, tool: Tool) -> String {
+    let source = source.to_lowercase();
+    match source.split('/').count() {
+        1 => format!("{}/hashicorp/{source}", tool.default_registry()),
+        2 => format!("{}/{source}", tool.default_registry()),
+        _ => source,
+    }
+}
+
+fn is_lock_file_out_of_sync(required: &HashSet<String>, locked: Option<&HashSet<String>>) -> bool {
+    if required.is_empty() {
+        return false;
+    }
+    let Some(locked) = locked else {
+        return true;
+    };
+
+    required.iter().any(|provider| {
+        // The lock file may record providers from either registry, as both resolve
+        // unqualified provider sources to their own registry.
+        let unqualified = provider
+            .strip_prefix(TERRAFORM_REGISTRY)
+            .or_else(|| provider.strip_prefix(OPENTOFU_REGISTRY));
+        match unqualified {
+            Some(path) => ![TERRAFORM_REGISTRY, OPENTOFU_REGISTRY]
+                .iter()
+                .any(|registry| locked.contains(&format!("{registry}{path}"))),
+            None => !locked.contains(provider),
+        }
+    })
+}
+
+fn parse_terraform_tool(version: &str) -> Tool {
+    if version.trim_start().starts_with("OpenTofu ") {
+        Tool::OpenTofu
+    } else {
+        Tool::Terraform
+    }
+}
+
 fn parse_terraform_version(version: &str) -> Option<String> {
     // `terraform version` or `tofu version` output looks like this
     //   Terraform v0.12.14/OpenTofu v1.7.2
@@ -106,9 +412,11 @@ fn parse_terraform_version(version: &str) -> Option<String> {
 mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
     use std::fs::{self, File};
     use std::io::{self, Write};
+    use std::path::Path;
 
     #[test]
     fn test_parse_terraform_version_release() {
@@ -319,4 +627,354 @@ is 0.12.14. You can update by downloading from www.terraform.io/downloads.html
         assert_eq!(expected, actual);
         dir.close()
     }
+    #[test]
+    fn test_parse_terraform_tool() {
+        assert_eq!(parse_terraform_tool("Terraform v0.12.14"), Tool::Terraform);
+        assert_eq!(
+            parse_terraform_tool("OpenTofu v1.7.2\non linux_amd64"),
+            Tool::OpenTofu
+        );
+    }
+
+    #[test]
+    fn test_parse_lock_file_providers() {
+        let input = r#"
+# This file is maintained automatically by "terraform init".
+# Manual edits may be lost in future updates.
+
+provider "registry.terraform.io/hashicorp/aws" {
+  version     = "5.31.0"
+  constraints = "~> 5.0"
+  hashes = [
+    "h1:ltxyuBWIy9cq0kIKDJH1jeWJy/y7XJLjS4QrsQK4plA=",
+  ]
+}
+
+provider "registry.opentofu.org/Hashicorp/Random" {
+  version = "3.6.0"
+}
+"#;
+        let expected = HashSet::from([
+            "registry.terraform.io/hashicorp/aws".to_string(),
+            "registry.opentofu.org/hashicorp/random".to_string(),
+        ]);
+        assert_eq!(parse_lock_file_providers(input), expected);
+    }
+
+    #[test]
+    fn test_parse_required_providers() {
+        let input = r#"
+terraform {
+  required_version = ">= 1.5"
+
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws" # pinned below
+      version = "~> 5.0"
+    }
+    google = { source = "hashicorp/google", version = "~> 5.0" }
+    random = "~> 3.0"
+    custom = {
+      source = "example.com/acme/custom"
+    }
+  }
+}
+
+resource "aws_instance" "web" {
+  source = "not-a-provider"
+}
+"#;
+        assert_eq!(
+            parse_required_providers(input),
+            vec![
+                "hashicorp/aws",
+                "hashicorp/google",
+                "random",
+                "example.com/acme/custom"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_required_providers_with_comments() {
+        let input = r##"
+terraform {
+  required_providers {
+    // aws = { source = "hashicorp/aws" }
+    # google = "~> 5.0"
+    /* azurerm = {
+      source = "hashicorp/azurerm"
+    } */
+    custom = {
+      source = "example.com/acme#team/custom" // a "#" in a string
+    }
+    random = /* inline */ "~> 3.0"
+  }
+}
+"##;
+        assert_eq!(
+            parse_required_providers(input),
+            vec!["example.com/acme#team/custom", "random"]
+        );
+    }
+
+    #[test]
+    fn test_strip_hcl_comments() {
+        assert_eq!(
+            strip_hcl_comments("a = \"x # y // z /* w */\" # comment\nb = 1 // comment\n"),
+            "a = \"x # y // z /* w */\" \nb = 1 \n"
+        );
+        assert_eq!(
+            strip_hcl_comments("a /* one\ntwo */ = \"\\\"#\"\n"),
+            "a \n = \"\\\"#\"\n"
+        );
+    }
+
+    #[test]
+    fn test_normalize_provider_source() {
+        assert_eq!(
+            normalize_provider_source("random", Tool::Terraform),
+            "registry.terraform.io/hashicorp/random"
+        );
+        assert_eq!(
+            normalize_provider_source("Hashicorp/AWS", Tool::OpenTofu),
+            "registry.opentofu.org/hashicorp/aws"
+        );
+        assert_eq!(
+            normalize_provider_source("example.com/acme/custom", Tool::Terraform),
+            "example.com/acme/custom"
+        );
+    }
+
+    #[test]
+    fn folder_with_tofu_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.tofu"))?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .config(toml::toml! {
+                [terraform]
+                format = "via [$symbol$tool $version $workspace]($style) "
+                tofu_symbol = "🦉 "
+            })
+            .cmd(
+                "tofu version",
+                Some(CommandOutput {
+                    stdout: String::from("OpenTofu v1.7.2\n"),
+                    stderr: String::default(),
+                }),
+            )
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("🦉 tofu v1.7.2 default")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_tf_file_detects_tofu_from_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.tf"))?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .config(toml::toml! {
+                [terraform]
+                format = "via [$symbol$tool $version]($style) "
+                tofu_symbol = "🦉 "
+                commands = [["tofu", "version"]]
+            })
+            .cmd(
+                "tofu version",
+                Some(CommandOutput {
+                    stdout: String::from("OpenTofu v1.8.0\n"),
+                    stderr: String::default(),
+                }),
+            )
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("🦉 tofu v1.8.0")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_opentofu_lock_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.tf"))?;
+        fs::write(
+            dir.path().join(".terraform.lock.hcl"),
+            "provider \"registry.opentofu.org/hashicorp/aws\" {\n  version = \"5.31.0\"\n}\n",
+        )?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .config(toml::toml! {
+                [terraform]
+                format = "via [$symbol$workspace]($style) "
+                tofu_symbol = "🦉 "
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("🦉 default")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn workspace_override_takes_precedence_over_environment_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let tf_dir = dir.path().join(".terraform");
+        fs::create_dir(&tf_dir)?;
+        fs::write(tf_dir.join("environment"), "development")?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .env("TF_WORKSPACE", "production")
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 production")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_backend_state() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let tf_dir = dir.path().join(".terraform");
+        fs::create_dir(&tf_dir)?;
+        fs::write(
+            tf_dir.join("terraform.tfstate"),
+            r#"{
+                "version": 3,
+                "serial": 1,
+                "backend": {
+                    "type": "s3",
+                    "config": { "bucket": "state", "key": "prod.tfstate" },
+                    "hash": 1234
+                }
+            }"#,
+        )?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .config(toml::toml! {
+                [terraform]
+                format = "via [$symbol$workspace \\($backend\\)]($style) "
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 default (s3)")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_without_backend_state_is_local() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.tf"))?;
+
+        let actual = ModuleRenderer::new("terraform")
+            .path(dir.path())
+            .config(toml::toml! {
+                [terraform]
+                format = "via [$symbol$backend]($style) "
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 local")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    const REQUIRED_PROVIDERS: &str = r#"
+terraform {
+  required_providers {
+    aws = {
+      source = "hashicorp/aws"
+    }
+    random = "~> 3.0"
+  }
+}
+"#;
+
+    fn render_lock_status(dir: &Path) -> Option<String> {
+        ModuleRenderer::new("terraform")
+            .path(dir)
+            .config(toml::toml! {
+                [terraform]
+                format = "via [$symbol$workspace( $lock)]($style) "
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lock_file_in_sync() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("main.tf"), REQUIRED_PROVIDERS)?;
+        fs::write(
+            dir.path().join(".terraform.lock.hcl"),
+            "provider \"registry.terraform.io/hashicorp/aws\" {\n}\n\
+             provider \"registry.terraform.io/hashicorp/random\" {\n}\n",
+        )?;
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 default")
+        ));
+
+        assert_eq!(expected, render_lock_status(dir.path()));
+        dir.close()
+    }
+
+    #[test]
+    fn lock_file_missing_provider() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("main.tf"), REQUIRED_PROVIDERS)?;
+        fs::write(
+            dir.path().join(".terraform.lock.hcl"),
+            "provider \"registry.terraform.io/hashicorp/aws\" {\n}\n",
+        )?;
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 default !lock")
+        ));
+
+        assert_eq!(expected, render_lock_status(dir.path()));
+        dir.close()
+    }
+
+    #[test]
+    fn lock_file_absent_with_required_providers() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("main.tf"), REQUIRED_PROVIDERS)?;
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 default !lock")
+        ));
+
+        assert_eq!(expected, render_lock_status(dir.path()));
+        dir.close()
+    }
 }
@@ -1,4 +1,4 @@
-use super::{Context, Detected, Module, ModuleConfig};
+use super::{Context, Detected, Module, ModuleConfig, Variable};
 
 use crate::configs::username::UsernameConfig;
 use crate::formatter::StringFormatter;
@@ -9,6 +9,14 @@ const USERNAME_ENV_VAR: &str = "USER";
 #[cfg(target_os = "windows")]
 const USERNAME_ENV_VAR: &str = "USERNAME";
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("user", "The currently logged-in user ID"),
+    Variable::style(
+        "style",
+        "Mirrors the value of option `style_root` when root is logged in and `style_user` otherwise",
+    ),
+];
+
 /// Creates a module with the current user's username
 ///
 /// Will display the username if any of the following criteria are met:
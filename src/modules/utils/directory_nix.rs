@@ -36,6 +36,18 @@ pub fn is_write_allowed(folder_path: &Path) -> Result<bool, String> {
     }
 }
 
+/// Checks if `folder_path` is the root of a mounted filesystem,
+/// i.e. if it's on a different device than its parent.
+pub fn is_mount_point(folder_path: &Path) -> bool {
+    let Some(parent) = folder_path.parent() else {
+        return true;
+    };
+    match (fs::metadata(folder_path), fs::metadata(parent)) {
+        (Ok(meta), Ok(parent_meta)) => meta.dev() != parent_meta.dev(),
+        _ => false,
+    }
+}
+
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
 fn get_supplementary_groups() -> Vec<u32> {
     nix::unistd::getgroups()
@@ -148,3 +148,8 @@ pub fn is_write_allowed(folder_path: &Path) -> std::result::Result<bool, String>
 
     Ok(result.as_bool())
 }
+
+/// Checks if `folder_path` is the root of a volume, e.g. `C:\`
+pub fn is_mount_point(folder_path: &Path) -> bool {
+    folder_path.parent().is_none()
+}
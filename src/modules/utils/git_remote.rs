@@ -0,0 +1,128 @@
+use indexmap::IndexMap;
+
+use crate::utils::encode_url_path;
+
+/// The web location of a git remote, e.g. `https://github.com/starship/starship`
+#[derive(Debug, PartialEq, Eq)]
+pub struct WebRemote {
+    pub host: String,
+    pub base_url: String,
+}
+
+/// Derives the web URL of a repository from its remote URL
+///
+/// Supports `scheme://[user@]host[:port]/path` URLs as well as the scp-like
+/// `[user@]host:path` syntax. The web URL always uses `https`, drops the user
+/// and port, and strips the trailing `.git` from the path.
+pub fn web_remote(remote_url: &str) -> Option<WebRemote> {
+    let (host, path) = if let Some((scheme, rest)) = remote_url.split_once("://") {
+        if scheme == "file" {
+            return None;
+        }
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        (host, path)
+    } else {
+        let (authority, path) = remote_url.split_once(':')?;
+        // Local paths such as `C:\repo` or `./repo:name` are not remotes
+        if authority.contains(['/', '\\']) || authority.len() == 1 {
+            return None;
+        }
+        (authority.rsplit('@').next()?, path)
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+
+    Some(WebRemote {
+        host: host.to_string(),
+        base_url: format!("https://{host}/{path}"),
+    })
+}
+
+/// Expands the URL template configured for the host of `remote_url`
+///
+/// `{base_url}` is replaced with the web URL of the repository and every
+/// `{name}` in `values` with its percent-encoded value.
+pub fn expand_url_template(
+    templates: &IndexMap<String, &str>,
+    remote_url: &str,
+    values: &[(&str, &str)],
+) -> Option<String> {
+    let remote = web_remote(remote_url)?;
+    let template = templates.get(&remote.host)?;
+
+    let url = values.iter().fold(
+        template.replace("{base_url}", &remote.base_url),
+        |url, (name, value)| url.replace(&format!("{{{name}}}"), &encode_url_path(value)),
+    );
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::indexmap;
+
+    #[test]
+    fn web_remote_from_https_url() {
+        assert_eq!(
+            web_remote("https://github.com/starship/starship.git"),
+            Some(WebRemote {
+                host: "github.com".to_string(),
+                base_url: "https://github.com/starship/starship".to_string(),
+            })
+        );
+        assert_eq!(
+            web_remote("https://user@gitlab.example.com:8443/group/sub/project/")
+                .map(|r| r.base_url),
+            Some("https://gitlab.example.com/group/sub/project".to_string())
+        );
+    }
+
+    #[test]
+    fn web_remote_from_ssh_url() {
+        assert_eq!(
+            web_remote("git@codeberg.org:forgejo/forgejo.git").map(|r| r.base_url),
+            Some("https://codeberg.org/forgejo/forgejo".to_string())
+        );
+        assert_eq!(
+            web_remote("ssh://git@gitea.example.com:2222/owner/repo.git").map(|r| r.base_url),
+            Some("https://gitea.example.com/owner/repo".to_string())
+        );
+    }
+
+    #[test]
+    fn web_remote_from_local_path() {
+        assert_eq!(web_remote("/srv/git/repo.git"), None);
+        assert_eq!(web_remote("file:///srv/git/repo.git"), None);
+        assert_eq!(web_remote(r"C:\git\repo"), None);
+    }
+
+    #[test]
+    fn expand_template_for_host() {
+        let templates = indexmap! {
+            "gitlab.example.com".to_string() => "{base_url}/-/tree/{branch}",
+        };
+        assert_eq!(
+            expand_url_template(
+                &templates,
+                "git@gitlab.example.com:team/app.git",
+                &[("branch", "feature/50% off")]
+            ),
+            Some("https://gitlab.example.com/team/app/-/tree/feature/50%25%20off".to_string())
+        );
+        assert_eq!(
+            expand_url_template(
+                &templates,
+                "git@github.com:team/app.git",
+                &[("branch", "main")]
+            ),
+            None
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod directory;
 
+pub mod git_remote;
+
 #[cfg(target_os = "windows")]
 pub mod directory_win;
 
@@ -1,9 +1,15 @@
-use super::{Context, Module};
+use super::{Context, Module, Variable};
 
 use crate::config::ModuleConfig;
 use crate::configs::vcsh::VcshConfig;
 use crate::formatter::StringFormatter;
 
+pub const VARIABLES: &[Variable] = &[
+    Variable::new("repo", "The active repository name"),
+    Variable::new("symbol", "Mirrors the value of option `symbol`"),
+    Variable::style("style", "Mirrors the value of option `style`"),
+];
+
 /// Creates a module that displays VCSH repository currently in use
 ///
 /// Will display the name of the current VCSH repository if one is active.
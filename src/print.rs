@@ -1,26 +1,33 @@
 use clap::{ValueEnum, builder::PossibleValue};
-use nu_ansi_term::AnsiStrings;
+use indexmap::IndexMap;
+use nu_ansi_term::{AnsiStrings, Style as AnsiStyle};
 use rayon::prelude::*;
 use regex::Regex;
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 use std::fmt::{Debug, Write as FmtWrite};
 use std::io::{self, Write};
+use std::mem;
 use std::path::PathBuf;
 use std::sync::OnceLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use terminal_size::terminal_size;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthChar;
 
-use crate::configs::PROMPT_ORDER;
+use crate::config::ModuleConfig;
+use crate::configs::directory::DirectoryConfig;
+use crate::configs::{PROMPT_ORDER, REQUIRED_LAYOUT_PRIORITY, SeparatorConfig};
 use crate::context::{Context, Properties, Shell, Target};
 use crate::formatter::{StringFormatter, VariableHolder};
+use crate::init;
 use crate::module::ALL_MODULES;
 use crate::module::Module;
 use crate::modules;
 use crate::segment::Segment;
 use crate::shadow;
-use crate::utils::wrap_colorseq_for_shell;
+use crate::timings;
+use crate::utils::{self, shell_integration_mark, wrap_colorseq_for_shell};
 
 pub struct Grapheme<'a>(pub &'a str);
 
@@ -41,7 +48,10 @@ pub trait UnicodeWidthGraphemes {
 static ANSI_REGEX: OnceLock<Regex> = OnceLock::new();
 
 fn ansi_strip() -> &'static Regex {
-    ANSI_REGEX.get_or_init(|| Regex::new(r"\x1B\[[0-9;]*m").unwrap())
+    // Color sequences, OSC 8 hyperlinks and OSC 133 marks, terminated by either BEL or ST
+    ANSI_REGEX.get_or_init(|| {
+        Regex::new(r"\x1B\[[0-9;]*m|\x1B\](?:8|133);[^\x07\x1B]*(?:\x07|\x1B\\)").unwrap()
+    })
 }
 
 impl<T> UnicodeWidthGraphemes for T
@@ -67,13 +77,34 @@ fn test_grapheme_aware_width() {
     assert_eq!(11, "normal text".width_graphemes());
     // Magenta string test
     assert_eq!(11, "\x1B[35;6mnormal text".width_graphemes());
+    // Hyperlink test
+    assert_eq!(
+        4,
+        "\x1B]8;;file:///tmp\x07/tmp\x1B]8;;\x1B\\".width_graphemes()
+    );
 }
 
 pub fn prompt(args: Properties, target: Target) {
     let context = Context::new(args, target);
+    let recorder = context
+        .root_config
+        .record_timings
+        .then(|| context.record_spans());
+    let start = Instant::now();
+
     let stdout = io::stdout();
     let mut handle = stdout.lock();
     write!(handle, "{}", get_prompt(&context)).unwrap();
+    context.save_module_runtimes();
+    init::record_prompt(&context, std::env::args().skip(1));
+
+    if let Some(recorder) = recorder {
+        let entry = timings::TimingsEntry::new(&context, start.elapsed(), &recorder.spans());
+        let path = timings::timings_log_path(&context);
+        if let Err(error) = timings::append_timings_log(&path, &entry) {
+            log::warn!("Unable to write timings to {}: {error}", path.display());
+        }
+    }
 }
 
 pub fn get_prompt(context: &Context) -> String {
@@ -95,42 +126,34 @@ pub fn get_prompt(context: &Context) -> String {
         buf.push_str("\x1b[J"); // An ASCII control code to clear screen
     }
 
-    let (formatter, modules) = load_formatter_and_modules(context);
+    // OSC 133 marks for the end of the previous command and the start of the prompt.
+    // The start of the command output is marked by the preexec hooks of the init scripts.
+    let shell_integration = config.shell_integration && context.target == Target::Main;
+    if shell_integration {
+        let command_finished = match context.properties.status_code.as_deref() {
+            Some(status) if status.parse::<i64>().is_ok() => format!("D;{status}"),
+            _ => "D".to_string(),
+        };
+        let marks = shell_integration_mark(&command_finished) + &shell_integration_mark("A");
+        buf.push_str(&wrap_colorseq_for_shell(marks, context.shell));
+    }
 
-    let formatter = formatter.map_variables_to_segments(|module| {
-        // Make $all display all modules not explicitly referenced
-        if module == "all" {
-            Some(Ok(all_modules_uniq(&modules)
-                .par_iter()
-                .flat_map(|module| {
-                    handle_module(module, context, &modules)
-                        .into_iter()
-                        .flat_map(|module| module.segments)
-                        .collect::<Vec<Segment>>()
-                })
-                .collect::<Vec<_>>()))
-        } else if context.is_module_disabled_in_config(module) {
-            None
-        } else {
-            // Get segments from module
-            Some(Ok(handle_module(module, context, &modules)
-                .into_iter()
-                .flat_map(|module| module.segments)
-                .collect::<Vec<Segment>>()))
-        }
-    });
+    let mut module_segments = compute_module_segments(context);
+    let mut root_segments = render_root(context, &module_segments);
+    if config.responsive_layout {
+        root_segments = apply_responsive_layout(context, &mut module_segments, root_segments);
+    }
+    if let Some(fitted) = fit_directory_to_width(context, &mut module_segments, &root_segments) {
+        root_segments = fitted;
+    }
 
     // Creates a root module and prints it.
     let mut root_module = Module::new("Starship Root", "The root module", None);
-    root_module.set_segments(
-        formatter
-            .parse(None, Some(context))
-            .expect("Unexpected error returned in root format variables"),
-    );
+    root_module.set_segments(root_segments);
 
     let module_strings = root_module.ansi_strings_for_width(Some(context.width));
-    if config.add_newline && context.target != Target::Continuation {
-        // continuation prompts normally do not include newlines, but they can
+    if config.add_newline && !matches!(context.target, Target::Continuation | Target::Transient) {
+        // continuation and transient prompts normally do not include newlines, but they can
         writeln!(buf).unwrap();
     }
     // AnsiStrings strips redundant ANSI color sequences, so apply it before modifying the ANSI
@@ -139,6 +162,11 @@ pub fn get_prompt(context: &Context) -> String {
         wrap_colorseq_for_shell(AnsiStrings(&module_strings).to_string(), context.shell);
     write!(buf, "{shell_wrapped_output}").unwrap();
 
+    if shell_integration {
+        let prompt_end = shell_integration_mark("B");
+        buf.push_str(&wrap_colorseq_for_shell(prompt_end, context.shell));
+    }
+
     if context.target == Target::Right {
         // right prompts generally do not allow newlines
         buf = buf.replace('\n', "");
@@ -151,9 +179,395 @@ pub fn get_prompt(context: &Context) -> String {
         buf = buf.replace('\n', " \\n");
     }
 
+    // ksh replaces ! with the history number, !! is a literal !
+    if matches!(context.shell, Shell::Ksh | Shell::Mksh) {
+        buf = buf.replace('!', "!!");
+    }
+
     buf
 }
 
+/// Computes the segments of every module referenced by the prompt format, keyed by module name
+///
+/// Modules that are disabled in the config and referenced explicitly are left out, so that
+/// they are treated as unset variables by the root format.
+fn compute_module_segments(context: &Context) -> IndexMap<String, Vec<Segment>> {
+    let (formatter, modules) = load_formatter_and_modules(context);
+
+    formatter
+        .get_variables()
+        .into_iter()
+        .flat_map(|module| {
+            // $all displays all modules not explicitly referenced
+            if module == "all" {
+                all_modules_uniq(&modules)
+            } else if context.is_module_disabled_in_config(&module) {
+                Vec::new()
+            } else {
+                vec![module]
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|module| {
+            let segments = handle_module(&module, context, &modules)
+                .into_iter()
+                .flat_map(|module| module.segments)
+                .collect::<Vec<Segment>>();
+            (module, segments)
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Renders the root format with the precomputed module segments
+fn render_root(
+    context: &Context,
+    module_segments: &IndexMap<String, Vec<Segment>>,
+) -> Vec<Segment> {
+    let (formatter, modules) = load_formatter_and_modules(context);
+    let separator = &context.root_config.separator;
+    // Mark where each module starts for the separators to be inserted there
+    let segments_of = |module: &str| {
+        let segments = module_segments.get(module)?;
+        let boundary = (!separator.disabled).then_some(Segment::Boundary);
+        Some(
+            boundary
+                .into_iter()
+                .chain(segments.iter().cloned())
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let segments = formatter
+        .map_variables_to_segments(|module| {
+            if module == "all" {
+                Some(Ok(all_modules_uniq(&modules)
+                    .iter()
+                    .filter_map(|module| segments_of(module))
+                    .flatten()
+                    .collect()))
+            } else {
+                segments_of(module).map(Ok)
+            }
+        })
+        .parse(None, Some(context))
+        .expect("Unexpected error returned in root format variables");
+
+    if separator.disabled {
+        segments
+    } else {
+        insert_separators(separator, segments, context.target == Target::Right)
+    }
+}
+
+/// Replaces the boundaries between modules with separators colored by the backgrounds of the
+/// modules around them
+///
+/// Each line is split in parts at `$fill`. The separators of the parts after a `$fill` and of the
+/// right prompt point left.
+fn insert_separators(
+    config: &SeparatorConfig,
+    segments: Vec<Segment>,
+    right: bool,
+) -> Vec<Segment> {
+    let mut output = Vec::with_capacity(segments.len());
+    let mut modules: Vec<Vec<Segment>> = Vec::new();
+    let mut pointing_left = right;
+    for segment in segments {
+        match segment {
+            Segment::Boundary => modules.push(Vec::new()),
+            Segment::LineTerm | Segment::Fill(_) => {
+                output.extend(join_modules(config, mem::take(&mut modules), pointing_left));
+                pointing_left = right || matches!(segment, Segment::Fill(_));
+                output.push(segment);
+            }
+            segment => match modules.last_mut() {
+                Some(module) => module.push(segment),
+                None => output.push(segment),
+            },
+        }
+    }
+    output.extend(join_modules(config, modules, pointing_left));
+    output
+}
+
+/// Joins the modules of a part of a line with separators, skipping modules that are empty
+fn join_modules(
+    config: &SeparatorConfig,
+    modules: Vec<Vec<Segment>>,
+    pointing_left: bool,
+) -> Vec<Segment> {
+    let mut output = Vec::new();
+    // The style at the end of the previous module, if any
+    let mut previous: Option<Option<AnsiStyle>> = None;
+    for module in modules {
+        let mut visible = module
+            .iter()
+            .filter(|segment| segment.width_graphemes() > 0);
+        let Some(first) = visible.next() else {
+            output.extend(module);
+            continue;
+        };
+        let last = visible.next_back().unwrap_or(first).style();
+        output.extend(separator(
+            config,
+            previous.flatten(),
+            first.style(),
+            pointing_left,
+        ));
+        output.extend(module);
+        previous = Some(last);
+    }
+    output.extend(separator(config, previous.flatten(), None, pointing_left));
+    output
+}
+
+/// The separator between the styles at the edges of two modules, or of a module and the start
+/// or end of the line if `None`
+fn separator(
+    config: &SeparatorConfig,
+    before: Option<AnsiStyle>,
+    after: Option<AnsiStyle>,
+    pointing_left: bool,
+) -> Vec<Segment> {
+    let paint = |glyph: &str, style: AnsiStyle| {
+        if glyph.is_empty() {
+            Vec::new()
+        } else {
+            Segment::from_text(Some(style.into()), glyph)
+        }
+    };
+    let before_bg = before.and_then(|style| style.background);
+    let after_bg = after.and_then(|style| style.background);
+
+    match (before_bg, after_bg) {
+        // Modules with the same background are told apart by a thin separator in the foreground
+        // color of the module it points at
+        (Some(before_bg), Some(after_bg)) if before_bg == after_bg => {
+            let (glyph, foreground) = if pointing_left {
+                (&config.right_thin, after.and_then(|style| style.foreground))
+            } else {
+                (&config.left_thin, before.and_then(|style| style.foreground))
+            };
+            let style = AnsiStyle {
+                foreground,
+                ..AnsiStyle::new().on(before_bg)
+            };
+            paint(glyph, style)
+        }
+        (Some(before_bg), Some(after_bg)) if pointing_left => {
+            paint(&config.right, after_bg.on(before_bg))
+        }
+        (Some(before_bg), Some(after_bg)) => paint(&config.left, before_bg.on(after_bg)),
+        (Some(before_bg), None) if !config.end.is_empty() => paint(&config.end, before_bg.normal()),
+        (Some(before_bg), None) if !pointing_left => paint(&config.left, before_bg.normal()),
+        (None, Some(after_bg)) if !config.start.is_empty() => {
+            paint(&config.start, after_bg.normal())
+        }
+        (None, Some(after_bg)) if pointing_left => paint(&config.right, after_bg.normal()),
+        _ => Vec::new(),
+    }
+}
+
+/// Shrinks the prompt while any of its lines is wider than the terminal
+///
+/// Modules are handled from the lowest to the highest `layout_priorities`, later modules first
+/// among equal priorities. First, modules with a `format_compact` switch to it, then modules are
+/// dropped. Changes that don't shrink an overflowing line are reverted.
+fn apply_responsive_layout(
+    context: &Context,
+    module_segments: &mut IndexMap<String, Vec<Segment>>,
+    root_segments: Vec<Segment>,
+) -> Vec<Segment> {
+    let config = &context.root_config;
+    if context.width == 0 {
+        return root_segments;
+    }
+
+    let (_, module_list) = load_formatter_and_modules(context);
+    let mut by_priority: Vec<String> = module_segments.keys().rev().cloned().collect();
+    by_priority.sort_by_key(|module| config.layout_priority(module));
+
+    let mut root_segments = root_segments;
+    for compact in [true, false] {
+        for module in &by_priority {
+            let widths = line_widths(&root_segments);
+            if widths.iter().all(|width| *width <= context.width) {
+                return root_segments;
+            }
+
+            let replacement = if compact {
+                if !context.set_compact(module, true) {
+                    continue;
+                }
+                handle_module(module, context, &module_list)
+                    .into_iter()
+                    .flat_map(|module| module.segments)
+                    .collect()
+            } else if config.layout_priority(module) < REQUIRED_LAYOUT_PRIORITY {
+                Vec::new()
+            } else {
+                continue;
+            };
+
+            let Some(previous) = module_segments.insert(module.clone(), replacement) else {
+                continue;
+            };
+            let candidate = render_root(context, module_segments);
+            if shrinks_overflowing_line(&widths, &line_widths(&candidate), context.width) {
+                log::trace!(
+                    "Responsive layout {} module {module:?}",
+                    if compact { "compacted" } else { "dropped" }
+                );
+                root_segments = candidate;
+            } else {
+                if compact {
+                    context.set_compact(module, false);
+                }
+                module_segments.insert(module.clone(), previous);
+            }
+        }
+    }
+
+    root_segments
+}
+
+/// Whether an overflowing line got narrower, without any other line starting to overflow
+fn shrinks_overflowing_line(before: &[usize], after: &[usize], width: usize) -> bool {
+    before.len() == after.len()
+        && before
+            .iter()
+            .zip(after)
+            .any(|(before, after)| *before > width && after < before)
+        && before
+            .iter()
+            .zip(after)
+            .all(|(before, after)| after <= before || *after <= width)
+}
+
+/// Returns the width of each line of the prompt, not counting `$fill`
+fn line_widths(segments: &[Segment]) -> Vec<usize> {
+    let mut widths = vec![0];
+    for segment in segments {
+        match segment {
+            Segment::LineTerm => widths.push(0),
+            Segment::Fill(_) | Segment::Boundary => {}
+            Segment::Text(_) => *widths.last_mut().unwrap() += segment.width_graphemes(),
+        }
+    }
+    widths
+}
+
+/// Re-renders the prompt with a shortened `directory` if `truncate_to_width` is enabled and its
+/// line doesn't fit in the terminal
+///
+/// The columns left for the directory are measured by rendering its line without it.
+fn fit_directory_to_width(
+    context: &Context,
+    module_segments: &mut IndexMap<String, Vec<Segment>>,
+    root_segments: &[Segment],
+) -> Option<Vec<Segment>> {
+    let config = DirectoryConfig::try_load(context.config.get_module_config("directory"));
+    if !config.truncate_to_width || context.width == 0 {
+        return None;
+    }
+
+    let directory = module_segments.get("directory")?;
+    if directory
+        .iter()
+        .any(|segment| matches!(segment, Segment::LineTerm))
+    {
+        return None;
+    }
+
+    let widths = line_widths(root_segments);
+    if widths.iter().all(|width| *width <= context.width) {
+        return None;
+    }
+
+    let directory = module_segments.insert("directory".to_string(), Vec::new())?;
+    let widths_without = line_widths(&render_root(context, module_segments));
+    let line = widths
+        .iter()
+        .zip(&widths_without)
+        .position(|(with, without)| with != without)
+        .filter(|line| widths[*line] > context.width && widths.len() == widths_without.len());
+    let Some(line) = line else {
+        module_segments.insert("directory".to_string(), directory);
+        return None;
+    };
+
+    let available = context.width.saturating_sub(widths_without[line]);
+    log::trace!("Fitting directory in {available} columns on line {line}");
+    let fitted = modules::directory::module_for_width(context, Some(available))
+        .map(|module| module.segments)
+        .unwrap_or(directory);
+    module_segments.insert("directory".to_string(), fitted);
+    Some(render_root(context, module_segments))
+}
+
+pub fn title(args: Properties, command: Option<&str>, plain: bool) {
+    let mut context = Context::new(args, Target::Main);
+    // The title is written to the terminal directly, so it must not be escaped for the prompt
+    context.shell = Shell::Unknown;
+
+    let title = get_title(&context, command);
+    if plain {
+        print!("{title}");
+    } else if !title.is_empty() {
+        print!("\x1b]0;{title}\x07");
+    }
+}
+
+/// Renders `title_format`, or `title_preexec_format` while `command` runs, as plain text
+pub fn get_title(context: &Context, command: Option<&str>) -> String {
+    let (name, format) = match command {
+        Some(_) => (
+            "title_preexec_format",
+            &context.root_config.title_preexec_format,
+        ),
+        None => ("title_format", &context.root_config.title_format),
+    };
+    let formatter = match StringFormatter::new(format) {
+        Ok(formatter) => formatter,
+        Err(error) => {
+            log::warn!("Error parsing `{name}`:\n{error}");
+            return String::new();
+        }
+    };
+    let modules = formatter.get_variables().into_iter().collect();
+
+    let segments = formatter
+        .map_variables_to_segments(|variable| match variable {
+            "command" => command.map(|command| Ok(Segment::from_text(None, command))),
+            module => Some(Ok(handle_module(module, context, &modules)
+                .into_iter()
+                .flat_map(|module| module.segments)
+                .collect())),
+        })
+        .parse(None, Some(context));
+
+    match segments {
+        Ok(segments) => {
+            let title = segments.iter().map(Segment::value).collect::<String>();
+            // Drop the styles and hyperlinks of modules as well as line breaks
+            ansi_strip()
+                .replace_all(&title, "")
+                .chars()
+                .filter(|c| !c.is_control())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        }
+        Err(error) => {
+            log::warn!("Error in `{name}`:\n{error}");
+            String::new()
+        }
+    }
+}
+
 pub fn module(module_name: &str, args: Properties) {
     let context = Context::new(args, Target::Main);
     let module = get_module(module_name, &context).unwrap_or_default();
@@ -166,6 +580,12 @@ pub fn get_module(module_name: &str, context: &Context) -> Option<String> {
 
 pub fn timings(args: Properties) {
     let context = Context::new(args, Target::Main);
+    print!("{}", get_timings(&context));
+}
+
+/// Returns the timings of the modules in the prompt, as printed by `starship timings`
+pub fn get_timings(context: &Context) -> String {
+    let mut buf = String::new();
 
     struct ModuleTiming {
         name: String,
@@ -175,7 +595,7 @@ pub fn timings(args: Properties) {
         duration_len: usize,
     }
 
-    let mut modules = compute_modules(&context)
+    let mut modules = compute_modules(context)
         .iter()
         .filter(|module| !module.is_empty() || module.duration.as_millis() > 0)
         .map(|module| ModuleTiming {
@@ -189,29 +609,55 @@ pub fn timings(args: Properties) {
         })
         .collect::<Vec<ModuleTiming>>();
 
-    modules.sort_by(|a, b| b.duration.cmp(&a.duration));
+    modules.sort_by_key(|module| std::cmp::Reverse(module.duration));
 
     let max_name_width = modules.iter().map(|i| i.name_len).max().unwrap_or(0);
     let max_duration_width = modules.iter().map(|i| i.duration_len).max().unwrap_or(0);
 
-    println!("\n Here are the timings of modules in your prompt (>=1ms or output):");
+    writeln!(
+        buf,
+        "\n Here are the timings of modules in your prompt (>=1ms or output):"
+    )
+    .unwrap();
 
     // for now we do not expect a wrap around at the end... famous last words
     // Overall a line looks like this: " {module name}  -  {duration}  -  "{module value}"".
     for timing in &modules {
-        println!(
+        writeln!(
+            buf,
             " {}{}  -  {}{}  -   \"{}\"",
             timing.name,
             " ".repeat(max_name_width - (timing.name_len)),
             " ".repeat(max_duration_width - (timing.duration_len)),
             format_duration(&timing.duration),
             timing.value
-        );
+        )
+        .unwrap();
     }
+
+    let auto_disabled = context.auto_disabled_modules();
+    if !auto_disabled.is_empty() {
+        writeln!(
+            buf,
+            "\n Disabled in this directory for taking longer than {}ms: {}",
+            context.root_config.auto_disable_threshold,
+            auto_disabled.join(", ")
+        )
+        .unwrap();
+        writeln!(buf, " Run `starship timings --reset` to enable them again.").unwrap();
+    }
+
+    buf
 }
 
 pub fn explain(args: Properties) {
     let context = Context::new(args, Target::Main);
+    print!("{}", get_explain(&context));
+}
+
+/// Returns the breakdown of the prompt printed by `starship explain`
+pub fn get_explain(context: &Context) -> String {
+    let mut buf = String::new();
 
     struct ModuleInfo {
         value: String,
@@ -222,7 +668,7 @@ pub fn explain(args: Properties) {
 
     static DONT_PRINT: &[&str] = &["line_break"];
 
-    let modules = compute_modules(&context)
+    let modules = compute_modules(context)
         .into_iter()
         .filter(|module| !DONT_PRINT.contains(&module.get_name().as_str()))
         // this contains empty modules which should not print
@@ -250,26 +696,28 @@ pub fn explain(args: Properties) {
         // Add padding length to module length to avoid text overflow. This line also assures desc_width >= 0.
         .map(|width| width - std::cmp::min(width, max_module_width + PADDING_WIDTH));
 
-    println!("\n Here's a breakdown of your prompt:");
+    writeln!(buf, "\n Here's a breakdown of your prompt:").unwrap();
     for info in modules {
         if let Some(desc_width) = desc_width {
             // Custom Textwrapping!
             let mut current_pos = 0;
             let mut escaping = false;
             // Print info
-            print!(
+            write!(
+                buf,
                 " \"{}\" ({}){}  -  ",
                 info.value,
                 info.duration,
                 " ".repeat(max_module_width - (info.value_len))
-            );
+            )
+            .unwrap();
             for g in info.desc.graphemes(true) {
                 // Handle ANSI escape sequences
                 if g == "\x1B" {
                     escaping = true;
                 }
                 if escaping {
-                    print!("{g}");
+                    write!(buf, "{g}").unwrap();
                     escaping = !(("a"..="z").contains(&g) || ("A"..="Z").contains(&g));
                     continue;
                 }
@@ -283,7 +731,7 @@ pub fn explain(args: Properties) {
                         continue;
                     }
 
-                    print!("\n{}", " ".repeat(max_module_width + PADDING_WIDTH));
+                    write!(buf, "\n{}", " ".repeat(max_module_width + PADDING_WIDTH)).unwrap();
                     if g == "\n" {
                         current_pos = 0;
                         continue;
@@ -291,18 +739,22 @@ pub fn explain(args: Properties) {
 
                     current_pos = 1;
                 }
-                print!("{g}");
+                write!(buf, "{g}").unwrap();
             }
-            println!();
+            writeln!(buf).unwrap();
         } else {
-            println!(
+            writeln!(
+                buf,
                 " {}{}  -  {}",
                 info.value,
                 " ".repeat(max_module_width - info.value_len),
                 info.desc,
-            );
+            )
+            .unwrap();
         }
     }
+
+    buf
 }
 
 fn compute_modules<'a>(context: &'a Context) -> Vec<Module<'a>> {
@@ -428,16 +880,20 @@ fn all_modules_uniq(module_list: &BTreeSet<String>) -> Vec<String> {
 fn load_formatter_and_modules<'a>(context: &'a Context) -> (StringFormatter<'a>, BTreeSet<String>) {
     let config = &context.root_config;
 
-    if context.target == Target::Continuation {
-        let cf = &config.continuation_prompt;
-        let formatter = StringFormatter::new(cf);
+    let single_format = match context.target {
+        Target::Continuation => Some(("continuation prompt", &config.continuation_prompt)),
+        Target::Transient => Some(("transient_format", &config.transient_format)),
+        _ => None,
+    };
+    if let Some((name, format)) = single_format {
+        let formatter = StringFormatter::new(format);
         return match formatter {
             Ok(f) => {
                 let modules = f.get_variables().into_iter().collect();
                 (f, modules)
             }
             Err(e) => {
-                log::error!("Error parsing continuation prompt: {e}");
+                log::error!("Error parsing {name}: {e}");
                 (StringFormatter::raw(">"), BTreeSet::new())
             }
         };
@@ -453,7 +909,9 @@ fn load_formatter_and_modules<'a>(context: &'a Context) -> (StringFormatter<'a>,
                 return (StringFormatter::raw(">"), BTreeSet::new());
             }
         }
-        Target::Continuation => unreachable!("Continuation prompt should have been handled above"),
+        Target::Continuation | Target::Transient => {
+            unreachable!("Continuation and transient prompts should have been handled above")
+        }
     };
 
     let lf = StringFormatter::new(left_format_str);
@@ -481,7 +939,9 @@ fn load_formatter_and_modules<'a>(context: &'a Context) -> (StringFormatter<'a>,
     let main_formatter = match context.target {
         Target::Main | Target::Profile(_) => lf,
         Target::Right => rf,
-        Target::Continuation => unreachable!("Continuation prompt should have been handled above"),
+        Target::Continuation | Target::Transient => {
+            unreachable!("Continuation and transient prompts should have been handled above")
+        }
     };
 
     match main_formatter {
@@ -490,9 +950,106 @@ fn load_formatter_and_modules<'a>(context: &'a Context) -> (StringFormatter<'a>,
     }
 }
 
+/// Prints the supported modules, with their description and variables if `verbose` is set
+pub fn list_modules(verbose: bool) {
+    println!("Supported modules list");
+    println!("----------------------");
+    for module in ALL_MODULES {
+        if !verbose {
+            println!("{module}");
+            continue;
+        }
+
+        println!("\n{module}: {}", modules::description(module));
+        let variables = modules::variables(module);
+        let width = variables
+            .iter()
+            .map(|variable| variable.name.len() + usize::from(variable.style))
+            .max()
+            .unwrap_or_default();
+        for variable in variables {
+            let name = format!("{}{}", variable.name, if variable.style { "*" } else { "" });
+            println!("  ${name:width$}  {}", variable.description);
+        }
+    }
+    if verbose {
+        println!("\n*: This variable can only be used as a part of a style string");
+    }
+}
+
 #[cfg(feature = "config-schema")]
 pub fn print_schema() {
-    let schema = schemars::schema_for!(crate::configs::FullConfig);
+    let mut schema = schemars::schema_for!(crate::configs::FullConfig);
+
+    // Every module with a format also accepts the `format_compact` used by the responsive layout
+    if let Some(defs) = schema
+        .get_mut("$defs")
+        .and_then(|defs| defs.as_object_mut())
+    {
+        for def in defs.values_mut() {
+            let Some(properties) = def
+                .get_mut("properties")
+                .and_then(|properties| properties.as_object_mut())
+            else {
+                continue;
+            };
+            if properties.contains_key("format") {
+                properties.insert(
+                    "format_compact".to_string(),
+                    serde_json::json!({
+                        "description": "The format used when the responsive layout compacts the module.",
+                        "type": "string",
+                    }),
+                );
+            }
+        }
+    }
+
+    // Describe each module and the variables it provides to its format strings
+    if let Some(properties) = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.as_object_mut())
+    {
+        for module in ALL_MODULES.iter().chain(&["custom", "env_var"]) {
+            let Some(property) = properties.get_mut(*module) else {
+                continue;
+            };
+            // `custom` and `env_var` are tables of modules
+            let property = match property.get_mut("additionalProperties") {
+                Some(module) => module,
+                None => property,
+            };
+            let Some(property) = property.as_object_mut() else {
+                continue;
+            };
+
+            let variables = modules::variables(module);
+            let names = variables
+                .iter()
+                .map(|variable| format!("${}", variable.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let mut description = modules::description(module).to_string();
+            if !names.is_empty() {
+                write!(description, "\n\nVariables: {names}").unwrap();
+            }
+            property.insert("description".to_string(), description.into());
+            property.insert(
+                "x-variables".to_string(),
+                variables
+                    .iter()
+                    .map(|variable| {
+                        serde_json::json!({
+                            "name": variable.name,
+                            "description": variable.description,
+                            "style": variable.style,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+    }
+
     println!("{}", serde_json::to_string_pretty(&schema).unwrap());
 }
 
@@ -501,7 +1058,7 @@ pub struct Preset(pub &'static str);
 
 impl ValueEnum for Preset {
     fn value_variants<'a>() -> &'a [Self] {
-        shadow::get_preset_list()
+        all_presets()
     }
 
     fn to_possible_value(&self) -> Option<PossibleValue> {
@@ -509,19 +1066,105 @@ impl ValueEnum for Preset {
     }
 }
 
+/// The built-in presets, followed by the user presets
+fn all_presets() -> &'static [Preset] {
+    static PRESETS: OnceLock<Vec<Preset>> = OnceLock::new();
+    PRESETS.get_or_init(|| {
+        shadow::get_preset_list()
+            .iter()
+            .cloned()
+            .chain(user_presets().iter().map(|(preset, _)| preset.clone()))
+            .collect()
+    })
+}
+
+/// The presets found in the preset directories, see `preset_dirs`
+fn user_presets() -> &'static [(Preset, PathBuf)] {
+    static USER_PRESETS: OnceLock<Vec<(Preset, PathBuf)>> = OnceLock::new();
+    USER_PRESETS.get_or_init(|| {
+        find_presets(&preset_dirs())
+            .into_iter()
+            // Like those of the built-in presets, the names live as long as the process
+            .map(|(name, path)| (Preset(Box::leak(name.into_boxed_str())), path))
+            .collect()
+    })
+}
+
+/// `~/.config/starship/presets`, followed by the directories listed in `STARSHIP_PRESET_PATH`
+fn preset_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<_> = utils::home_dir()
+        .map(|home| home.join(".config").join("starship").join("presets"))
+        .into_iter()
+        .collect();
+    if let Some(paths) = std::env::var_os("STARSHIP_PRESET_PATH") {
+        dirs.extend(std::env::split_paths(&paths));
+    }
+    dirs
+}
+
+/// Finds the `*.toml` presets in the directories, skipping the names that are already taken by a
+/// built-in preset or a preset of an earlier directory
+fn find_presets(dirs: &[PathBuf]) -> Vec<(String, PathBuf)> {
+    let mut presets: Vec<(String, PathBuf)> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let taken = shadow::get_preset_list()
+                .iter()
+                .any(|preset| preset.0 == name)
+                || presets.iter().any(|(other, _)| other == name);
+            if taken {
+                log::warn!(
+                    "Ignoring the preset {}, as there is already a preset named `{name}`",
+                    path.display()
+                );
+                continue;
+            }
+            presets.push((name.to_string(), path));
+        }
+    }
+    presets
+}
+
+/// Returns the content of the preset, reading user presets from their file
+pub fn get_preset_content(preset: &Preset) -> io::Result<Cow<'static, [u8]>> {
+    match user_presets().iter().find(|(user, _)| user.0 == preset.0) {
+        Some((_, path)) => std::fs::read(path).map(Cow::Owned),
+        None => Ok(Cow::Borrowed(shadow::get_preset_content(preset.0))),
+    }
+}
+
 pub fn preset_command(name: Option<Preset>, output: Option<PathBuf>, list: bool) {
     if list {
         println!("{}", preset_list());
         return;
     }
     let variant = name.expect("name argument must be specified");
-    let content = shadow::get_preset_content(variant.0);
+    let content = match get_preset_content(&variant) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("Error reading preset {}: {err}", variant.0);
+            std::process::exit(1);
+        }
+    };
     if let Some(output) = output {
-        if let Err(err) = std::fs::write(output, content) {
+        if let Err(err) = std::fs::write(output, &content) {
             eprintln!("Error writing preset to file: {err}");
             std::process::exit(1);
         }
-    } else if let Err(err) = std::io::stdout().write_all(content) {
+    } else if let Err(err) = std::io::stdout().write_all(&content) {
         eprintln!("Error writing preset to stdout: {err}");
         std::process::exit(1);
     }
@@ -541,6 +1184,7 @@ mod test {
     use super::*;
     use crate::test::default_context;
     use crate::utils;
+    use nu_ansi_term::Color;
 
     const NULL_DEVICE: &str = if cfg!(windows) { "NUL" } else { "/dev/null" };
 
@@ -559,6 +1203,74 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn main_prompt_with_shell_integration() {
+        let mut context = default_context().set_config(toml::toml! {
+                add_newline=false
+                shell_integration=true
+                format="$character"
+                [character]
+                format=">"
+        });
+        context.properties.status_code = Some("127".to_string());
+        context.shell = Shell::Zsh;
+
+        let expected = "%{\x1b]133;D;127\x07%}%{\x1b]133;A\x07%}>%{\x1b]133;B\x07%}";
+        assert_eq!(expected, get_prompt(&context));
+
+        context.properties.status_code = None;
+        context.shell = Shell::Fish;
+        let expected = "\x1b[J\x1b]133;D\x07\x1b]133;A\x07>\x1b]133;B\x07";
+        assert_eq!(expected, get_prompt(&context));
+
+        context.target = Target::Right;
+        assert_eq!("", get_prompt(&context));
+    }
+
+    #[test]
+    fn title_from_format() {
+        let mut context = default_context().set_config(toml::toml! {
+                title_format="$character — $jobs"
+                [character]
+                format="[>](bold red)\n"
+                [jobs]
+                format="[$number jobs]($style)"
+                threshold=1
+        });
+        context.properties.jobs = 2;
+
+        assert_eq!(get_title(&context, None), "> — 2 jobs");
+        assert_eq!(get_title(&context, Some("cargo test\n")), "cargo test");
+    }
+
+    #[test]
+    fn title_with_hyperlinks() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context().set_config(toml::toml! {
+                title_format="$directory"
+                title_preexec_format="$command in $directory"
+                [directory]
+                hyperlinks=true
+                format="[$path]($style)"
+        });
+        context.current_dir = dir.path().to_path_buf();
+        context.logical_dir = dir.path().to_path_buf();
+        let name = dir
+            .path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+
+        assert!(get_title(&context, None).ends_with(&name));
+        assert!(
+            get_title(&context, Some("ls"))
+                .strip_prefix("ls in ")
+                .is_some_and(|title| title.ends_with(&name))
+        );
+        dir.close()
+    }
+
     #[test]
     fn right_prompt() {
         let mut context = default_context().set_config(toml::toml! {
@@ -656,6 +1368,443 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn ksh_escapes_exclamation_mark() {
+        let mut context = default_context().set_config(toml::toml! {
+                format="$character"
+                [character]
+                format="!>"
+        });
+        context.shell = Shell::Ksh;
+
+        let expected = String::from("\n!!>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn ksh_escapes_command_substitution() {
+        let mut context = default_context().set_config(toml::toml! {
+                format="${env_var.branch}"
+                [env_var.branch]
+                variable="BRANCH"
+                format="$env_value"
+        });
+        context.shell = Shell::Ksh;
+        context
+            .env
+            .insert("BRANCH", "$(touch${IFS}pwned)`id`".to_string());
+
+        let expected = String::from("\n\\$(touch\\${IFS}pwned)\\`id\\`");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn osh_escapes_command_substitution() {
+        let mut context = default_context().set_config(toml::toml! {
+                format="${env_var.branch}"
+                [env_var.branch]
+                variable="BRANCH"
+                format="$env_value"
+        });
+        context.shell = Shell::Osh;
+        context
+            .env
+            .insert("BRANCH", "$(touch${IFS}pwned)".to_string());
+
+        let expected = String::from("\n\\$(touch\\${IFS}pwned)");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn transient_prompt() {
+        let mut context = default_context().set_config(toml::toml! {
+                format="$directory$character"
+                transient_format="$character"
+                [character]
+                format=">>"
+        });
+        context.target = Target::Transient;
+
+        let expected = String::from(">>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    fn narrow_directory_context(width: usize) -> Context<'static> {
+        narrow_directory_context_with_config(
+            width,
+            toml::toml! {
+                add_newline = false
+                format = "$directory$character"
+                [directory]
+                format = "$path"
+                truncation_length = 0
+                truncate_to_width = true
+                [character]
+                format = ">"
+            },
+        )
+    }
+
+    fn narrow_directory_context_with_config(width: usize, config: toml::Table) -> Context<'static> {
+        let mut context = default_context().set_config(config);
+        let dir = PathBuf::from("/home/user/projects/starship/src/modules/utils");
+        context.env.insert("HOME", "/home/user".to_string());
+        context.current_dir.clone_from(&dir);
+        context.logical_dir = dir;
+        context.width = width;
+        context
+    }
+
+    #[test]
+    fn directory_fits_terminal_width() {
+        let context = narrow_directory_context(80);
+
+        let expected = String::from("~/projects/starship/src/modules/utils>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn directory_abbreviated_to_fit_terminal_width() {
+        let context = narrow_directory_context(20);
+
+        let expected = String::from("~/p/s/s/m/utils>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn directory_elided_to_fit_terminal_width() {
+        let context = narrow_directory_context(12);
+
+        let expected = String::from("~/…/m/utils>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn directory_fit_ignores_other_lines() {
+        let context = narrow_directory_context_with_config(
+            40,
+            toml::toml! {
+                add_newline = false
+                format = "$character$line_break$directory"
+                [directory]
+                format = "$path"
+                truncation_length = 0
+                truncate_to_width = true
+                [character]
+                format = ">>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>"
+            },
+        );
+
+        let expected = format!("{}\n~/projects/starship/src/modules/utils", ">".repeat(50));
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    fn styled(text: &str, style: AnsiStyle) -> Vec<Segment> {
+        Segment::from_text(Some(style.into()), text)
+    }
+
+    fn separated(
+        segments: Vec<Vec<Segment>>,
+        config: &SeparatorConfig,
+        right: bool,
+    ) -> Vec<(String, Option<AnsiStyle>)> {
+        insert_separators(config, segments.concat(), right)
+            .iter()
+            .filter(|segment| !segment.value().is_empty())
+            .map(|segment| (segment.value().to_string(), segment.style()))
+            .collect()
+    }
+
+    #[test]
+    fn separators_between_modules() {
+        let config = SeparatorConfig::default();
+        let actual = separated(
+            vec![
+                vec![Segment::Boundary],
+                styled("a", Color::Black.on(Color::Blue)),
+                vec![Segment::Boundary],
+                Segment::from_text(None, ""),
+                vec![Segment::Boundary],
+                styled("b", Color::White.on(Color::Blue)),
+                vec![Segment::Boundary],
+                styled("c", Color::White.on(Color::Red)),
+                vec![Segment::Boundary],
+                Segment::from_text(None, " d"),
+            ],
+            &config,
+            false,
+        );
+        let expected = vec![
+            ("a".to_string(), Some(Color::Black.on(Color::Blue))),
+            ("\u{e0b1}".to_string(), Some(Color::Black.on(Color::Blue))),
+            ("b".to_string(), Some(Color::White.on(Color::Blue))),
+            ("\u{e0b0}".to_string(), Some(Color::Blue.on(Color::Red))),
+            ("c".to_string(), Some(Color::White.on(Color::Red))),
+            ("\u{e0b0}".to_string(), Some(Color::Red.normal())),
+            (" d".to_string(), None),
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn separators_point_left_after_fill() {
+        let config = SeparatorConfig::default();
+        let segments = vec![
+            vec![Segment::Boundary],
+            styled("a", Color::Black.on(Color::Blue)),
+            vec![Segment::Boundary, Segment::fill(None, ".")],
+            vec![Segment::Boundary],
+            styled("b", Color::Black.on(Color::Red)),
+            vec![Segment::Boundary],
+            styled("c", Color::Black.on(Color::Green)),
+            vec![Segment::Boundary, Segment::LineTerm],
+            vec![Segment::Boundary],
+            styled("d", Color::Black.on(Color::Blue)),
+        ];
+
+        let actual = separated(segments.clone(), &config, false);
+        let expected = vec![
+            ("a".to_string(), Some(Color::Black.on(Color::Blue))),
+            ("\u{e0b0}".to_string(), Some(Color::Blue.normal())),
+            (".".to_string(), None),
+            ("\u{e0b2}".to_string(), Some(Color::Red.normal())),
+            ("b".to_string(), Some(Color::Black.on(Color::Red))),
+            ("\u{e0b2}".to_string(), Some(Color::Green.on(Color::Red))),
+            ("c".to_string(), Some(Color::Black.on(Color::Green))),
+            ("\n".to_string(), None),
+            ("d".to_string(), Some(Color::Black.on(Color::Blue))),
+            ("\u{e0b0}".to_string(), Some(Color::Blue.normal())),
+        ];
+        assert_eq!(expected, actual);
+
+        // The right prompt points left from the start
+        let actual = separated(segments[..2].to_vec(), &config, true);
+        let expected = vec![
+            ("\u{e0b2}".to_string(), Some(Color::Blue.normal())),
+            ("a".to_string(), Some(Color::Black.on(Color::Blue))),
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn separators_with_caps() {
+        let config = SeparatorConfig {
+            start: "(".to_string(),
+            end: ")".to_string(),
+            ..SeparatorConfig::default()
+        };
+        let actual = separated(
+            vec![
+                vec![Segment::Boundary],
+                styled("a", Color::Black.on(Color::Blue)),
+                vec![Segment::Boundary],
+                Segment::from_text(None, " > "),
+            ],
+            &config,
+            false,
+        );
+        let expected = vec![
+            ("(".to_string(), Some(Color::Blue.normal())),
+            ("a".to_string(), Some(Color::Black.on(Color::Blue))),
+            (")".to_string(), Some(Color::Blue.normal())),
+            (" > ".to_string(), None),
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn separators_skip_empty_modules() {
+        let mut context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = "${env_var.first}${env_var.missing}${env_var.second}$character"
+            [separator]
+            disabled = false
+            left = ">"
+            [env_var.first]
+            variable = "FIRST"
+            format = "[$env_value](bg:blue)"
+            [env_var.missing]
+            variable = "MISSING"
+            format = "[$env_value](bg:yellow)"
+            [env_var.second]
+            variable = "SECOND"
+            format = "[$env_value](bg:red)"
+            [character]
+            format = " >"
+        });
+        context.env.insert("FIRST", "a".to_string());
+        context.env.insert("SECOND", "b".to_string());
+
+        let actual = get_prompt(&context);
+        assert_eq!("a>b> >", ansi_strip().replace_all(&actual, ""));
+    }
+
+    fn responsive_context(width: usize, config: toml::Table) -> Context<'static> {
+        let mut context = default_context().set_config(config);
+        context.env.insert("FIRST", "aaaa ".to_string());
+        context.env.insert("SECOND", "bbbb ".to_string());
+        context.width = width;
+        context
+    }
+
+    #[test]
+    fn responsive_layout_disabled() {
+        let context = responsive_context(
+            10,
+            toml::toml! {
+                add_newline = false
+                format = "${env_var.first}${env_var.second}$character"
+                [env_var.first]
+                variable = "FIRST"
+                format = "$env_value"
+                [env_var.second]
+                variable = "SECOND"
+                format = "$env_value"
+                [character]
+                format = ">"
+            },
+        );
+
+        let expected = String::from("aaaa bbbb >");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn responsive_layout_drops_lowest_priority() {
+        let context = responsive_context(
+            10,
+            toml::toml! {
+                add_newline = false
+                responsive_layout = true
+                format = "${env_var.first}${env_var.second}$character"
+                [layout_priorities]
+                "env_var.first" = 10
+                [env_var.first]
+                variable = "FIRST"
+                format = "$env_value"
+                [env_var.second]
+                variable = "SECOND"
+                format = "$env_value"
+                [character]
+                format = ">"
+            },
+        );
+
+        let expected = String::from("bbbb >");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn responsive_layout_prefers_compact_format() {
+        let context = responsive_context(
+            10,
+            toml::toml! {
+                add_newline = false
+                responsive_layout = true
+                format = "$shlvl${env_var.second}$character"
+                [layout_priorities]
+                shlvl = 10
+                [shlvl]
+                disabled = false
+                threshold = 0
+                format = "level $shlvl "
+                format_compact = "$shlvl"
+                [env_var.second]
+                variable = "SECOND"
+                format = "$env_value"
+                [character]
+                format = ">"
+            },
+        );
+        let mut context = context;
+        context.env.insert("SHLVL", "2".to_string());
+
+        let expected = String::from("2bbbb >");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn responsive_layout_compacts_env_var_modules() {
+        let context = responsive_context(
+            10,
+            toml::toml! {
+                add_newline = false
+                responsive_layout = true
+                format = "${env_var.first}${env_var.second}$character"
+                [layout_priorities]
+                "env_var.first" = 10
+                [env_var.first]
+                variable = "FIRST"
+                format = "$env_value"
+                format_compact = "a "
+                [env_var.second]
+                variable = "SECOND"
+                format = "$env_value"
+                [character]
+                format = ">"
+            },
+        );
+
+        let expected = String::from("a bbbb >");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn responsive_layout_keeps_required_modules() {
+        let context = responsive_context(
+            3,
+            toml::toml! {
+                add_newline = false
+                responsive_layout = true
+                format = "$character"
+                [character]
+                format = ">>>>>"
+            },
+        );
+
+        let expected = String::from(">>>>>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn responsive_layout_ignores_fitting_lines() {
+        let context = responsive_context(
+            10,
+            toml::toml! {
+                add_newline = false
+                responsive_layout = true
+                format = "${env_var.first}$line_break${env_var.second}$character"
+                [layout_priorities]
+                "env_var.first" = 10
+                [env_var.first]
+                variable = "FIRST"
+                format = "$env_value"
+                [env_var.second]
+                variable = "SECOND"
+                format = "$env_value$env_value"
+                [character]
+                format = ">"
+            },
+        );
+
+        let expected = String::from("aaaa \n>");
+        let actual = get_prompt(&context);
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn preset_list_returns_one_or_more_items() {
         assert!(preset_list().lines().count() > 0);
@@ -682,6 +1831,31 @@ mod test {
         dir.close()
     }
 
+    #[test]
+    fn find_presets_in_dirs() -> std::io::Result<()> {
+        let user = tempfile::tempdir()?;
+        let team = tempfile::tempdir()?;
+        std::fs::write(user.path().join("mine.toml"), "")?;
+        std::fs::write(user.path().join("README.md"), "")?;
+        std::fs::write(team.path().join("company.toml"), "")?;
+        std::fs::write(team.path().join("mine.toml"), "")?;
+        std::fs::write(team.path().join("nerd-font-symbols.toml"), "")?;
+
+        let dirs = [
+            user.path().to_path_buf(),
+            user.path().join("missing"),
+            team.path().to_path_buf(),
+        ];
+        let expected = vec![
+            ("mine".to_string(), user.path().join("mine.toml")),
+            ("company".to_string(), team.path().join("company.toml")),
+        ];
+        assert_eq!(find_presets(&dirs), expected);
+
+        user.close()?;
+        team.close()
+    }
+
     #[test]
     #[cfg(feature = "config-schema")]
     fn print_schema_does_not_panic() {
@@ -92,6 +92,9 @@ pub enum Segment {
     Text(TextSegment),
     Fill(FillSegment),
     LineTerm,
+    /// Marks where a module starts in the root prompt, for separators to be inserted between
+    /// modules. Removed before the prompt is printed.
+    Boundary,
 }
 
 impl Segment {
@@ -128,7 +131,7 @@ impl Segment {
         match self {
             Self::Fill(fs) => fs.style.map(|cs| cs.to_ansi_style(None)),
             Self::Text(ts) => ts.style.map(|cs| cs.to_ansi_style(None)),
-            Self::LineTerm => None,
+            Self::LineTerm | Self::Boundary => None,
         }
     }
 
@@ -144,7 +147,7 @@ impl Segment {
                     ts.style = style;
                 }
             }
-            Self::LineTerm => {}
+            Self::LineTerm | Self::Boundary => {}
         }
     }
 
@@ -153,6 +156,7 @@ impl Segment {
             Self::Fill(fs) => &fs.value,
             Self::Text(ts) => &ts.value,
             Self::LineTerm => LINE_TERMINATOR_STRING,
+            Self::Boundary => "",
         }
     }
 
@@ -162,6 +166,7 @@ impl Segment {
             Self::Fill(fs) => fs.ansi_string(None, prev),
             Self::Text(ts) => ts.ansi_string(prev),
             Self::LineTerm => AnsiString::from(LINE_TERMINATOR_STRING),
+            Self::Boundary => AnsiString::from(""),
         }
     }
 
@@ -169,7 +174,7 @@ impl Segment {
         match self {
             Self::Fill(fs) => fs.value.width_graphemes(),
             Self::Text(ts) => ts.value.width_graphemes(),
-            Self::LineTerm => 0,
+            Self::LineTerm | Self::Boundary => 0,
         }
     }
 }
@@ -3,7 +3,7 @@ use serde::de::{
     Deserializer, Error, IntoDeserializer, Visitor,
     value::{Error as ValueError, MapDeserializer, SeqDeserializer},
 };
-use std::{cmp::Ordering, fmt};
+use std::{cell::RefCell, cmp::Ordering, fmt};
 use toml::Value;
 
 /// A `toml::Value` that borrows its contents instead of owning them.
@@ -38,6 +38,17 @@ impl<'de> From<&'de toml::Table> for ValueRef<'de> {
     }
 }
 
+/// A key that is not part of the config it was found in, with the closest known key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// Dotted path of the key, e.g. `git_status.ahead_fromat`
+    pub path: String,
+    pub suggestion: Option<&'static str>,
+}
+
+/// Where a `ValueDeserializer` collects unknown keys instead of failing on the first one.
+pub type UnknownKeys = RefCell<Vec<UnknownKey>>;
+
 /// A helper struct for deserializing a TOML value references with serde.
 /// This also prints a warning and suggestions if a key is unknown.
 #[derive(Debug)]
@@ -46,6 +57,8 @@ pub struct ValueDeserializer<'de> {
     info: Option<StructInfo>,
     current_key: Option<&'de str>,
     error_on_ignored: bool,
+    /// Collects unknown keys along with the path of the current value, if set
+    unknown_keys: Option<(&'de UnknownKeys, String)>,
 }
 
 /// When deserializing a struct, this struct stores information about the struct.
@@ -62,20 +75,45 @@ impl<'de> ValueDeserializer<'de> {
             info: None,
             current_key: None,
             error_on_ignored: true,
+            unknown_keys: None,
         }
     }
 
-    fn with_info(
-        value: ValueRef<'de>,
-        info: Option<StructInfo>,
-        current_key: &'de str,
-        ignored: bool,
-    ) -> Self {
+    /// Deserializer for the value at `key` of the table of `self`.
+    fn with_info(&self, value: &'de Value, current_key: &'de str) -> Self {
         Self {
-            value,
-            info,
+            value: value.into(),
+            info: self.info,
             current_key: Some(current_key),
-            error_on_ignored: ignored,
+            error_on_ignored: self.error_on_ignored,
+            unknown_keys: self.unknown_keys.as_ref().map(|(unknown_keys, path)| {
+                let path = if path.is_empty() {
+                    current_key.to_string()
+                } else {
+                    format!("{path}.{current_key}")
+                };
+                (*unknown_keys, path)
+            }),
+        }
+    }
+
+    /// Deserializer for the value at `index` of the array of `self`.
+    fn with_index(&self, value: &'de Value, index: usize) -> Self {
+        Self {
+            unknown_keys: self
+                .unknown_keys
+                .as_ref()
+                .map(|(unknown_keys, path)| (*unknown_keys, format!("{path}[{index}]"))),
+            ..Self::new(value)
+        }
+    }
+
+    /// Collect unknown keys into `unknown_keys` and ignore them, instead of failing on the first
+    /// one. `path` is the dotted path of the value, used to report the keys.
+    pub fn with_unknown_keys(self, unknown_keys: &'de UnknownKeys, path: &str) -> Self {
+        Self {
+            unknown_keys: Some((unknown_keys, path.to_string())),
+            ..self
         }
     }
 
@@ -123,21 +161,18 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             ValueRef::Float(f) => visitor.visit_f64(f),
             ValueRef::String(s) => visitor.visit_borrowed_str(s),
             ValueRef::Array(a) => {
-                let seq = SeqDeserializer::new(a.iter().map(ValueDeserializer::new));
+                let seq = SeqDeserializer::new(
+                    a.iter()
+                        .enumerate()
+                        .map(|(index, v)| self.with_index(v, index)),
+                );
                 seq.deserialize_seq(visitor)
             }
             ValueRef::Table(t) => {
-                let map = MapDeserializer::new(t.iter().map(|(k, v)| {
-                    (
-                        k.as_str(),
-                        ValueDeserializer::with_info(
-                            v.into(),
-                            self.info,
-                            k.as_str(),
-                            self.error_on_ignored,
-                        ),
-                    )
-                }));
+                let map = MapDeserializer::new(
+                    t.iter()
+                        .map(|(k, v)| (k.as_str(), self.with_info(v, k.as_str()))),
+                );
                 map.deserialize_map(visitor)
             }
             ValueRef::Datetime(d) => visitor.visit_string(d.to_string()),
@@ -185,20 +220,38 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             return visitor.visit_none();
         }
 
-        let did_you_mean = match (self.current_key, self.info) {
-            (Some(key), Some(StructInfo { fields, .. })) => fields
-                .iter()
-                .filter_map(|field| {
-                    let score = strsim::jaro_winkler(key, field);
-                    (score > 0.8).then_some((score, field))
-                })
-                .max_by(|(score_a, _field_a), (score_b, _field_b)| {
-                    score_a.partial_cmp(score_b).unwrap_or(Ordering::Equal)
+        // Every module accepts a `format_compact`, which is handled by the prompt layout
+        if self.current_key == Some("format_compact")
+            && self
+                .info
+                .is_some_and(|StructInfo { fields, .. }| fields.contains(&"format"))
+        {
+            return visitor.visit_none();
+        }
+
+        let suggestion = match (self.current_key, self.info) {
+            // Top-level tables configure modules, so suggest those as well
+            (
+                Some(key),
+                Some(StructInfo {
+                    fields,
+                    name: "StarshipRootConfig",
                 }),
+            ) => closest_match(key, fields.iter().chain(ALL_MODULES)),
+            (Some(key), Some(StructInfo { fields, .. })) => closest_match(key, fields),
             _ => None,
         };
-        let did_you_mean = did_you_mean
-            .map(|(_score, field)| format!(" (Did you mean '{field}'?)"))
+
+        if let Some((unknown_keys, path)) = &self.unknown_keys {
+            unknown_keys.borrow_mut().push(UnknownKey {
+                path: path.clone(),
+                suggestion,
+            });
+            return visitor.visit_none();
+        }
+
+        let did_you_mean = suggestion
+            .map(|field| format!(" (Did you mean '{field}'?)"))
             .unwrap_or_default();
 
         Err(self.error(format!("Unknown key{did_you_mean}")))
@@ -222,6 +275,23 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
     }
 }
 
+/// Returns the candidate most similar to `key`, if any is similar enough to be a typo of it
+pub fn closest_match<'a>(
+    key: &str,
+    candidates: impl IntoIterator<Item = &'a &'static str>,
+) -> Option<&'static str> {
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let score = strsim::jaro_winkler(key, candidate);
+            (score > 0.8).then_some((score, *candidate))
+        })
+        .max_by(|(score_a, _field_a), (score_b, _field_b)| {
+            score_a.partial_cmp(score_b).unwrap_or(Ordering::Equal)
+        })
+        .map(|(_score, candidate)| candidate)
+}
+
 #[cfg(test)]
 mod test {
     use crate::configs::StarshipRootConfig;
@@ -371,6 +441,83 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_deserialize_format_compact() {
+        let value = toml::toml! {
+            format = "$foo "
+            format_compact = "$foo"
+        };
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Sample {
+            format: String,
+        }
+
+        let deserializer = ValueDeserializer::new(&value);
+        let result = Sample::deserialize(deserializer).unwrap();
+        assert_eq!(result.format, "$foo ");
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct NoFormat {
+            style: Option<String>,
+        }
+
+        let value = toml::toml! {
+            format_compact = "$foo"
+        };
+        let deserializer = ValueDeserializer::new(&value);
+        let result = NoFormat::deserialize(deserializer).unwrap_err();
+        assert_eq!(
+            format!("{result}"),
+            "Error in 'NoFormat' at 'format_compact': Unknown key"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_collect_unknown() {
+        let value = toml::toml! {
+            food = "bar"
+            [nested]
+            bar = 1
+            [[list]]
+            qux = 2
+        };
+
+        #[derive(Debug, Default, Deserialize)]
+        #[serde(default)]
+        #[allow(dead_code)]
+        struct Item {
+            bar: i64,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Sample {
+            foo: Option<String>,
+            nested: Item,
+            list: Vec<Item>,
+        }
+
+        let unknown_keys = UnknownKeys::default();
+        let deserializer = ValueDeserializer::new(&value).with_unknown_keys(&unknown_keys, "");
+        assert!(Sample::deserialize(deserializer).is_ok());
+        assert_eq!(
+            unknown_keys.into_inner(),
+            [
+                UnknownKey {
+                    path: "food".to_string(),
+                    suggestion: Some("foo"),
+                },
+                UnknownKey {
+                    path: "list[0].qux".to_string(),
+                    suggestion: None,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_deserialize_unknown_root_config() {
         let value = toml::toml! {
@@ -0,0 +1,749 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::context::Context;
+use crate::logger;
+use crate::print::{self, UnicodeWidthGraphemes};
+
+/// Name of the file in the cache directory that stores module runtimes
+const RUNTIMES_FILE: &str = "module_runtimes.json";
+
+/// Runtimes of slow modules, keyed by repository (or directory) and module name
+///
+/// Only modules that exceeded `auto_disable_threshold` on their last render are kept,
+/// so the store stays small even when the prompt is used in many directories.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RuntimeStore {
+    dirs: BTreeMap<String, BTreeMap<String, ModuleRuntime>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModuleRuntime {
+    /// Runtime of the last render, in milliseconds
+    pub last_ms: u64,
+    /// Number of consecutive renders that exceeded the threshold
+    pub strikes: u32,
+    /// Whether the module has been disabled for this directory
+    pub disabled: bool,
+}
+
+impl RuntimeStore {
+    /// Loads the store from `path`, starting with an empty store if it is missing or invalid
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|error| {
+                log::debug!("Unable to parse module runtimes {path:?}: {error}");
+                Self::default()
+            }),
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    log::debug!("Unable to read module runtimes {path:?}: {error}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the store to `path`, replacing the file atomically
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        let tmp_path = path.with_extension(format!("json.{}", std::process::id()));
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Returns the recorded runtimes of the modules in `dir`
+    pub fn modules(&self, dir: &str) -> Option<&BTreeMap<String, ModuleRuntime>> {
+        self.dirs.get(dir)
+    }
+
+    /// Returns whether `module` has been disabled for `dir`
+    pub fn is_disabled(&self, dir: &str, module: &str) -> bool {
+        self.dirs
+            .get(dir)
+            .and_then(|modules| modules.get(module))
+            .is_some_and(|runtime| runtime.disabled)
+    }
+
+    /// Records a render of `module` in `dir`
+    ///
+    /// The module is disabled once `max_strikes` consecutive renders took at least
+    /// `threshold`. Returns whether the store was changed.
+    pub fn record(
+        &mut self,
+        dir: &str,
+        module: &str,
+        elapsed: Duration,
+        threshold: Duration,
+        max_strikes: u32,
+    ) -> bool {
+        if elapsed < threshold {
+            // Fast renders reset the strikes of a module
+            let Some(modules) = self.dirs.get_mut(dir) else {
+                return false;
+            };
+            if modules.remove(module).is_none() {
+                return false;
+            }
+            if modules.is_empty() {
+                self.dirs.remove(dir);
+            }
+            return true;
+        }
+
+        let runtime = self
+            .dirs
+            .entry(dir.to_string())
+            .or_default()
+            .entry(module.to_string())
+            .or_default();
+        runtime.last_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        runtime.strikes = runtime.strikes.saturating_add(1);
+        runtime.disabled = runtime.strikes >= max_strikes.max(1);
+        true
+    }
+
+    /// Replaces the recorded runtimes of `dir` with the ones of `other`
+    pub fn merge_dir(&mut self, dir: &str, other: &Self) {
+        match other.dirs.get(dir) {
+            Some(modules) => self.dirs.insert(dir.to_string(), modules.clone()),
+            None => self.dirs.remove(dir),
+        };
+    }
+
+    /// Forgets the recorded runtimes of `dir`, or of all directories if `dir` is `None`.
+    /// Returns the names of the modules that were disabled.
+    pub fn reset(&mut self, dir: Option<&str>) -> Vec<String> {
+        let removed = match dir {
+            Some(dir) => self.dirs.remove(dir).into_iter().collect(),
+            None => std::mem::take(&mut self.dirs)
+                .into_values()
+                .collect::<Vec<_>>(),
+        };
+        removed
+            .into_iter()
+            .flatten()
+            .filter(|(_, runtime)| runtime.disabled)
+            .map(|(module, _)| module)
+            .collect()
+    }
+}
+
+/// Module runtimes of the directory the prompt is rendered in
+#[derive(Debug)]
+pub struct RuntimeTracker {
+    path: PathBuf,
+    key: String,
+    store: RuntimeStore,
+    changed: bool,
+}
+
+impl RuntimeTracker {
+    pub fn load(context: &Context) -> Self {
+        let path = runtimes_path(context);
+        let store = RuntimeStore::load(&path);
+        Self {
+            path,
+            key: runtimes_key(context),
+            store,
+            changed: false,
+        }
+    }
+
+    pub fn is_disabled(&self, module: &str) -> bool {
+        self.store.is_disabled(&self.key, module)
+    }
+
+    /// Returns the names of the modules disabled in the current directory
+    pub fn disabled_modules(&self) -> Vec<&str> {
+        self.store
+            .modules(&self.key)
+            .into_iter()
+            .flatten()
+            .filter(|(_, runtime)| runtime.disabled)
+            .map(|(module, _)| module.as_str())
+            .collect()
+    }
+
+    pub fn record(&mut self, module: &str, elapsed: Duration, threshold: Duration, strikes: u32) {
+        self.changed |= self
+            .store
+            .record(&self.key, module, elapsed, threshold, strikes);
+    }
+
+    /// Writes the runtimes of the current directory back to the store, if they changed
+    pub fn save(&self) {
+        if !self.changed {
+            return;
+        }
+        // Reload the store to keep the runtimes other shells recorded in the meantime
+        let mut store = RuntimeStore::load(&self.path);
+        store.merge_dir(&self.key, &self.store);
+        if let Err(error) = store.save(&self.path) {
+            log::warn!(
+                "Unable to write module runtimes to {}: {error}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// Returns the directory the module runtimes and the timings log are stored in
+pub fn cache_dir(context: &Context) -> PathBuf {
+    context
+        .get_env_os("STARSHIP_CACHE")
+        .map(PathBuf::from)
+        .unwrap_or_else(logger::get_log_dir)
+}
+
+/// Returns the path of the module runtime store
+pub fn runtimes_path(context: &Context) -> PathBuf {
+    cache_dir(context).join(RUNTIMES_FILE)
+}
+
+/// Returns the key under which module runtimes of the current directory are stored
+///
+/// Runtimes are shared by the whole repository when the directory is inside one.
+pub fn runtimes_key(context: &Context) -> String {
+    context
+        .get_repo()
+        .ok()
+        .and_then(|repo| repo.workdir.as_deref())
+        .unwrap_or(&context.current_dir)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Re-enables the modules that were disabled for being slow, either in the current
+/// directory or everywhere
+pub fn reset(context: &Context, all: bool) {
+    let path = runtimes_path(context);
+    let key = runtimes_key(context);
+    let mut store = RuntimeStore::load(&path);
+    let modules = store.reset((!all).then_some(key.as_str()));
+
+    if let Err(error) = store.save(&path) {
+        eprintln!(
+            "Unable to write module runtimes to {}: {error}",
+            path.display()
+        );
+        std::process::exit(1);
+    }
+
+    let location = if all {
+        "any directory".to_string()
+    } else {
+        key
+    };
+    if modules.is_empty() {
+        println!("No modules were disabled in {location}");
+    } else {
+        println!("Re-enabled in {location}: {}", modules.join(", "));
+    }
+}
+
+/// A timed section of a prompt render, e.g. a module or an external command
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub category: &'static str,
+    pub name: String,
+    /// 0 for the main thread, or 1 + the index of the rayon worker thread
+    pub thread: usize,
+    /// Offset of the start of the span from the start of the recording
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Collects the spans recorded while rendering a prompt
+#[derive(Debug)]
+pub struct SpanRecorder {
+    origin: Instant,
+    spans: Mutex<Vec<Span>>,
+}
+
+impl Default for SpanRecorder {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            spans: Mutex::default(),
+        }
+    }
+}
+
+impl SpanRecorder {
+    /// Records a span that started at `start` and ends now
+    pub fn record(&self, category: &'static str, name: String, start: Instant) {
+        let span = Span {
+            category,
+            name,
+            thread: rayon::current_thread_index().map_or(0, |index| index + 1),
+            start: start.saturating_duration_since(self.origin),
+            duration: start.elapsed(),
+        };
+        self.spans.lock().unwrap().push(span);
+    }
+
+    /// Returns the recorded spans, ordered by their start
+    pub fn spans(&self) -> Vec<Span> {
+        let mut spans = self.spans.lock().unwrap().clone();
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+}
+
+/// Name of the file in the cache directory that prompt timings are logged to
+const TIMINGS_LOG_FILE: &str = "timings.log";
+
+/// Size after which the oldest half of the timings log is dropped
+const TIMINGS_LOG_MAX_SIZE: u64 = 1024 * 1024;
+
+/// Timings of a single prompt, stored as one line of the timings log
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimingsEntry {
+    /// Unix timestamp of the prompt, in seconds
+    pub time: u64,
+    pub dir: String,
+    pub shell: String,
+    /// Time taken to render the whole prompt, in milliseconds
+    pub total_ms: f64,
+    /// Time taken to render each module, in milliseconds
+    pub modules: BTreeMap<String, f64>,
+}
+
+impl TimingsEntry {
+    pub fn new(context: &Context, total: Duration, spans: &[Span]) -> Self {
+        let modules = spans
+            .iter()
+            .filter(|span| span.category == "module")
+            .map(|span| (span.name.clone(), as_millis(span.duration)))
+            .collect();
+        Self {
+            time: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs()),
+            dir: runtimes_key(context),
+            shell: format!("{:?}", context.shell).to_lowercase(),
+            total_ms: as_millis(total),
+            modules,
+        }
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+/// Returns the path of the timings log
+pub fn timings_log_path(context: &Context) -> PathBuf {
+    cache_dir(context).join(TIMINGS_LOG_FILE)
+}
+
+/// Appends `entry` to the timings log at `path`, dropping the oldest entries once it is too big
+pub fn append_timings_log(path: &Path, entry: &TimingsEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+
+    if file.metadata()?.len() > TIMINGS_LOG_MAX_SIZE {
+        let content = fs::read_to_string(path)?;
+        let lines = content.lines().collect::<Vec<_>>();
+        let kept = lines[lines.len() / 2..].join("\n");
+        let tmp_path = path.with_extension(format!("log.{}", std::process::id()));
+        fs::write(&tmp_path, kept + "\n")?;
+        fs::rename(&tmp_path, path)?;
+    }
+    Ok(())
+}
+
+/// Reads the entries of the timings log, skipping lines that can't be parsed
+pub fn read_timings_log(path: &Path) -> io::Result<Vec<TimingsEntry>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Percentiles of the samples of one module, directory or shell
+#[derive(Debug, PartialEq)]
+pub struct ReportRow {
+    pub name: String,
+    pub samples: usize,
+    pub p50: f64,
+    pub p95: f64,
+}
+
+/// Returns the `p`th percentile of sorted `samples`, using the nearest-rank method
+fn percentile(samples: &[f64], p: usize) -> f64 {
+    let rank = (samples.len() * p).div_ceil(100).max(1);
+    samples[rank - 1]
+}
+
+/// Summarizes the samples of each group, slowest p95 first
+fn report_rows(groups: BTreeMap<String, Vec<f64>>) -> Vec<ReportRow> {
+    let mut rows = groups
+        .into_iter()
+        .map(|(name, mut samples)| {
+            samples.sort_by(f64::total_cmp);
+            ReportRow {
+                p50: percentile(&samples, 50),
+                p95: percentile(&samples, 95),
+                samples: samples.len(),
+                name,
+            }
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by(|a, b| b.p95.total_cmp(&a.p95).then_with(|| a.name.cmp(&b.name)));
+    rows
+}
+
+/// Returns the report rows per module, per directory and per shell
+pub fn report_tables(entries: &[TimingsEntry]) -> [Vec<ReportRow>; 3] {
+    let mut modules: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut dirs: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut shells: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+
+    for entry in entries {
+        for (module, ms) in &entry.modules {
+            modules.entry(module.clone()).or_default().push(*ms);
+        }
+        dirs.entry(entry.dir.clone())
+            .or_default()
+            .push(entry.total_ms);
+        shells
+            .entry(entry.shell.clone())
+            .or_default()
+            .push(entry.total_ms);
+    }
+
+    [report_rows(modules), report_rows(dirs), report_rows(shells)]
+}
+
+/// Prints p50 and p95 render times from the timings log
+pub fn report(context: &Context) {
+    let path = timings_log_path(context);
+    let entries = match read_timings_log(&path) {
+        Ok(entries) if !entries.is_empty() => entries,
+        Ok(_) | Err(_) => {
+            println!(
+                "No timings were recorded in {}. Set `record_timings = true` in your config to record them.",
+                path.display()
+            );
+            return;
+        }
+    };
+
+    println!("\n Prompt timings from {} prompts:", entries.len());
+    let [modules, dirs, shells] = report_tables(&entries);
+    for (title, rows) in [("Module", modules), ("Directory", dirs), ("Shell", shells)] {
+        print_report_table(title, &rows);
+    }
+}
+
+fn print_report_table(title: &str, rows: &[ReportRow]) {
+    let format_ms = |ms: f64| format!("{ms:.1}ms");
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.width_graphemes())
+        .chain([title.len()])
+        .max()
+        .unwrap_or(0);
+
+    println!();
+    println!(
+        " {title}{}  {:>8}  {:>10}  {:>10}",
+        " ".repeat(name_width - title.len()),
+        "Samples",
+        "p50",
+        "p95"
+    );
+    for row in rows {
+        println!(
+            " {}{}  {:>8}  {:>10}  {:>10}",
+            row.name,
+            " ".repeat(name_width - row.name.width_graphemes()),
+            row.samples,
+            format_ms(row.p50),
+            format_ms(row.p95)
+        );
+    }
+}
+
+/// Converts spans to the Chrome trace event format
+pub fn chrome_trace(spans: &[Span]) -> serde_json::Value {
+    let events = spans
+        .iter()
+        .map(|span| {
+            serde_json::json!({
+                "name": span.name,
+                "cat": span.category,
+                "ph": "X",
+                "ts": span.start.as_micros(),
+                "dur": span.duration.as_micros(),
+                "pid": std::process::id(),
+                "tid": span.thread,
+            })
+        })
+        .chain(
+            spans
+                .iter()
+                .map(|span| span.thread)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .map(|thread| {
+                    let name = match thread {
+                        0 => "main".to_string(),
+                        thread => format!("rayon worker {}", thread - 1),
+                    };
+                    serde_json::json!({
+                        "name": "thread_name",
+                        "ph": "M",
+                        "pid": std::process::id(),
+                        "tid": thread,
+                        "args": { "name": name },
+                    })
+                }),
+        )
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+    })
+}
+
+/// Renders the prompt and writes a Chrome trace of it to `path`
+pub fn trace(context: &Context, path: &Path) {
+    let recorder = context.record_spans();
+    let start = Instant::now();
+    print::get_prompt(context);
+    recorder.record("prompt", "prompt".to_string(), start);
+
+    let spans = recorder.spans();
+    let trace = chrome_trace(&spans);
+    if let Err(error) = fs::write(path, trace.to_string()) {
+        eprintln!("Unable to write trace to {}: {error}", path.display());
+        std::process::exit(1);
+    }
+    println!(
+        "Wrote {} spans to {}. Open it in chrome://tracing or https://ui.perfetto.dev",
+        spans.len(),
+        path.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn disables_module_after_repeated_slow_renders() {
+        let mut store = RuntimeStore::default();
+        let slow = Duration::from_millis(250);
+
+        assert!(store.record("/repo", "git_status", slow, THRESHOLD, 3));
+        assert!(store.record("/repo", "git_status", slow, THRESHOLD, 3));
+        assert!(!store.is_disabled("/repo", "git_status"));
+        assert!(store.record("/repo", "git_status", slow, THRESHOLD, 3));
+        assert!(store.is_disabled("/repo", "git_status"));
+        assert!(!store.is_disabled("/other", "git_status"));
+        assert_eq!(
+            store.modules("/repo").unwrap()["git_status"],
+            ModuleRuntime {
+                last_ms: 250,
+                strikes: 3,
+                disabled: true,
+            }
+        );
+    }
+
+    #[test]
+    fn fast_render_resets_strikes() {
+        let mut store = RuntimeStore::default();
+        let slow = Duration::from_millis(250);
+        let fast = Duration::from_millis(5);
+
+        store.record("/repo", "rust", slow, THRESHOLD, 2);
+        assert!(store.record("/repo", "rust", fast, THRESHOLD, 2));
+        assert_eq!(store, RuntimeStore::default());
+        assert!(!store.record("/repo", "rust", fast, THRESHOLD, 2));
+        store.record("/repo", "rust", slow, THRESHOLD, 2);
+        assert!(!store.is_disabled("/repo", "rust"));
+    }
+
+    #[test]
+    fn reset_directory_or_all() {
+        let mut store = RuntimeStore::default();
+        let slow = Duration::from_millis(250);
+        store.record("/a", "git_status", slow, THRESHOLD, 1);
+        store.record("/a", "nodejs", slow, THRESHOLD, 2);
+        store.record("/b", "python", slow, THRESHOLD, 1);
+
+        assert_eq!(store.reset(Some("/a")), vec!["git_status".to_string()]);
+        assert!(store.modules("/a").is_none());
+        assert!(store.is_disabled("/b", "python"));
+        assert_eq!(store.reset(None), vec!["python".to_string()]);
+        assert_eq!(store, RuntimeStore::default());
+    }
+
+    #[test]
+    fn save_and_load() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("cache").join(RUNTIMES_FILE);
+        assert_eq!(RuntimeStore::load(&path), RuntimeStore::default());
+
+        let mut store = RuntimeStore::default();
+        store.record("/repo", "git_status", Duration::from_secs(1), THRESHOLD, 1);
+        store.save(&path)?;
+        assert_eq!(RuntimeStore::load(&path), store);
+
+        fs::write(&path, "not json")?;
+        assert_eq!(RuntimeStore::load(&path), RuntimeStore::default());
+        dir.close()
+    }
+
+    fn entry(dir: &str, shell: &str, total_ms: f64, modules: &[(&str, f64)]) -> TimingsEntry {
+        TimingsEntry {
+            time: 0,
+            dir: dir.to_string(),
+            shell: shell.to_string(),
+            total_ms,
+            modules: modules
+                .iter()
+                .map(|(module, ms)| ((*module).to_string(), *ms))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn nearest_rank_percentile() {
+        let samples = (1..=20).map(f64::from).collect::<Vec<_>>();
+        assert_eq!(percentile(&samples, 50), 10.0);
+        assert_eq!(percentile(&samples, 95), 19.0);
+        assert_eq!(percentile(&[7.0], 95), 7.0);
+    }
+
+    #[test]
+    fn report_per_module_dir_and_shell() {
+        let entries = [
+            entry("/a", "zsh", 10.0, &[("git_status", 8.0), ("rust", 1.0)]),
+            entry("/a", "zsh", 30.0, &[("git_status", 28.0)]),
+            entry("/b", "fish", 5.0, &[("rust", 3.0)]),
+        ];
+        let [modules, dirs, shells] = report_tables(&entries);
+
+        assert_eq!(
+            modules,
+            vec![
+                ReportRow {
+                    name: "git_status".to_string(),
+                    samples: 2,
+                    p50: 8.0,
+                    p95: 28.0,
+                },
+                ReportRow {
+                    name: "rust".to_string(),
+                    samples: 2,
+                    p50: 1.0,
+                    p95: 3.0,
+                },
+            ]
+        );
+        assert_eq!(
+            dirs.iter()
+                .map(|row| (row.name.as_str(), row.samples))
+                .collect::<Vec<_>>(),
+            vec![("/a", 2), ("/b", 1)]
+        );
+        assert_eq!(shells[0].name, "zsh");
+        assert_eq!(shells[1].p95, 5.0);
+    }
+
+    #[test]
+    fn timings_log_roundtrip_and_rotation() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(TIMINGS_LOG_FILE);
+        let first = entry("/a", "zsh", 1.0, &[("git_status", 0.5)]);
+        append_timings_log(&path, &first)?;
+        append_timings_log(&path, &first)?;
+        assert_eq!(read_timings_log(&path)?, vec![first.clone(), first.clone()]);
+
+        // Fill the log past its maximum size with a large entry
+        let modules = (0..1000)
+            .map(|i| (format!("custom.module_{i}"), 1.0))
+            .collect::<BTreeMap<_, _>>();
+        let large = TimingsEntry {
+            modules,
+            ..first.clone()
+        };
+        let size = serde_json::to_string(&large).unwrap().len() as u64;
+        let entries = TIMINGS_LOG_MAX_SIZE / size + 2;
+        for _ in 0..entries {
+            append_timings_log(&path, &large)?;
+        }
+        let log = read_timings_log(&path)?;
+        assert!((log.len() as u64) < entries);
+        assert!(fs::metadata(&path)?.len() <= TIMINGS_LOG_MAX_SIZE);
+        assert!(!log.contains(&first));
+        dir.close()
+    }
+
+    #[test]
+    fn chrome_trace_events() {
+        let spans = [
+            Span {
+                category: "module",
+                name: "git_status".to_string(),
+                thread: 2,
+                start: Duration::from_micros(150),
+                duration: Duration::from_millis(3),
+            },
+            Span {
+                category: "exec_cmd",
+                name: "git status".to_string(),
+                thread: 2,
+                start: Duration::from_micros(200),
+                duration: Duration::from_millis(2),
+            },
+        ];
+        let trace = chrome_trace(&spans);
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["name"], "git_status");
+        assert_eq!(events[0]["ph"], "X");
+        assert_eq!(events[0]["ts"], 150);
+        assert_eq!(events[0]["dur"], 3000);
+        assert_eq!(events[1]["cat"], "exec_cmd");
+        assert_eq!(events[2]["ph"], "M");
+        assert_eq!(events[2]["args"]["name"], "rayon worker 1");
+    }
+
+    #[test]
+    fn records_module_spans() {
+        let context: Context = crate::test::ModuleRenderer::new("character").into();
+        crate::modules::handle("character", &context);
+        assert!(context.record_spans().spans().is_empty());
+
+        crate::modules::handle("character", &context);
+        let spans = context.record_spans().spans();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].category, "module");
+        assert_eq!(spans[0].name, "character");
+        assert_eq!(spans[0].thread, 0);
+    }
+}
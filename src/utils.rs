@@ -9,6 +9,7 @@ use std::time::{Duration, Instant};
 
 use crate::context::Context;
 use crate::context::Shell;
+use crate::formatter::string_formatter::shell_prompt_escape;
 
 /// Create a `PathBuf` from an absolute path, where the root directory will be mocked in test
 #[cfg(not(test))]
@@ -130,7 +131,6 @@ impl PartialEq for CommandOutput {
     }
 }
 
-#[cfg(test)]
 pub fn display_command<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
     cmd: T,
     args: &[U],
@@ -592,30 +592,127 @@ pub fn wrap_seq_for_shell(
 ) -> String {
     let (beg, end) = match shell {
         // \[ and \]
-        Shell::Bash => ("\u{5c}\u{5b}", "\u{5c}\u{5d}"),
+        Shell::Bash | Shell::Osh => ("\u{5c}\u{5b}", "\u{5c}\u{5d}"),
+        // The string returned by `renderPrompt` in YSH is passed to readline without the
+        // backslash decoding of PS1, so it needs readline's own \1 and \2 markers
+        Shell::Ysh => ("\u{1}", "\u{2}"),
+        // \1 on both ends, as declared by the `\1\r` prefix of the mksh prompt
+        Shell::Mksh => ("\u{1}", "\u{1}"),
         // %{ and %}
         Shell::Tcsh | Shell::Zsh => ("\u{25}\u{7b}", "\u{25}\u{7d}"),
+        // ksh93 has no markers for zero-width prompt text, so its line editor counts the escape
+        // sequences towards the width of the prompt
         _ => return ansi,
     };
 
     // ANSI escape codes cannot be nested, so we can keep track of whether we're
     // in an escape or not with a single boolean variable
     let mut escaped = false;
-    let final_string: String = ansi
+    let mut final_string = String::with_capacity(ansi.len());
+    let mut pos = 0;
+    while let Some(x) = ansi[pos..].chars().next() {
+        let rest = &ansi[pos..];
+        if let Some(begin) = OSC_SEQUENCES
+            .iter()
+            .find(|begin| x == escape_begin && !escaped && rest.starts_with(**begin))
+        {
+            // OSC sequences end with BEL or ST rather than `escape_end`
+            let len = rest[begin.len()..]
+                .find(['\u{7}', '\u{1b}'])
+                .map_or(rest.len(), |i| {
+                    let terminator = begin.len() + i;
+                    if rest[terminator..].starts_with(ST) {
+                        terminator + ST.len()
+                    } else {
+                        terminator + 1
+                    }
+                });
+            final_string.push_str(beg);
+            final_string.push_str(&rest[..len]);
+            final_string.push_str(end);
+            pos += len;
+            continue;
+        }
+
+        if x == escape_begin && !escaped {
+            escaped = true;
+            final_string.push_str(beg);
+            final_string.push(escape_begin);
+        } else if x == escape_end && escaped {
+            escaped = false;
+            final_string.push(escape_end);
+            final_string.push_str(end);
+        } else {
+            final_string.push(x);
+        }
+        pos += x.len_utf8();
+    }
+    final_string
+}
+
+const OSC8_BEGIN: &str = "\u{1b}]8;";
+const OSC133_BEGIN: &str = "\u{1b}]133;";
+const ST: &str = "\u{1b}\\";
+
+/// OSC sequences that are wrapped as a whole by `wrap_seq_for_shell`
+const OSC_SEQUENCES: &[&str] = &[OSC8_BEGIN, OSC133_BEGIN];
+
+/// Returns the OSC 133 shell integration mark `mark`, e.g. `A` for the start of the prompt
+///
+/// See <https://gitlab.freedesktop.org/Per_Bothner/specifications/blob/master/proposals/semantic-prompts.md>
+pub fn shell_integration_mark(mark: &str) -> String {
+    format!("{OSC133_BEGIN}{mark}\u{7}")
+}
+
+/// Wraps `text` in an OSC 8 hyperlink pointing to `url`
+///
+/// `text` is escaped for the prompt of `shell`. The link is terminated with BEL
+/// instead of ST, as bash would interpret the backslash of ST.
+pub fn hyperlink(url: &str, text: &str, shell: Shell) -> String {
+    let url: String = url
         .chars()
-        .map(|x| {
-            if x == escape_begin && !escaped {
-                escaped = true;
-                format!("{beg}{escape_begin}")
-            } else if x == escape_end && escaped {
-                escaped = false;
-                format!("{escape_end}{end}")
+        .map(|c| {
+            if c.is_ascii_graphic() && !"$`\\\"<>{}|^!".contains(c) {
+                c.to_string()
             } else {
-                x.to_string()
+                percent_encode(c)
             }
         })
         .collect();
-    final_string
+    format!(
+        "{OSC8_BEGIN};{}\u{7}{}{OSC8_BEGIN};\u{7}",
+        shell_prompt_escape(url, shell),
+        shell_prompt_escape(text, shell)
+    )
+}
+
+/// Percent-encodes the characters of `text` that are not safe in a URL path
+pub fn encode_url_path(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-._~/:@&'()*+,;=".contains(c) {
+                c.to_string()
+            } else {
+                percent_encode(c)
+            }
+        })
+        .collect()
+}
+
+fn percent_encode(c: char) -> String {
+    let mut buf = [0; 4];
+    c.encode_utf8(&mut buf)
+        .bytes()
+        .map(|b| format!("%{b:02X}"))
+        .collect()
+}
+
+/// The `file://` URL of `path` on this host
+pub fn file_url(path: &Path) -> String {
+    let host = whoami::fallible::hostname().unwrap_or_default();
+    let path = path.to_string_lossy().replace('\\', "/");
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    format!("file://{host}{separator}{}", encode_url_path(&path))
 }
 
 fn internal_exec_cmd<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
@@ -948,6 +1045,67 @@ mod tests {
         assert_eq!(&bresult3, "\\[OH NO\\]");
         assert_eq!(&bresult4, "herpaderp");
         assert_eq!(&bresult5, "");
+
+        let mresult0 = wrap_seq_for_shell(test0.to_string(), Shell::Mksh, '\x1b', 'm');
+        let mresult4 = wrap_seq_for_shell(test4.to_string(), Shell::Mksh, '\x1b', 'm');
+
+        assert_eq!(&mresult0, "\x01\x1b2m\x01hellomynamekeyes\x01\x1b2m\x01");
+        assert_eq!(&mresult4, "herpaderp");
+
+        let oresult0 = wrap_seq_for_shell(test0.to_string(), Shell::Osh, '\x1b', 'm');
+        assert_eq!(oresult0, bresult0);
+
+        let yresult0 = wrap_seq_for_shell(test0.to_string(), Shell::Ysh, '\x1b', 'm');
+        let yresult4 = wrap_seq_for_shell(test4.to_string(), Shell::Ysh, '\x1b', 'm');
+
+        assert_eq!(&yresult0, "\x01\x1b2m\x02hellomynamekeyes\x01\x1b2m\x02");
+        assert_eq!(&yresult4, "herpaderp");
+
+        let kresult0 = wrap_seq_for_shell(test0.to_string(), Shell::Ksh, '\x1b', 'm');
+        assert_eq!(&kresult0, test0);
+    }
+
+    #[test]
+    fn test_shell_integration_mark_wrappers() {
+        let marks = format!(
+            "{}{}> {}",
+            shell_integration_mark("D;1"),
+            shell_integration_mark("A"),
+            shell_integration_mark("B")
+        );
+        assert_eq!(marks, "\x1b]133;D;1\x07\x1b]133;A\x07> \x1b]133;B\x07");
+        assert_eq!(
+            wrap_colorseq_for_shell(marks.clone(), Shell::Bash),
+            "\\[\x1b]133;D;1\x07\\]\\[\x1b]133;A\x07\\]> \\[\x1b]133;B\x07\\]"
+        );
+        assert_eq!(wrap_colorseq_for_shell(marks.clone(), Shell::Fish), marks);
+    }
+
+    #[test]
+    fn test_hyperlink_wrappers() {
+        let link = hyperlink("file://host/tmp/50%", "tmp", Shell::Zsh);
+        assert_eq!(link, "\x1b]8;;file://host/tmp/50%%\x07tmp\x1b]8;;\x07");
+        assert_eq!(
+            wrap_colorseq_for_shell(link, Shell::Zsh),
+            "%{\x1b]8;;file://host/tmp/50%%\x07%}tmp%{\x1b]8;;\x07%}"
+        );
+
+        let link = hyperlink("https://example.com/$HOME", "a\\b", Shell::Bash);
+        assert_eq!(
+            wrap_colorseq_for_shell(format!("\x1b[1m{link}\x1b[0m"), Shell::Bash),
+            "\\[\x1b[1m\\]\\[\x1b]8;;https://example.com/%24HOME\x07\\]a\\\\b\\[\x1b]8;;\x07\\]\\[\x1b[0m\\]"
+        );
+
+        // Links terminated by ST are wrapped as well
+        assert_eq!(
+            wrap_seq_for_shell(
+                "\x1b]8;;file:///home\x1b\\~\x1b]8;;\x1b\\".to_string(),
+                Shell::Zsh,
+                '\x1b',
+                'm'
+            ),
+            "%{\x1b]8;;file:///home\x1b\\%}~%{\x1b]8;;\x1b\\%}"
+        );
     }
 
     #[test]